@@ -1,13 +1,25 @@
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+use goxlr_profile::{Route, ROUTE_LEVEL_MAX};
+use goxlr_shared::animation::{AnimationMode, WaterfallDirection};
+use goxlr_shared::buttons::{InactiveButtonBehaviour, LightingButtons};
 use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::channels::input::InputChannels;
+use goxlr_shared::channels::mute::MuteActionChannels;
+use goxlr_shared::channels::output::OutputChannels;
 use goxlr_shared::channels::sub_mix::SubMixChannels;
 use goxlr_shared::channels::volume::VolumeChannels;
+use goxlr_shared::colours::Colour;
 use goxlr_shared::compressor::{CompressorAttackTime, CompressorRatio, CompressorReleaseTime};
+use goxlr_shared::encoders::Encoders;
 use goxlr_shared::eq_frequencies::{Frequencies, MiniFrequencies};
 use goxlr_shared::faders::Fader;
 use goxlr_shared::gate::GateTimes;
 use goxlr_shared::microphone::MicrophoneType;
 use goxlr_shared::mute::MuteState;
+use goxlr_shared::scribbles::ScribbleContent;
+use goxlr_shared::submix::Mix;
 
 #[derive(Parser, Debug)]
 #[command(about, version, author)]
@@ -36,10 +48,30 @@ pub enum SubCommands {
         command: ChannelCommands,
     },
 
+    Cough {
+        #[command(subcommand)]
+        command: CoughCommands,
+    },
+
     Pages {
         #[command(subcommand)]
         command: PageCommands,
     },
+
+    Routing {
+        #[command(subcommand)]
+        command: RoutingCommands,
+    },
+
+    Lighting {
+        #[command(subcommand)]
+        command: LightingCommands,
+    },
+
+    Scribbles {
+        #[command(subcommand)]
+        command: ScribbleCommands,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -67,6 +99,78 @@ pub enum ChannelCommands {
         #[command(subcommand)]
         command: SubMixCommands,
     },
+
+    /// Assigns an output to Mix A or Mix B
+    OutputMix {
+        #[arg(value_enum)]
+        output: OutputChannels,
+
+        #[arg(value_enum)]
+        mix: Mix,
+    },
+
+    /// The mix sent to the Headphones, leave empty to follow the Headphones' assignment
+    MonitorMix {
+        #[arg(value_enum)]
+        mix: Option<Mix>,
+    },
+
+    /// Enables Sub Mixing, when disabled all outputs are sent through Mix A
+    SubMixEnabled { enabled: bool },
+
+    MuteTargets {
+        #[arg(value_enum)]
+        channel: MuteActionChannels,
+
+        #[command(subcommand)]
+        command: MuteTargetCommands,
+    },
+}
+
+/// The outputs to mute to, leave empty to mute to all
+#[derive(Debug, Subcommand)]
+pub enum MuteTargetCommands {
+    Press {
+        #[arg(value_enum)]
+        targets: Vec<OutputChannels>,
+    },
+    Hold {
+        #[arg(value_enum)]
+        targets: Vec<OutputChannels>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CoughCommands {
+    Behaviour {
+        #[command(subcommand)]
+        behaviour: CoughBehaviours,
+    },
+
+    Channel {
+        #[arg(value_enum)]
+        channel: MuteActionChannels,
+    },
+
+    MuteTargets {
+        #[command(subcommand)]
+        command: MuteTargetCommands,
+    },
+
+    /// Mutes or Unmutes the cough channel, as if the button were pressed
+    State {
+        #[arg(value_enum)]
+        state: MuteState,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CoughBehaviours {
+    /// Pressing the button toggles the mute
+    Press,
+
+    /// The channel is only muted while the button is held down
+    Hold,
 }
 
 #[derive(Debug, Subcommand)]
@@ -98,6 +202,205 @@ pub enum PageCommands {
     },
 }
 
+#[derive(Debug, Subcommand)]
+pub enum RoutingCommands {
+    /// Enables or Disables a single route
+    Set {
+        #[arg(value_enum)]
+        input: InputChannels,
+        #[arg(value_enum)]
+        output: OutputChannels,
+        enabled: bool,
+    },
+
+    /// Toggles a single route
+    Toggle {
+        #[arg(value_enum)]
+        input: InputChannels,
+        #[arg(value_enum)]
+        output: OutputChannels,
+    },
+
+    /// Sets the level a route is sent at, from 0 to 32 (full volume)
+    Level {
+        #[arg(value_enum)]
+        input: InputChannels,
+        #[arg(value_enum)]
+        output: OutputChannels,
+
+        /// Either a level from 0 to 32, where each step is 1/32 of full volume (so 16 is roughly
+        /// -6 dB), or a gain in decibels such as '-6dB'
+        #[arg(value_parser = parse_route_level, allow_hyphen_values = true)]
+        level: u8,
+    },
+
+    /// Sets all the outputs an input is routed to
+    Input {
+        #[arg(value_enum)]
+        input: InputChannels,
+        #[arg(value_enum)]
+        outputs: Vec<OutputChannels>,
+    },
+
+    /// Sets all the inputs routed to an output
+    Output {
+        #[arg(value_enum)]
+        output: OutputChannels,
+        #[arg(value_enum)]
+        inputs: Vec<InputChannels>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum LightingCommands {
+    /// Colours are in the format RRGGBB
+    Fader {
+        #[arg(value_enum)]
+        channel: FaderChannels,
+        top: Colour,
+        bottom: Colour,
+    },
+
+    Mute {
+        #[arg(value_enum)]
+        channel: FaderChannels,
+
+        #[command(flatten)]
+        colours: ButtonColours,
+    },
+
+    Scribble {
+        #[arg(value_enum)]
+        channel: FaderChannels,
+        colour: Colour,
+    },
+
+    Swear {
+        #[command(flatten)]
+        colours: ButtonColours,
+    },
+
+    Cough {
+        #[command(flatten)]
+        colours: ButtonColours,
+    },
+
+    Button {
+        #[arg(value_enum)]
+        button: LightingButtons,
+
+        #[command(flatten)]
+        colours: ButtonColours,
+    },
+
+    Encoder {
+        #[arg(value_enum)]
+        encoder: Encoders,
+        left: Colour,
+        right: Colour,
+        knob: Colour,
+    },
+
+    /// Copies a channel's fader, mute and scribble colours to all other channels
+    CopyChannel {
+        #[arg(value_enum)]
+        channel: FaderChannels,
+    },
+
+    Theme {
+        #[command(subcommand)]
+        command: ThemeCommands,
+    },
+
+    /// Sets the lighting animation, only supported on newer firmware
+    Animation {
+        #[arg(value_enum)]
+        mode: AnimationMode,
+        mod1: u8,
+        mod2: u8,
+
+        #[arg(value_enum)]
+        waterfall: WaterfallDirection,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ThemeCommands {
+    /// Saves a theme which sets everything to a single colour
+    SaveSolid {
+        name: String,
+        colour: Colour,
+    },
+
+    /// Saves a theme which sets every fader to a gradient
+    SaveGradient {
+        name: String,
+        top: Colour,
+        bottom: Colour,
+    },
+
+    Remove {
+        name: String,
+    },
+    Apply {
+        name: String,
+    },
+}
+
+/// Omitting the value from Text, Label or Image will clear it
+#[derive(Debug, Subcommand)]
+pub enum ScribbleCommands {
+    Text {
+        #[arg(value_enum)]
+        channel: FaderChannels,
+        text: Option<String>,
+    },
+
+    Label {
+        #[arg(value_enum)]
+        channel: FaderChannels,
+        label: Option<char>,
+    },
+
+    /// Relative paths are loaded from the 'icons' directory
+    Image {
+        #[arg(value_enum)]
+        channel: FaderChannels,
+        image: Option<PathBuf>,
+    },
+
+    Inverted {
+        #[arg(value_enum)]
+        channel: FaderChannels,
+        inverted: bool,
+    },
+
+    /// Where the screen's text comes from
+    Content {
+        #[arg(value_enum)]
+        channel: FaderChannels,
+
+        #[arg(value_enum)]
+        content: ScribbleContent,
+    },
+
+    /// Text displayed when the content is 'custom', this isn't saved
+    Push {
+        #[arg(value_enum)]
+        channel: FaderChannels,
+        text: Option<String>,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct ButtonColours {
+    pub active: Colour,
+    pub inactive: Colour,
+
+    #[arg(value_enum)]
+    pub inactive_behaviour: InactiveButtonBehaviour,
+}
+
 #[derive(Debug, Subcommand)]
 pub enum MicrophoneCommands {
     SetUp {
@@ -217,3 +520,19 @@ pub enum MicrophoneEqFullCommands {
         gain: i8,
     },
 }
+
+/// Route levels can be given as the GoXLR's value, or in decibels which are converted to the
+/// nearest level
+fn parse_route_level(value: &str) -> Result<u8, String> {
+    let lower = value.to_lowercase();
+    if let Some(decibels) = lower.strip_suffix("db") {
+        let decibels: f64 = decibels.trim().parse().map_err(|e| format!("{}", e))?;
+        return Ok(Route::level_from_decibels(decibels));
+    }
+
+    let level: u8 = value.parse().map_err(|e| format!("{}", e))?;
+    if level > ROUTE_LEVEL_MAX {
+        return Err(format!("Level must be <= {}", ROUTE_LEVEL_MAX));
+    }
+    Ok(level)
+}
@@ -6,8 +6,12 @@ use goxlr_ipc::clients::ipc::ipc_client::IPCClient;
 
 use crate::cli::{Cli, SubCommands};
 use crate::processors::channel::handle_channels;
+use crate::processors::cough::handle_cough;
+use crate::processors::lighting::handle_lighting;
 use crate::processors::microphone::handle_microphone;
 use crate::processors::pages::handle_pages;
+use crate::processors::routing::handle_routing;
+use crate::processors::scribbles::handle_scribbles;
 
 mod cli;
 mod processors;
@@ -50,9 +54,21 @@ async fn main() -> Result<()> {
             SubCommands::Channels { command } => {
                 handle_channels(serial, client, command).await?;
             }
+            SubCommands::Cough { command } => {
+                handle_cough(serial, client, command).await?;
+            }
             SubCommands::Pages { command } => {
                 handle_pages(serial, client, command).await?;
             }
+            SubCommands::Routing { command } => {
+                handle_routing(serial, client, command).await?;
+            }
+            SubCommands::Lighting { command } => {
+                handle_lighting(serial, client, command).await?;
+            }
+            SubCommands::Scribbles { command } => {
+                handle_scribbles(serial, client, command).await?;
+            }
         }
     }
 
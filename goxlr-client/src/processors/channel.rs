@@ -2,10 +2,12 @@ use anyhow::Result;
 
 use goxlr_ipc::client::Client;
 use goxlr_ipc::commands::channels::ChannelCommands as IPCChannelCommand;
-use goxlr_ipc::commands::channels::{ChannelVolume, MuteCommand};
+use goxlr_ipc::commands::channels::{ChannelVolume, MuteCommand, MuteTargets, OutputMix};
 use goxlr_ipc::commands::{DaemonRequest, DeviceCommand, GoXLRCommand};
+use goxlr_profile::MuteAction;
+use goxlr_shared::channels::output::OutputChannels;
 
-use crate::cli::{ChannelCommands, FaderCommands, VolumeCommands};
+use crate::cli::{ChannelCommands, FaderCommands, MuteTargetCommands, VolumeCommands};
 
 pub async fn handle_channels(
     serial: String,
@@ -34,7 +36,48 @@ pub async fn handle_channels(
             }
         },
         ChannelCommands::SubMix { .. } => {}
+        ChannelCommands::OutputMix { output, mix } => {
+            let command = IPCChannelCommand::OutputMix(OutputMix { output, mix });
+            let command = GoXLRCommand::Channels(command);
+            let command = DaemonRequest::DeviceCommand(DeviceCommand { serial, command });
+
+            client.send(command).await?;
+        }
+        ChannelCommands::MonitorMix { mix } => {
+            let command = IPCChannelCommand::MonitorMix(mix);
+            let command = GoXLRCommand::Channels(command);
+            let command = DaemonRequest::DeviceCommand(DeviceCommand { serial, command });
+
+            client.send(command).await?;
+        }
+        ChannelCommands::SubMixEnabled { enabled } => {
+            let command = IPCChannelCommand::SubMixEnabled(enabled);
+            let command = GoXLRCommand::Channels(command);
+            let command = DaemonRequest::DeviceCommand(DeviceCommand { serial, command });
+
+            client.send(command).await?;
+        }
+        ChannelCommands::MuteTargets { channel, command } => {
+            let (action, targets) = get_mute_targets(command);
+            let command = MuteTargets {
+                channel,
+                action,
+                targets,
+            };
+            let command = IPCChannelCommand::MuteTargets(command);
+            let command = GoXLRCommand::Channels(command);
+            let command = DaemonRequest::DeviceCommand(DeviceCommand { serial, command });
+
+            client.send(command).await?;
+        }
     }
 
     Ok(())
 }
+
+pub fn get_mute_targets(command: MuteTargetCommands) -> (MuteAction, Vec<OutputChannels>) {
+    match command {
+        MuteTargetCommands::Press { targets } => (MuteAction::Press, targets),
+        MuteTargetCommands::Hold { targets } => (MuteAction::Hold, targets),
+    }
+}
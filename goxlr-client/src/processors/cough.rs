@@ -0,0 +1,34 @@
+use anyhow::Result;
+
+use goxlr_ipc::client::Client;
+use goxlr_ipc::commands::cough::CoughCommand;
+use goxlr_ipc::commands::{DaemonRequest, DeviceCommand, GoXLRCommand};
+use goxlr_profile::CoughBehaviour;
+
+use crate::cli::{CoughBehaviours, CoughCommands};
+use crate::processors::channel::get_mute_targets;
+
+pub async fn handle_cough(
+    serial: String,
+    mut client: Box<dyn Client>,
+    command: CoughCommands,
+) -> Result<()> {
+    let command = match command {
+        CoughCommands::Behaviour { behaviour } => CoughCommand::Behaviour(match behaviour {
+            CoughBehaviours::Press => CoughBehaviour::Press,
+            CoughBehaviours::Hold => CoughBehaviour::Hold,
+        }),
+        CoughCommands::Channel { channel } => CoughCommand::Channel(channel),
+        CoughCommands::MuteTargets { command } => {
+            let (action, targets) = get_mute_targets(command);
+            CoughCommand::MuteTargets(action, targets)
+        }
+        CoughCommands::State { state } => CoughCommand::State(state),
+    };
+
+    let command = GoXLRCommand::Cough(command);
+    let command = DaemonRequest::DeviceCommand(DeviceCommand { serial, command });
+    client.send(command).await?;
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use crate::cli::{ButtonColours, LightingCommands, ThemeCommands};
+use anyhow::Result;
+use goxlr_ipc::client::Client;
+use goxlr_ipc::commands::lighting::LightingCommand;
+use goxlr_ipc::commands::{DaemonRequest, DeviceCommand, GoXLRCommand};
+use goxlr_profile::{Animation, ButtonColourSet, EncoderColourSet, FaderColourSet, LightingTheme};
+
+pub async fn handle_lighting(
+    serial: String,
+    mut client: Box<dyn Client>,
+    command: LightingCommands,
+) -> Result<()> {
+    let command = match command {
+        LightingCommands::Fader {
+            channel,
+            top,
+            bottom,
+        } => LightingCommand::FaderColours(
+            channel,
+            FaderColourSet {
+                top_colour: top,
+                bottom_colour: bottom,
+            },
+        ),
+        LightingCommands::Mute { channel, colours } => {
+            LightingCommand::MuteColours(channel, colours.into())
+        }
+        LightingCommands::Scribble { channel, colour } => {
+            LightingCommand::ScribbleColour(channel, colour)
+        }
+        LightingCommands::Swear { colours } => LightingCommand::SwearColours(colours.into()),
+        LightingCommands::Cough { colours } => LightingCommand::CoughColours(colours.into()),
+        LightingCommands::Button { button, colours } => {
+            LightingCommand::ButtonColours(button, colours.into())
+        }
+        LightingCommands::Encoder {
+            encoder,
+            left,
+            right,
+            knob,
+        } => LightingCommand::EncoderColours(
+            encoder,
+            EncoderColourSet {
+                left_colour: left,
+                right_colour: right,
+                knob_colour: knob,
+            },
+        ),
+        LightingCommands::CopyChannel { channel } => LightingCommand::CopyChannelColours(channel),
+        LightingCommands::Theme { command } => match command {
+            ThemeCommands::SaveSolid { name, colour } => {
+                LightingCommand::SaveTheme(name, LightingTheme::Solid(colour))
+            }
+            ThemeCommands::SaveGradient { name, top, bottom } => {
+                LightingCommand::SaveTheme(name, LightingTheme::FaderGradient { top, bottom })
+            }
+            ThemeCommands::Remove { name } => LightingCommand::RemoveTheme(name),
+            ThemeCommands::Apply { name } => LightingCommand::ApplyTheme(name),
+        },
+        LightingCommands::Animation {
+            mode,
+            mod1,
+            mod2,
+            waterfall,
+        } => LightingCommand::Animation(Animation {
+            mode,
+            mod1,
+            mod2,
+            waterfall,
+        }),
+    };
+
+    let command = GoXLRCommand::Lighting(command);
+    let command = DaemonRequest::DeviceCommand(DeviceCommand { serial, command });
+    client.send(command).await?;
+
+    Ok(())
+}
+
+impl From<ButtonColours> for ButtonColourSet {
+    fn from(value: ButtonColours) -> Self {
+        ButtonColourSet {
+            active_colour: value.active,
+            inactive_colour: value.inactive,
+            inactive_behaviour: value.inactive_behaviour,
+        }
+    }
+}
@@ -1,3 +1,7 @@
 pub(crate) mod channel;
+pub(crate) mod cough;
+pub(crate) mod lighting;
 pub(crate) mod microphone;
 pub(crate) mod pages;
+pub(crate) mod routing;
+pub(crate) mod scribbles;
@@ -0,0 +1,41 @@
+use crate::cli::RoutingCommands;
+use anyhow::Result;
+use goxlr_ipc::client::Client;
+use goxlr_ipc::commands::routing::{RoutingCommand, SetRoute, SetRouteLevel};
+use goxlr_ipc::commands::{DaemonRequest, DeviceCommand, GoXLRCommand};
+
+pub async fn handle_routing(
+    serial: String,
+    mut client: Box<dyn Client>,
+    command: RoutingCommands,
+) -> Result<()> {
+    let command = match command {
+        RoutingCommands::Set {
+            input,
+            output,
+            enabled,
+        } => RoutingCommand::SetRoute(SetRoute {
+            input,
+            output,
+            enabled,
+        }),
+        RoutingCommands::Toggle { input, output } => RoutingCommand::ToggleRoute(input, output),
+        RoutingCommands::Level {
+            input,
+            output,
+            level,
+        } => RoutingCommand::SetLevel(SetRouteLevel {
+            input,
+            output,
+            level,
+        }),
+        RoutingCommands::Input { input, outputs } => RoutingCommand::SetInput(input, outputs),
+        RoutingCommands::Output { output, inputs } => RoutingCommand::SetOutput(output, inputs),
+    };
+
+    let command = GoXLRCommand::Routing(command);
+    let command = DaemonRequest::DeviceCommand(DeviceCommand { serial, command });
+    client.send(command).await?;
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use crate::cli::ScribbleCommands;
+use anyhow::Result;
+use goxlr_ipc::client::Client;
+use goxlr_ipc::commands::scribbles::ScribbleCommand;
+use goxlr_ipc::commands::{DaemonRequest, DeviceCommand, GoXLRCommand};
+
+pub async fn handle_scribbles(
+    serial: String,
+    mut client: Box<dyn Client>,
+    command: ScribbleCommands,
+) -> Result<()> {
+    let command = match command {
+        ScribbleCommands::Text { channel, text } => ScribbleCommand::Text(channel, text),
+        ScribbleCommands::Label { channel, label } => ScribbleCommand::Label(channel, label),
+        ScribbleCommands::Image { channel, image } => ScribbleCommand::Image(channel, image),
+        ScribbleCommands::Inverted { channel, inverted } => {
+            ScribbleCommand::Inverted(channel, inverted)
+        }
+        ScribbleCommands::Content { channel, content } => {
+            ScribbleCommand::Content(channel, content)
+        }
+        ScribbleCommands::Push { channel, text } => ScribbleCommand::PushText(channel, text),
+    };
+
+    let command = GoXLRCommand::Scribbles(command);
+    let command = DaemonRequest::DeviceCommand(DeviceCommand { serial, command });
+    client.send(command).await?;
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use log::LevelFilter;
+
+/// Any values set here will override those in the settings file.
+#[derive(Parser, Debug, Clone, Default)]
+#[command(about, version, author)]
+pub struct Cli {
+    /// The location of the Settings File
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+
+    /// Disable the HTTP Server
+    #[arg(long)]
+    pub http_disable: bool,
+
+    /// The Address the HTTP Server should bind to
+    #[arg(long)]
+    pub http_bind_address: Option<String>,
+
+    /// The Port the HTTP Server should listen on
+    #[arg(long)]
+    pub http_port: Option<u16>,
+
+    /// Allow Cross Origin requests from localhost to the HTTP Server
+    #[arg(long)]
+    pub http_enable_cors: bool,
+
+    /// The Path of the IPC Socket (ignored on platforms that use Named Pipes)
+    #[arg(long)]
+    pub socket_path: Option<String>,
+
+    /// The Logging Level (Off, Error, Warn, Info, Debug, Trace)
+    #[arg(long)]
+    pub log_level: Option<LevelFilter>,
+
+    /// The location where Profiles are stored
+    #[arg(long)]
+    pub profile_directory: Option<PathBuf>,
+
+    /// Capture all USB traffic for newly connected devices to this directory
+    #[arg(long)]
+    pub capture_directory: Option<PathBuf>,
+
+    /// Attach a previously captured device, this can be specified multiple times
+    #[arg(long)]
+    pub replay: Vec<PathBuf>,
+}
@@ -2,7 +2,7 @@
    The primary device manager, this is responsible for most of the general workings of the daemon
 */
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::time::{Duration, SystemTime};
 
 use json_patch::diff;
@@ -11,23 +11,33 @@ use tokio::sync::broadcast::Sender;
 use tokio::sync::{mpsc, oneshot};
 use tokio::{join, select, task, time};
 
-use goxlr_ipc::commands::{DaemonResponse, DaemonStatus, GoXLRCommand, GoXLRCommandResponse};
-use goxlr_ipc::status::{Configuration, DeviceStatus};
+use anyhow::{bail, Result};
+use goxlr_ipc::commands::{
+    DaemonCommand, DaemonResponse, DaemonStatus, GoXLRCommand, GoXLRCommandResponse, ProfileType,
+};
+use goxlr_ipc::status::{Configuration, DeviceStatus, InteractionEvent, RuntimeState};
 use goxlr_shared::device::DeviceInfo;
+use goxlr_shared::firmware::{FirmwareUpdateStage as Stage, FirmwareUpdateStatus};
 use goxlr_usb::runners::pnp::PnPDeviceMessage;
 use goxlr_usb::runners::pnp::{start_pnp_runner, PnPConfiguration};
 use goxlr_usb::USBLocation;
 
-use crate::device::device_manager::ManagerMessage::{Execute, GetConfig, GetDevice};
+use crate::device::device_manager::ManagerMessage::{
+    Execute, GetConfig, GetDevice, GetProfileNames, GetState,
+};
 use crate::device::goxlr::device::start_goxlr;
-use crate::device::goxlr::device_config::GoXLRDeviceConfiguration;
+use crate::device::goxlr::device_config::{GoXLRDeviceConfiguration, MicMeterBroadcast};
 use crate::device::messaging::DeviceMessage;
+use crate::profiles::{DeviceProfiles, ProfileStore};
 use crate::servers::http_server::PatchEvent;
+use crate::settings::SettingsHandle;
 use crate::stop::Stop;
 
 struct DeviceManager {
     last_status: DaemonStatus,
     patch_broadcast: Sender<PatchEvent>,
+    mic_meter: MicMeterBroadcast,
+    interactions: Sender<InteractionEvent>,
 
     /// Used for Devices sending messages back to the Manager
     device_receiver: mpsc::Receiver<RunnerMessage>,
@@ -46,6 +56,15 @@ struct DeviceManager {
     /// Currently registered device serials
     serials: HashMap<String, USBLocation>,
 
+    /// The latest status of firmware updates, by serial
+    firmware_updates: BTreeMap<String, FirmwareUpdateStatus>,
+
+    /// Shared access to profiles on disk
+    profile_store: ProfileStore,
+
+    /// The Daemon Settings
+    settings: SettingsHandle,
+
     /// Shutdown Signaller
     shutdown: Stop,
 
@@ -54,13 +73,22 @@ struct DeviceManager {
 }
 
 impl DeviceManager {
-    pub fn new(shutdown: Stop, broadcast_tx: Sender<PatchEvent>) -> Self {
+    pub fn new(
+        shutdown: Stop,
+        broadcast_tx: Sender<PatchEvent>,
+        mic_meter: MicMeterBroadcast,
+        interactions: Sender<InteractionEvent>,
+        profile_store: ProfileStore,
+        settings: SettingsHandle,
+    ) -> Self {
         let (device_sender, device_receiver) = mpsc::channel(128);
         let (update_sender, update_receiver) = mpsc::channel(1);
 
         Self {
             last_status: DaemonStatus::default(),
             patch_broadcast: broadcast_tx,
+            mic_meter,
+            interactions,
 
             device_receiver,
             device_sender,
@@ -71,6 +99,9 @@ impl DeviceManager {
             devices: Default::default(),
             states: HashMap::default(),
             serials: HashMap::default(),
+            firmware_updates: BTreeMap::default(),
+            profile_store,
+            settings,
             shutdown,
             stopping: false,
         }
@@ -121,6 +152,10 @@ impl DeviceManager {
                         RunnerMessage::Error(device) => {
                             self.handle_error(device);
                         },
+                        RunnerMessage::FirmwareUpdate(serial, status) => {
+                            self.firmware_updates.insert(serial, status);
+                            self.update_status().await;
+                        }
                     }
                 },
                 Some(()) = self.update_receiver.recv() => {
@@ -150,6 +185,10 @@ impl DeviceManager {
                         RunnerMessage::Error(device) => {
                             self.handle_error(device);
                         },
+                        RunnerMessage::FirmwareUpdate(serial, status) => {
+                            self.firmware_updates.insert(serial, status);
+                            self.update_status().await;
+                        }
                     }
                     if self.devices_stopped() {
                         break;
@@ -181,6 +220,10 @@ impl DeviceManager {
             update_sender: self.update_sender.clone(),
             manager_sender: self.device_sender.clone(),
             manager_recv,
+            profile_store: self.profile_store.clone(),
+            capture: self.settings.get().await.capture_directory,
+            mic_meter: self.mic_meter.clone(),
+            interactions: self.interactions.clone(),
         };
 
         let state = DeviceState {
@@ -312,7 +355,11 @@ impl DeviceManager {
     }
 
     async fn update_status(&mut self) {
-        let mut status = DaemonStatus::default();
+        let mut status = DaemonStatus {
+            profiles: self.profile_store.get_profile_list().await,
+            firmware_updates: self.firmware_updates.clone(),
+            ..Default::default()
+        };
 
         for (serial, usb) in &self.serials {
             if let Some(device) = self.states.get(usb) {
@@ -326,6 +373,15 @@ impl DeviceManager {
 
                 let profile = cmd_rx.await.ok();
 
+                let (cmd_tx, cmd_rx) = oneshot::channel();
+                let result = device.messenger.send(GetState(cmd_tx)).await;
+                if let Err(e) = result {
+                    warn!("Unable to Fetch Device State: {}", e);
+                    continue;
+                }
+
+                let state = cmd_rx.await.ok();
+
                 let (cmd_tx, cmd_rx) = oneshot::channel();
                 let result = device.messenger.send(GetDevice(cmd_tx)).await;
                 if let Err(e) = result {
@@ -335,8 +391,8 @@ impl DeviceManager {
 
                 let device = cmd_rx.await.ok();
 
-                if profile.is_none() || device.is_none() {
-                    warn!("Error Obtaining Profile or Device");
+                if profile.is_none() || device.is_none() || state.is_none() {
+                    warn!("Error Obtaining Profile, Device or State");
                     continue;
                 }
 
@@ -346,6 +402,7 @@ impl DeviceManager {
                         hardware: device.unwrap(),
                         serial: serial.clone(),
                         config: profile.unwrap(),
+                        state: state.unwrap(),
                     },
                 );
             }
@@ -363,21 +420,32 @@ impl DeviceManager {
         self.last_status = status;
     }
 
-    async fn handle_command(&self, command: DeviceMessage) -> bool {
+    async fn handle_command(&mut self, command: DeviceMessage) -> bool {
         let mut update = false;
 
         match command {
             DeviceMessage::GetStatus(tx) => {
                 let _ = tx.send(self.last_status.clone());
             }
-            DeviceMessage::RunDaemon(_command, tx) => {
-                let _ = tx.send(DaemonResponse::Ok);
+            DeviceMessage::SubscribePatches(tx) => {
+                // The status and patches are updated together, so this ensures that the
+                // subscriber receives every patch made after the status, and only those.
+                let receiver = self.patch_broadcast.subscribe();
+                let _ = tx.send((self.last_status.clone(), receiver));
+            }
+            DeviceMessage::RunDaemon(command, tx) => {
+                let response = match self.handle_daemon_command(command).await {
+                    Ok(()) => DaemonResponse::Ok,
+                    Err(error) => DaemonResponse::Err(error.to_string()),
+                };
+                let _ = tx.send(response);
                 update = true;
             }
             DeviceMessage::RunDevice(serial, command, tx) => {
                 if let Some(usb) = self.serials.get(&*serial) {
                     if let Some(device) = self.states.get(usb) {
                         let (cmd_tx, cmd_rx) = oneshot::channel();
+                        let firmware = matches!(command, GoXLRCommand::Firmware(_));
 
                         let result = device.messenger.send(Execute(command, cmd_tx)).await;
                         if let Err(e) = result {
@@ -388,6 +456,12 @@ impl DeviceManager {
                         let response = cmd_rx.await;
                         match response {
                             Ok(result) => {
+                                // Replace the result of any previous update before replying, so
+                                // the status never shows it as the outcome of this one.
+                                if firmware && matches!(result, GoXLRCommandResponse::Ok) {
+                                    let status = FirmwareUpdateStatus::new(Stage::Start, 0);
+                                    self.firmware_updates.insert(serial.to_string(), status);
+                                }
                                 let _ = tx.send(result);
                             }
                             Err(error) => {
@@ -404,14 +478,86 @@ impl DeviceManager {
         }
         update
     }
+
+    /// Profiles which are active on an attached device can't be deleted or renamed, otherwise
+    /// the device would simply write them back out on the next save.
+    async fn check_not_in_use(&self, profile_type: ProfileType, name: &str) -> Result<()> {
+        for (serial, usb) in &self.serials {
+            let Some(device) = self.states.get(usb) else {
+                continue;
+            };
+
+            let (cmd_tx, cmd_rx) = oneshot::channel();
+            device.messenger.send(GetProfileNames(cmd_tx)).await?;
+            let profiles: DeviceProfiles = cmd_rx.await?;
+
+            let active = match profile_type {
+                ProfileType::Profile => &profiles.profile,
+                ProfileType::MicProfile => &profiles.mic_profile,
+            };
+            if active == name {
+                bail!("Profile {} is in use by {}", name, serial);
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_daemon_command(&self, command: DaemonCommand) -> Result<()> {
+        match command {
+            DaemonCommand::SetLogLevel(level) => {
+                self.settings.update(|s| s.log_level = level).await?;
+            }
+            DaemonCommand::SetHttpSettings(http) => {
+                self.settings.update(|s| s.http = http).await?;
+            }
+            DaemonCommand::SetMicMeterInterval(interval) => {
+                self.settings
+                    .update(|s| s.mic_meter_interval = interval)
+                    .await?;
+            }
+            DaemonCommand::CreateProfile(profile_type, name) => {
+                let store = &self.profile_store;
+                store.create_profile(profile_type, &name).await?;
+            }
+            DaemonCommand::DeleteProfile(profile_type, name) => {
+                self.check_not_in_use(profile_type, &name).await?;
+                let store = &self.profile_store;
+                store.delete_profile(profile_type, &name).await?;
+            }
+            DaemonCommand::RenameProfile(profile_type, from, to) => {
+                self.check_not_in_use(profile_type, &from).await?;
+                let store = &self.profile_store;
+                store.rename_profile(profile_type, &from, &to).await?;
+            }
+            DaemonCommand::RescanProfiles => {
+                self.profile_store.rescan().await;
+            }
+            DaemonCommand::Shutdown => {
+                info!("[DeviceManager] Shutdown Requested");
+                self.shutdown.trigger();
+            }
+        }
+        Ok(())
+    }
 }
 
 pub async fn start_device_manager(
     message_receiver: mpsc::Receiver<DeviceMessage>,
     shutdown: Stop,
     broadcast_tx: Sender<PatchEvent>,
+    mic_meter: MicMeterBroadcast,
+    interactions: Sender<InteractionEvent>,
+    profile_store: ProfileStore,
+    settings: SettingsHandle,
 ) {
-    let mut manager = DeviceManager::new(shutdown, broadcast_tx);
+    let mut manager = DeviceManager::new(
+        shutdown,
+        broadcast_tx,
+        mic_meter,
+        interactions,
+        profile_store,
+        settings,
+    );
     manager.run(message_receiver).await;
 }
 
@@ -419,6 +565,8 @@ pub async fn start_device_manager(
 pub enum ManagerMessage {
     GetConfig(oneshot::Sender<Configuration>),
     GetDevice(oneshot::Sender<DeviceInfo>),
+    GetState(oneshot::Sender<RuntimeState>),
+    GetProfileNames(oneshot::Sender<DeviceProfiles>),
     Execute(GoXLRCommand, oneshot::Sender<GoXLRCommandResponse>),
 }
 
@@ -432,6 +580,7 @@ struct DeviceState {
 pub enum RunnerMessage {
     UpdateState(USBLocation, RunnerState),
     Error(USBLocation),
+    FirmwareUpdate(String, FirmwareUpdateStatus),
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
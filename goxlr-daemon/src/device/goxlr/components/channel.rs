@@ -3,7 +3,9 @@ use goxlr_shared::channels::CanFrom;
 use log::debug;
 
 use crate::device::goxlr::components::has_feature;
+use crate::device::goxlr::components::scribbles::Scribbles;
 use crate::device::goxlr::components::submix::SubMix;
+use goxlr_shared::channels::fader::FaderChannels;
 use goxlr_shared::channels::sub_mix::SubMixChannels;
 use goxlr_shared::channels::volume::VolumeChannels;
 use goxlr_shared::device::GoXLRFeature;
@@ -47,6 +49,10 @@ impl Channels for GoXLR {
         let command = BasicResultCommand::SetVolume(source, volume);
         self.send_no_result(command).await?;
 
+        if FaderChannels::can_from(source) {
+            self.queue_scribble_update(source.into());
+        }
+
         if SubMixChannels::can_from(source) {
             self.sync_sub_mix_volume(source.into()).await?;
         }
@@ -62,6 +68,9 @@ impl Channels for GoXLR {
             let linked_volume = (mix_volume as f64 / linked) as u8;
 
             self.profile.channels.volumes[source] = linked_volume;
+            if FaderChannels::can_from(source) {
+                self.queue_scribble_update(source.into());
+            }
 
             // Bail at this point if Sub Mixes aren't supported.
             if !has_feature(&self.device, GoXLRFeature::SubMix)? {
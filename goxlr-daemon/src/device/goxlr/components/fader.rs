@@ -1,12 +1,10 @@
-use anyhow::{Context, Result};
-use goxlr_scribbles::get_scribble;
+use anyhow::Result;
 use log::debug;
 use strum::IntoEnumIterator;
 
 use goxlr_shared::buttons::Buttons;
 use goxlr_shared::channels::fader::FaderChannels;
 use goxlr_shared::colours::Colour;
-use goxlr_shared::device::{DeviceType, GoXLRFeature};
 use goxlr_shared::faders::Fader;
 use goxlr_shared::mute::MuteState;
 use goxlr_shared::scribbles::Scribble;
@@ -16,6 +14,7 @@ use crate::device::goxlr::components::buttons::ButtonHandlers;
 use crate::device::goxlr::components::load_profile::LoadProfile;
 use crate::device::goxlr::components::mute_handler::MuteHandler;
 use crate::device::goxlr::components::profile::Profile;
+use crate::device::goxlr::components::scribbles::Scribbles;
 use crate::device::goxlr::device::GoXLR;
 
 /// This trait is responsible for the management of faders, everything from the top of the
@@ -24,6 +23,12 @@ use crate::device::goxlr::device::GoXLR;
 pub(crate) trait DeviceFader {
     async fn assign_fader(&mut self, fader: Fader, source: FaderChannels) -> Result<()>;
     async fn update_mute_state(&mut self, source: FaderChannels, state: MuteState) -> Result<()>;
+
+    /// Gets the assigned fader for a source
+    fn get_fader_for_channel(&mut self, source: FaderChannels) -> Option<Fader>;
+
+    /// Applies fader colours based on config
+    async fn set_fader_colours(&mut self, source: FaderChannels, apply: bool) -> Result<()>;
 }
 
 impl DeviceFader for GoXLR {
@@ -81,14 +86,7 @@ impl DeviceFader for GoXLR {
         mute_colours.colour1 = style.mute_colours.active_colour;
         mute_colours.colour2 = style.mute_colours.inactive_colour;
 
-        let device = self.device.as_ref().context("Device Not Found!")?;
-        if device.device_type != DeviceType::Mini {
-            let text = format!("{:?}", source);
-            debug!("Setting Screen Text to {:?}", text);
-            let scribble = get_scribble(None, Some(text), None, false);
-            let command = BasicResultCommand::SetScribble(fader, scribble);
-            self.send_no_result(command).await?;
-        }
+        self.apply_scribble(fader, source).await?;
 
         // Get the button mute state for this channel..
         debug!("Loading Mute button state for {:?}", source);
@@ -102,6 +100,7 @@ impl DeviceFader for GoXLR {
     /// button and fader state.
     async fn update_mute_state(&mut self, source: FaderChannels, state: MuteState) -> Result<()> {
         self.profile.channels.configs[source].mute_state = state;
+        self.queue_scribble_update(source);
         if let Some(button) = self.get_button_for_channel(source) {
             let state = self.get_mute_button_state(source);
             self.button_states.set_state(button, state);
@@ -109,38 +108,13 @@ impl DeviceFader for GoXLR {
         }
         self.set_fader_colours(source, true).await
     }
-}
-
-trait DeviceFaderLocal {
-    /// Gets the assigned fader for a source
-    fn get_fader_for_channel(&mut self, source: FaderChannels) -> Option<Fader>;
-
-    /// Updates colours for a fader if they don't match provided colours (true on change)
-    fn update_colours(&mut self, c1: Colour, c2: Colour, current: Fader) -> bool;
 
-    /// Applies fader colours based on config
-    async fn set_fader_colours(&mut self, source: FaderChannels, apply: bool) -> Result<()>;
-}
-
-impl DeviceFaderLocal for GoXLR {
     fn get_fader_for_channel(&mut self, source: FaderChannels) -> Option<Fader> {
         let current_page = self.profile.pages.current;
         let current_page = &self.profile.pages.page_list[current_page];
         Fader::iter().find(|&fader| current_page.faders[fader] == source)
     }
 
-    fn update_colours(&mut self, c1: Colour, c2: Colour, fader: Fader) -> bool {
-        let current = self.colour_scheme.get_fader_target(fader);
-        if current.colour1 != c1 || current.colour2 != c2 {
-            // We need to refresh our faders
-            current.colour1 = c1;
-            current.colour2 = c2;
-
-            return true;
-        }
-        false
-    }
-
     async fn set_fader_colours(&mut self, source: FaderChannels, apply: bool) -> Result<()> {
         // Now we check whether we should dim the fader..
         if let Some(fader) = self.get_fader_for_channel(source) {
@@ -165,3 +139,22 @@ impl DeviceFaderLocal for GoXLR {
         Ok(())
     }
 }
+
+trait DeviceFaderLocal {
+    /// Updates colours for a fader if they don't match provided colours (true on change)
+    fn update_colours(&mut self, c1: Colour, c2: Colour, current: Fader) -> bool;
+}
+
+impl DeviceFaderLocal for GoXLR {
+    fn update_colours(&mut self, c1: Colour, c2: Colour, fader: Fader) -> bool {
+        let current = self.colour_scheme.get_fader_target(fader);
+        if current.colour1 != c1 || current.colour2 != c2 {
+            // We need to refresh our faders
+            current.colour1 = c1;
+            current.colour2 = c2;
+
+            return true;
+        }
+        false
+    }
+}
@@ -0,0 +1,87 @@
+use std::future::pending;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use log::{debug, info};
+use tokio::fs;
+use tokio::sync::mpsc;
+
+use goxlr_shared::firmware::FirmwareUpdateStatus;
+use goxlr_usb::events::commands::CommandSender;
+
+use crate::device::device_manager::RunnerMessage;
+use crate::device::goxlr::device::GoXLR;
+
+/*
+    The firmware update itself is handled by the USB runner, which takes over the device until
+    it's done, we just hand it the image and pass its progress up to the Device Manager.
+
+    While an update is running, all other IPC commands are refused, and anything the device does
+    periodically (held buttons, scribbles and the mic meter) is paused, as the runner can't answer
+    until the update is done. Once it's finished the runner will stop, and the device will be
+    re-initialised by the manager when it comes back up.
+*/
+
+pub(crate) trait FirmwareUpdate {
+    async fn begin_firmware_update(&mut self, path: &Path) -> Result<()>;
+    async fn on_firmware_status(&mut self, status: FirmwareUpdateStatus);
+    async fn flush_firmware_status(&mut self);
+
+    fn is_updating_firmware(&self) -> bool;
+}
+
+impl FirmwareUpdate for GoXLR {
+    async fn begin_firmware_update(&mut self, path: &Path) -> Result<()> {
+        if self.is_updating_firmware() {
+            bail!("A Firmware Update is already in progress");
+        }
+
+        let image = fs::read(path)
+            .await
+            .with_context(|| format!("Unable to read firmware image {:?}", path))?;
+
+        let sender = self.command_sender.clone();
+        let sender = sender.context("Sender not configured!")?;
+
+        info!("Starting Firmware Update from {:?}", path);
+        let (status_send, status_recv) = mpsc::channel(32);
+        sender
+            .send(CommandSender::UpdateFirmware(image, status_send))
+            .await?;
+
+        self.firmware_update = Some(status_recv);
+        Ok(())
+    }
+
+    async fn on_firmware_status(&mut self, status: FirmwareUpdateStatus) {
+        debug!("Firmware Update: {:?}", status);
+        if status.is_finished() {
+            self.firmware_update = None;
+        }
+
+        if let Some(device) = &self.device {
+            let message = RunnerMessage::FirmwareUpdate(device.serial.clone(), status);
+            self.send_manager_message(message).await;
+        }
+    }
+
+    async fn flush_firmware_status(&mut self) {
+        while let Some(Ok(status)) = self.firmware_update.as_mut().map(|r| r.try_recv()) {
+            self.on_firmware_status(status).await;
+        }
+    }
+
+    fn is_updating_firmware(&self) -> bool {
+        self.firmware_update.is_some()
+    }
+}
+
+/// Waits for the next status from a running update, or forever if there isn't one
+pub(crate) async fn next_firmware_status(
+    receiver: &mut Option<mpsc::Receiver<FirmwareUpdateStatus>>,
+) -> Option<FirmwareUpdateStatus> {
+    match receiver {
+        Some(receiver) => receiver.recv().await,
+        None => pending().await,
+    }
+}
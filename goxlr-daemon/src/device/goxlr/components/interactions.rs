@@ -4,6 +4,7 @@ use anyhow::Result;
 use log::debug;
 use strum::IntoEnumIterator;
 
+use goxlr_ipc::status::Interaction;
 use goxlr_profile::CoughBehaviour;
 use goxlr_shared::buttons::Buttons;
 use goxlr_shared::channels::sub_mix::SubMixChannels;
@@ -14,9 +15,11 @@ use goxlr_shared::mute::MuteState;
 use goxlr_shared::states::State;
 
 use crate::device::goxlr::components::buttons::ButtonHandlers;
+use crate::device::goxlr::components::firmware::FirmwareUpdate;
 use crate::device::goxlr::components::mute_handler::MuteHandler;
 use crate::device::goxlr::components::pages::FaderPages;
 use crate::device::goxlr::components::profile::Profile;
+use crate::device::goxlr::components::scribbles::Scribbles;
 use crate::device::goxlr::components::submix::SubMix;
 use crate::device::goxlr::device::{ButtonState, GoXLR};
 
@@ -34,6 +37,8 @@ pub(crate) trait Interactions {
 impl Interactions for GoXLR {
     async fn on_button_down(&mut self, button: Buttons) -> Result<()> {
         debug!("Button Down: {:?}", button);
+        self.send_interaction(Interaction::ButtonDown(button));
+
         let mut skip_hold = false;
         let skip_release = false;
 
@@ -85,6 +90,8 @@ impl Interactions for GoXLR {
 
     async fn on_button_up(&mut self, button: Buttons) -> Result<()> {
         debug!("Button Up: {:?}", button);
+        self.send_interaction(Interaction::ButtonUp(button));
+
         if let Some(state) = self.button_down_states[button] {
             debug!("{:?}", state);
             if state.skip_release {
@@ -111,8 +118,9 @@ impl Interactions for GoXLR {
                 self.apply_button_states().await?;
             }
             _ => {
-                // TODO: Remove this..
-                self.button_states.set_state(button, State::DimmedColour1);
+                let colours = self.profile.lighting.buttons[button.into()];
+                let state = State::from(colours.inactive_behaviour);
+                self.button_states.set_state(button, state);
                 self.apply_button_states().await?;
             }
         }
@@ -127,6 +135,8 @@ impl Interactions for GoXLR {
 
     async fn on_button_held(&mut self, button: Buttons) -> Result<(bool, bool)> {
         debug!("Button Held: {:?}", button);
+        self.send_interaction(Interaction::ButtonHeld(button));
+
         match button {
             Buttons::FaderA | Buttons::FaderB | Buttons::FaderC | Buttons::FaderD => {
                 // Get the source assigned to this fader..
@@ -151,7 +161,14 @@ impl Interactions for GoXLR {
         let channel = self.profile.pages.page_list[current].faders[fader];
 
         debug!("Fader Moved: {:?} to {:?}", channel, value);
+        self.send_interaction(Interaction::FaderMoved {
+            fader,
+            channel,
+            value,
+        });
+
         self.profile.channels.volumes[channel.into()] = value;
+        self.queue_scribble_update(channel);
 
         // IF SubMix is supported, sync the channel
         if SubMixChannels::can_from(channel) {
@@ -163,6 +180,8 @@ impl Interactions for GoXLR {
 
     async fn on_encoder_change(&mut self, encoder: Encoders, value: i8) -> Result<()> {
         debug!("Encoder {:?} changed to {}", encoder, value);
+        self.send_interaction(Interaction::EncoderChanged { encoder, value });
+
         match encoder {
             Encoders::Pitch => {}
             Encoders::Gender => {}
@@ -174,6 +193,11 @@ impl Interactions for GoXLR {
     }
 
     async fn check_held(&mut self) -> Result<()> {
+        // Hold behaviours send commands, which the device can't accept during an update
+        if self.is_updating_firmware() {
+            return Ok(());
+        }
+
         let hold_time = Duration::from_millis(self.profile.configuration.button_hold_time.into());
         for button in Buttons::iter() {
             if let Some(mut state) = self.button_down_states[button] {
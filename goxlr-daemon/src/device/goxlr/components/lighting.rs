@@ -0,0 +1,351 @@
+use anyhow::{bail, Result};
+use log::debug;
+use strum::IntoEnumIterator;
+
+use goxlr_profile::{Animation, ButtonColourSet, EncoderColourSet, FaderColourSet, LightingTheme};
+use goxlr_shared::buttons::{Buttons, LightingButtons};
+use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::colours::{Colour, ThreeColour, TwoColour, TwoColourTargets};
+use goxlr_shared::device::GoXLRFeature;
+use goxlr_shared::encoders::Encoders;
+use goxlr_shared::faders::Fader;
+use goxlr_shared::scribbles::Scribble;
+use goxlr_shared::states::State;
+
+use crate::device::goxlr::components::buttons::ButtonHandlers;
+use crate::device::goxlr::components::fader::DeviceFader;
+use crate::device::goxlr::components::has_feature;
+use crate::device::goxlr::components::load_profile::LoadProfile;
+use crate::device::goxlr::components::mute_handler::MuteHandler;
+use crate::device::goxlr::device::GoXLR;
+
+/*
+    Colour changes are stored in the profile, then copied into the Colour Scheme and Button States
+    if they're currently visible on the device. The whole scheme is sent to the GoXLR in one go, so
+    we only send it (and the button states) when something has actually changed.
+
+    Themes and bulk changes work the same way, the profile is updated first, then everything
+    visible is resynced from it in one go.
+*/
+
+pub(crate) trait Lighting {
+    async fn set_channel_fader_colours(
+        &mut self,
+        channel: FaderChannels,
+        colours: FaderColourSet,
+    ) -> Result<()>;
+    async fn set_channel_mute_colours(
+        &mut self,
+        channel: FaderChannels,
+        colours: ButtonColourSet,
+    ) -> Result<()>;
+    async fn set_channel_scribble_colour(
+        &mut self,
+        channel: FaderChannels,
+        colour: Colour,
+    ) -> Result<()>;
+    async fn set_swear_colours(&mut self, colours: ButtonColourSet) -> Result<()>;
+    async fn set_cough_colours(&mut self, colours: ButtonColourSet) -> Result<()>;
+    async fn set_button_colours(
+        &mut self,
+        button: LightingButtons,
+        colours: ButtonColourSet,
+    ) -> Result<()>;
+    async fn set_encoder_colours(
+        &mut self,
+        encoder: Encoders,
+        colours: EncoderColourSet,
+    ) -> Result<()>;
+
+    fn save_lighting_theme(&mut self, name: String, theme: LightingTheme) -> Result<()>;
+    fn remove_lighting_theme(&mut self, name: &str) -> Result<()>;
+    async fn apply_lighting_theme(&mut self, name: &str) -> Result<()>;
+    async fn copy_channel_colours(&mut self, source: FaderChannels) -> Result<()>;
+
+    async fn set_animation(&mut self, animation: Animation) -> Result<()>;
+}
+
+impl Lighting for GoXLR {
+    async fn set_channel_fader_colours(
+        &mut self,
+        channel: FaderChannels,
+        colours: FaderColourSet,
+    ) -> Result<()> {
+        self.profile.channels.configs[channel].display.fader_colours = colours;
+        self.set_fader_colours(channel, true).await
+    }
+
+    async fn set_channel_mute_colours(
+        &mut self,
+        channel: FaderChannels,
+        colours: ButtonColourSet,
+    ) -> Result<()> {
+        self.profile.channels.configs[channel].display.mute_colours = colours;
+
+        if let Some(fader) = self.get_fader_for_channel(channel) {
+            let button = Buttons::from_fader(fader);
+            let colour_change = self.update_two_colour(button.into(), colours.into());
+
+            let state = self.get_mute_button_state(channel);
+            let state_change = self.update_button_state(button, state);
+            self.apply_lighting(colour_change, state_change).await?;
+        }
+        Ok(())
+    }
+
+    async fn set_channel_scribble_colour(
+        &mut self,
+        channel: FaderChannels,
+        colour: Colour,
+    ) -> Result<()> {
+        self.profile.channels.configs[channel]
+            .display
+            .screen_display
+            .colour = colour;
+
+        if let Some(fader) = self.get_fader_for_channel(channel) {
+            // Only the first colour is used by the scribble, the second is left alone
+            let target = Scribble::from(fader).into();
+            let current = *self.colour_scheme.get_two_colour_target(target);
+            let colours = TwoColour {
+                colour1: colour,
+                ..current
+            };
+
+            let colour_change = self.update_two_colour(target, colours);
+            self.apply_lighting(colour_change, false).await?;
+        }
+        Ok(())
+    }
+
+    async fn set_swear_colours(&mut self, colours: ButtonColourSet) -> Result<()> {
+        self.profile.swear.colours = colours;
+        self.set_lighting_button(Buttons::Swear, colours).await
+    }
+
+    async fn set_cough_colours(&mut self, colours: ButtonColourSet) -> Result<()> {
+        self.profile.cough.colours = colours;
+
+        let button = Buttons::CoughButton;
+        let colour_change = self.update_two_colour(button.into(), colours.into());
+
+        let state = self.get_cough_button_state();
+        let state_change = self.update_button_state(button, state);
+        self.apply_lighting(colour_change, state_change).await
+    }
+
+    async fn set_button_colours(
+        &mut self,
+        button: LightingButtons,
+        colours: ButtonColourSet,
+    ) -> Result<()> {
+        self.profile.lighting.buttons[button] = colours;
+        self.set_lighting_button(button.into(), colours).await
+    }
+
+    async fn set_encoder_colours(
+        &mut self,
+        encoder: Encoders,
+        colours: EncoderColourSet,
+    ) -> Result<()> {
+        self.profile.lighting.encoders[encoder] = colours;
+
+        let colours = ThreeColour::from(colours);
+        let current = self.colour_scheme.get_encoder_target(encoder);
+        let colour_change = *current != colours;
+        *current = colours;
+
+        self.apply_lighting(colour_change, false).await
+    }
+
+    fn save_lighting_theme(&mut self, name: String, theme: LightingTheme) -> Result<()> {
+        if name.trim().is_empty() {
+            bail!("Theme name cannot be empty");
+        }
+        self.profile.lighting.themes.insert(name, theme);
+        Ok(())
+    }
+
+    fn remove_lighting_theme(&mut self, name: &str) -> Result<()> {
+        if self.profile.lighting.themes.remove(name).is_none() {
+            bail!("Theme {} not found", name);
+        }
+        Ok(())
+    }
+
+    async fn apply_lighting_theme(&mut self, name: &str) -> Result<()> {
+        let Some(theme) = self.profile.lighting.themes.get(name).copied() else {
+            bail!("Theme {} not found", name);
+        };
+
+        debug!("Applying Lighting Theme {}: {:?}", name, theme);
+        let profile = &mut self.profile;
+        match theme {
+            LightingTheme::Solid(colour) => {
+                for channel in FaderChannels::iter() {
+                    let display = &mut profile.channels.configs[channel].display;
+                    display.fader_colours.top_colour = colour;
+                    display.fader_colours.bottom_colour = colour;
+                    display.mute_colours.active_colour = colour;
+                    display.mute_colours.inactive_colour = colour;
+                    display.screen_display.colour = colour;
+                }
+
+                let buttons = profile.lighting.buttons.values_mut();
+                let buttons =
+                    buttons.chain([&mut profile.swear.colours, &mut profile.cough.colours]);
+                for colours in buttons {
+                    colours.active_colour = colour;
+                    colours.inactive_colour = colour;
+                }
+                for colours in profile.lighting.encoders.values_mut() {
+                    colours.left_colour = colour;
+                    colours.right_colour = colour;
+                    colours.knob_colour = colour;
+                }
+            }
+            LightingTheme::FaderGradient { top, bottom } => {
+                for channel in FaderChannels::iter() {
+                    let display = &mut profile.channels.configs[channel].display;
+                    display.fader_colours.top_colour = top;
+                    display.fader_colours.bottom_colour = bottom;
+                }
+            }
+        }
+
+        self.sync_lighting().await
+    }
+
+    async fn copy_channel_colours(&mut self, source: FaderChannels) -> Result<()> {
+        let display = self.profile.channels.configs[source].display.clone();
+        for channel in FaderChannels::iter().filter(|&channel| channel != source) {
+            let target = &mut self.profile.channels.configs[channel].display;
+            target.fader_colours = display.fader_colours;
+            target.mute_colours = display.mute_colours;
+            target.screen_display.colour = display.screen_display.colour;
+        }
+
+        self.sync_lighting().await
+    }
+
+    async fn set_animation(&mut self, animation: Animation) -> Result<()> {
+        if !has_feature(&self.device, GoXLRFeature::Animation)? {
+            bail!("Animations are not supported by this device's firmware");
+        }
+
+        self.profile.lighting.animation = animation;
+        self.apply_animation().await
+    }
+}
+
+trait LightingLocal {
+    /// Sets the colours for a button with no state of its own, it's inactive unless pressed
+    async fn set_lighting_button(
+        &mut self,
+        button: Buttons,
+        colours: ButtonColourSet,
+    ) -> Result<()>;
+
+    /// These update the Colour Scheme and Button States, returning true if anything changed
+    fn update_two_colour(&mut self, target: TwoColourTargets, colours: TwoColour) -> bool;
+    fn update_button_state(&mut self, button: Buttons, state: State) -> bool;
+
+    async fn apply_lighting(&self, colours: bool, states: bool) -> Result<()>;
+
+    /// Rebuilds the Colour Scheme and Button States from the profile, applying any changes
+    async fn sync_lighting(&mut self) -> Result<()>;
+}
+
+impl LightingLocal for GoXLR {
+    async fn set_lighting_button(
+        &mut self,
+        button: Buttons,
+        colours: ButtonColourSet,
+    ) -> Result<()> {
+        let colour_change = self.update_two_colour(button.into(), colours.into());
+
+        // If the button is currently held down, it'll pick up the new behaviour on release
+        let mut state_change = false;
+        if self.button_states.get_state(button) != State::Colour1 {
+            let state = State::from(colours.inactive_behaviour);
+            state_change = self.update_button_state(button, state);
+        }
+        self.apply_lighting(colour_change, state_change).await
+    }
+
+    fn update_two_colour(&mut self, target: TwoColourTargets, colours: TwoColour) -> bool {
+        let current = self.colour_scheme.get_two_colour_target(target);
+        if *current == colours {
+            return false;
+        }
+        current.replace(colours);
+        true
+    }
+
+    fn update_button_state(&mut self, button: Buttons, state: State) -> bool {
+        if self.button_states.get_state(button) == state {
+            return false;
+        }
+        self.button_states.set_state(button, state);
+        true
+    }
+
+    async fn apply_lighting(&self, colours: bool, states: bool) -> Result<()> {
+        if colours {
+            self.apply_colours().await?;
+        }
+        if states {
+            self.apply_button_states().await?;
+        }
+        Ok(())
+    }
+
+    async fn sync_lighting(&mut self) -> Result<()> {
+        let (colours, states) = (self.colour_scheme, self.button_states);
+
+        // Only channels on the current page are visible..
+        let page = self.profile.pages.current;
+        for fader in Fader::iter() {
+            let channel = self.profile.pages.page_list[page].faders[fader];
+            let display = &self.profile.channels.configs[channel].display;
+            let (screen, mute) = (display.screen_display.colour, display.mute_colours);
+
+            self.set_fader_colours(channel, false).await?;
+            let scribble = self
+                .colour_scheme
+                .get_two_colour_target(Scribble::from(fader).into());
+            scribble.colour1 = screen;
+
+            let button = Buttons::from_fader(fader);
+            self.update_two_colour(button.into(), mute.into());
+            let state = self.get_mute_button_state(channel);
+            self.update_button_state(button, state);
+        }
+
+        let cough = self.profile.cough.colours;
+        self.update_two_colour(Buttons::CoughButton.into(), cough.into());
+        let state = self.get_cough_button_state();
+        self.update_button_state(Buttons::CoughButton, state);
+
+        // Buttons with no state of their own are left alone if they're currently pressed
+        let mut buttons = vec![(Buttons::Swear, self.profile.swear.colours)];
+        for button in LightingButtons::iter() {
+            buttons.push((button.into(), self.profile.lighting.buttons[button]));
+        }
+        for (button, colours) in buttons {
+            self.update_two_colour(button.into(), colours.into());
+            if self.button_states.get_state(button) != State::Colour1 {
+                self.update_button_state(button, State::from(colours.inactive_behaviour));
+            }
+        }
+
+        for encoder in Encoders::iter() {
+            let colours = self.profile.lighting.encoders[encoder].into();
+            *self.colour_scheme.get_encoder_target(encoder) = colours;
+        }
+
+        let colour_change = colours != self.colour_scheme;
+        let state_change = states != self.button_states;
+        self.apply_lighting(colour_change, state_change).await
+    }
+}
@@ -4,13 +4,15 @@ use strum::IntoEnumIterator;
 
 use goxlr_profile::CoughBehaviour;
 use goxlr_shared::buttons::Buttons::CoughButton;
+use goxlr_shared::buttons::{Buttons, LightingButtons};
 use goxlr_shared::channels::fader::FaderChannels;
 use goxlr_shared::channels::input::InputChannels;
 use goxlr_shared::channels::output::{OutputChannels, RoutingOutput};
 use goxlr_shared::colours::{ColourScheme, TwoColourTargets};
 use goxlr_shared::device::GoXLRFeature;
+use goxlr_shared::encoders::Encoders;
 use goxlr_shared::mute::MuteState;
-use goxlr_shared::routing::RouteValue;
+use goxlr_shared::states::State;
 use goxlr_usb::events::commands::BasicResultCommand;
 
 use crate::device::goxlr::components::buttons::ButtonHandlers;
@@ -18,19 +20,29 @@ use crate::device::goxlr::components::channel::Channels;
 use crate::device::goxlr::components::has_feature;
 use crate::device::goxlr::components::mute_handler::{MuteHandler, MuteHandlerCrate};
 use crate::device::goxlr::components::pages::FaderPages;
+use crate::device::goxlr::components::persistence::ProfilePersistence;
 use crate::device::goxlr::components::routing_handler::RoutingHandler;
+use crate::device::goxlr::components::submix::SubMix;
 use crate::device::goxlr::device::GoXLR;
 
 /// This trait contains all public methods needed to successfully load a profile, and are implemented
-/// for the GoXLR type immediately after. This code assumes that self.profile is accurate.
+/// for the GoXLR type immediately after. `load_profile` will fetch the last active profile from
+/// disk, whereas `apply_profile` assumes that self.profile is accurate.
 pub(crate) trait LoadProfile {
     async fn load_profile(&mut self) -> Result<()>;
+    async fn apply_profile(&mut self) -> Result<()>;
 
     async fn apply_colours(&self) -> Result<()>;
+    async fn apply_animation(&self) -> Result<()>;
 }
 
 impl LoadProfile for GoXLR {
     async fn load_profile(&mut self) -> Result<()> {
+        self.read_profile().await?;
+        self.apply_profile().await
+    }
+
+    async fn apply_profile(&mut self) -> Result<()> {
         debug!("Beginning Profile Load");
         // These are setup methods, to do any pre-profile handling and setup..
         self.setup_routing();
@@ -43,13 +55,15 @@ impl LoadProfile for GoXLR {
         // Load the Mute States..
         self.load_mute_states().await?;
 
-        // Apply the volumes..
+        // Apply the volumes, and which mix each output is on..
         self.load_volumes().await?;
+        self.load_sub_mix_assignments().await?;
 
         // Finalise things setup earlier
         self.apply_button_states().await?;
 
         self.load_colours().await?;
+        self.apply_animation().await?;
         self.apply_routing().await?;
 
         debug!("Completed Profile Load");
@@ -61,6 +75,23 @@ impl LoadProfile for GoXLR {
         let command = BasicResultCommand::SetColour(self.colour_scheme);
         self.send_no_result(command).await
     }
+
+    async fn apply_animation(&self) -> Result<()> {
+        // Older firmware doesn't know about animations, so there's nothing to send
+        if !has_feature(&self.device, GoXLRFeature::Animation)? {
+            return Ok(());
+        }
+
+        debug!("Applying Animation..");
+        let animation = self.profile.lighting.animation;
+        let command = BasicResultCommand::SetAnimation(
+            animation.mode,
+            animation.mod1,
+            animation.mod2,
+            animation.waterfall,
+        );
+        self.send_no_result(command).await
+    }
 }
 
 /// This trait contains methods which are local to this mod. Traits require an attached scope to
@@ -89,10 +120,7 @@ impl LoadProfileLocal for GoXLR {
 
         for channel in InputChannels::iter() {
             for output in OutputChannels::iter() {
-                let value = match self.profile.routing[channel][output] {
-                    true => RouteValue::On,
-                    false => RouteValue::Off,
-                };
+                let value = self.profile.routing[channel][output].value();
 
                 let output = RoutingOutput::from(output);
 
@@ -129,6 +157,16 @@ impl LoadProfileLocal for GoXLR {
 
         let cough_state = self.get_cough_button_state();
         self.button_states.set_state(CoughButton, cough_state);
+
+        // Everything else simply sits in its inactive state
+        let swear_state = State::from(self.profile.swear.colours.inactive_behaviour);
+        self.button_states.set_state(Buttons::Swear, swear_state);
+
+        for button in LightingButtons::iter() {
+            let behaviour = self.profile.lighting.buttons[button].inactive_behaviour;
+            self.button_states
+                .set_state(button.into(), State::from(behaviour));
+        }
     }
 
     async fn load_volumes(&mut self) -> Result<()> {
@@ -167,6 +205,20 @@ impl LoadProfileLocal for GoXLR {
         swear_button.colour1 = self.profile.swear.colours.active_colour;
         swear_button.colour2 = self.profile.swear.colours.inactive_colour;
 
+        // And the remaining Buttons and Encoders..
+        for button in LightingButtons::iter() {
+            let target = Buttons::from(button).into();
+            let colours = self.profile.lighting.buttons[button].into();
+            self.colour_scheme
+                .get_two_colour_target(target)
+                .replace(colours);
+        }
+
+        for encoder in Encoders::iter() {
+            let colours = self.profile.lighting.encoders[encoder].into();
+            *self.colour_scheme.get_encoder_target(encoder) = colours;
+        }
+
         self.apply_colours().await
     }
 
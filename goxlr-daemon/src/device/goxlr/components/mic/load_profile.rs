@@ -7,14 +7,21 @@ use crate::device::goxlr::components::mic::compressor::CompressorCrate;
 use crate::device::goxlr::components::mic::eq::MicEqCrate;
 use crate::device::goxlr::components::mic::gate::GateCrate;
 use crate::device::goxlr::components::mic::mic_type::MicTypeCrate;
+use crate::device::goxlr::components::persistence::ProfilePersistence;
 use crate::device::goxlr::device::GoXLR;
 
 pub trait LoadMicProfile {
     async fn load_mic_profile(&mut self) -> Result<()>;
+    async fn apply_mic_profile(&mut self) -> Result<()>;
 }
 
 impl LoadMicProfile for GoXLR {
     async fn load_mic_profile(&mut self) -> Result<()> {
+        self.read_mic_profile().await?;
+        self.apply_mic_profile().await
+    }
+
+    async fn apply_mic_profile(&mut self) -> Result<()> {
         self.apply_mic_gain().await?;
 
         let mut mic_params = LinkedHashMap::new();
@@ -0,0 +1,140 @@
+/*
+   The Microphone level meter. Fetching the level is a round trip to the device, so this is only
+   sampled while at least one client is subscribed to the broadcast, and the state is discarded
+   when everyone leaves.
+
+   The raw level is pretty jumpy, so alongside it we provide a peak which is held for a short time
+   before falling away, and an RMS level which is smoothed over the last few hundred ms.
+*/
+
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use goxlr_ipc::status::MicLevelMeter;
+use goxlr_usb::events::commands::CommandSender;
+use tokio::sync::oneshot;
+
+use crate::device::goxlr::components::firmware::FirmwareUpdate;
+use crate::device::goxlr::device::GoXLR;
+
+// The device reports levels between -72.2dB and 0dB
+const METER_FLOOR: f64 = -72.2;
+
+// We can't sample any faster than the device ticker
+const MIN_INTERVAL: u64 = 20;
+
+// How long a peak is held before it starts falling, and how fast (in dB/s) it falls
+const PEAK_HOLD: Duration = Duration::from_millis(1500);
+const PEAK_FALL_RATE: f64 = 20.;
+
+// The time constant for the RMS smoothing
+const RMS_WINDOW: Duration = Duration::from_millis(300);
+
+pub(crate) trait MicMeter {
+    async fn get_mic_level(&self) -> Result<f64>;
+    async fn sample_mic_level(&mut self) -> Result<()>;
+}
+
+impl MicMeter for GoXLR {
+    async fn get_mic_level(&self) -> Result<f64> {
+        let (msg_send, msg_receive) = oneshot::channel();
+
+        if let Some(sender) = self.command_sender.clone() {
+            let command = CommandSender::GetMicLevel(msg_send);
+            let _ = sender.send(command).await;
+
+            return msg_receive.await?;
+        }
+        bail!("Sender Failure");
+    }
+
+    async fn sample_mic_level(&mut self) -> Result<()> {
+        // If nobody is listening, there's no need to bother the device. During a firmware update
+        // the runner won't answer until it's done, so waiting on it would stall everything else.
+        if self.mic_meter.sender.receiver_count() == 0 || self.is_updating_firmware() {
+            self.mic_meter_state = None;
+            return Ok(());
+        }
+
+        let interval = self.mic_meter.interval.load(Ordering::Relaxed);
+        let interval = Duration::from_millis(interval.max(MIN_INTERVAL));
+        if let Some(state) = &self.mic_meter_state {
+            if state.last_sample.elapsed() < interval {
+                return Ok(());
+            }
+        }
+
+        let level = self.get_mic_level().await?;
+        let now = Instant::now();
+
+        let state = self
+            .mic_meter_state
+            .get_or_insert_with(|| MeterState::new(level, now));
+        let (peak, rms) = state.update(level, now);
+
+        let serial = self
+            .device
+            .as_ref()
+            .context("Missing Device")?
+            .serial
+            .clone();
+        let meter = MicLevelMeter {
+            serial,
+            level,
+            peak,
+            rms,
+        };
+
+        // If the last subscriber has just gone, this will fail, we'll tidy up on the next tick.
+        let _ = self.mic_meter.sender.send(meter);
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct MeterState {
+    last_sample: Instant,
+
+    peak: f64,
+    peak_time: Instant,
+
+    // The smoothed mean of the squared (linear) level
+    mean_square: f64,
+}
+
+impl MeterState {
+    fn new(level: f64, now: Instant) -> Self {
+        Self {
+            last_sample: now,
+            peak: level,
+            peak_time: now,
+            mean_square: to_power(level),
+        }
+    }
+
+    /// Adds a new sample, and returns the current peak and RMS levels
+    fn update(&mut self, level: f64, now: Instant) -> (f64, f64) {
+        let elapsed = now.duration_since(self.last_sample).as_secs_f64();
+        self.last_sample = now;
+
+        // Exponentially weighted, so the window doesn't depend on the sample rate
+        let weight = 1. - (-elapsed / RMS_WINDOW.as_secs_f64()).exp();
+        self.mean_square += (to_power(level) - self.mean_square) * weight;
+
+        if level >= self.peak {
+            self.peak = level;
+            self.peak_time = now;
+        } else if now.duration_since(self.peak_time) > PEAK_HOLD {
+            let peak = self.peak - (PEAK_FALL_RATE * elapsed);
+            self.peak = peak.max(level);
+        }
+
+        let rms = (10. * self.mean_square.log10()).clamp(METER_FLOOR, 0.);
+        (self.peak, rms)
+    }
+}
+
+fn to_power(level: f64) -> f64 {
+    10_f64.powf(level / 10.)
+}
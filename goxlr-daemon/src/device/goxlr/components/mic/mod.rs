@@ -3,4 +3,5 @@ pub mod eq;
 pub mod extra;
 pub mod gate;
 pub mod load_profile;
+pub mod meter;
 pub mod mic_type;
@@ -5,13 +5,18 @@ use goxlr_shared::device::{DeviceInfo, GoXLRFeature};
 pub(crate) mod buttons;
 pub(crate) mod channel;
 pub(crate) mod fader;
+pub(crate) mod firmware;
 pub(crate) mod interactions;
+pub(crate) mod lighting;
 pub(crate) mod load_profile;
 pub(crate) mod mic;
 pub(crate) mod mute_handler;
 pub(crate) mod pages;
+pub(crate) mod persistence;
 pub(crate) mod profile;
+pub(crate) mod routing;
 pub(crate) mod routing_handler;
+pub(crate) mod scribbles;
 pub(crate) mod submix;
 
 pub fn has_feature(device: &Option<DeviceInfo>, feature: GoXLRFeature) -> Result<bool> {
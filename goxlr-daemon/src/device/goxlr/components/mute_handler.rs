@@ -1,9 +1,9 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use log::debug;
 use ritelinked::LinkedHashMap;
 use strum::IntoEnumIterator;
 
-use goxlr_profile::MuteAction;
+use goxlr_profile::{CoughBehaviour, MuteAction};
 use goxlr_shared::buttons::Buttons::CoughButton;
 use goxlr_shared::channels::fader::FaderChannels;
 use goxlr_shared::channels::input::InputChannels;
@@ -20,6 +20,7 @@ use goxlr_usb::events::commands::BasicResultCommand;
 use crate::device::goxlr::components::buttons::ButtonHandlers;
 use crate::device::goxlr::components::fader::DeviceFader;
 use crate::device::goxlr::components::routing_handler::RoutingHandler;
+use crate::device::goxlr::components::scribbles::Scribbles;
 use crate::device::goxlr::device::GoXLR;
 
 type Source = FaderChannels;
@@ -29,7 +30,16 @@ type Target = Vec<OutputChannels>;
 pub(crate) trait MuteHandler {
     /// Programmatically Setting the mute states..
     async fn set_mute_state(&mut self, source: Source, state: MuteState) -> Result<()>;
-    //async fn sync_mute_state(&mut self, source: Source) -> Result<()>;
+    async fn sync_mute_state(&mut self, source: Source) -> Result<()>;
+
+    /// Changing the targets of a Mute Action, these are re-synced if the action is active
+    async fn set_mute_targets(
+        &mut self,
+        source: MuteSource,
+        action: MuteAction,
+        targets: Target,
+    ) -> Result<()>;
+    async fn set_cough_mute_targets(&mut self, action: MuteAction, targets: Target) -> Result<()>;
 
     /// Used for button handling..
     async fn handle_mute_press(&mut self, source: Source) -> Result<()>;
@@ -39,6 +49,11 @@ pub(crate) trait MuteHandler {
     /// Used for the Cough Buttons..
     async fn handle_cough_press(&mut self, hold: bool) -> Result<()>;
 
+    /// Configuring the Cough Button..
+    async fn set_cough_behaviour(&mut self, behaviour: CoughBehaviour) -> Result<()>;
+    async fn set_cough_channel(&mut self, channel: MuteSource) -> Result<()>;
+    async fn set_cough_state(&mut self, state: MuteState) -> Result<()>;
+
     /// Returns the Button state for a mute button..
     fn get_mute_button_state(&self, source: Source) -> State;
 
@@ -47,6 +62,10 @@ pub(crate) trait MuteHandler {
 
     /// Returns whether a current source is 'Muted to All'
     fn is_muted_to_all(&self, source: Source) -> bool;
+
+    /// Returns the outputs a source is currently muted to via routing, this will be empty if
+    /// the source is unmuted, or muted to all (which is handled by the channel mute).
+    fn get_active_mute_targets(&self, source: Source) -> Target;
 }
 
 impl MuteHandler for GoXLR {
@@ -97,11 +116,7 @@ impl MuteHandler for GoXLR {
         let action = MuteAction::from(state);
 
         let targets = match MuteSource::can_from(source) {
-            true => {
-                let source = source.into();
-                let base = self.profile.channels.mute_actions[source].actions[action].clone();
-                self.add_cough_mute(source, Some(base)).unwrap_or_default()
-            }
+            true => self.get_targets_for_action(source.into(), action),
             false => vec![],
         };
 
@@ -114,9 +129,78 @@ impl MuteHandler for GoXLR {
 
     /// This is generally called when either a channels mute target list changes, or there's some
     /// other change to the transient routing. It's goal is to resync the state.
-    // async fn sync_mute_state(&mut self, source: FaderSources) -> Result<()> {
-    //     todo!()
-    // }
+    async fn sync_mute_state(&mut self, source: Source) -> Result<()> {
+        // Work out where we should be muted to right now, None meaning not muted at all
+        let state = self.profile.channels.configs[source].mute_state;
+        let targets = match (state, MuteSource::can_from(source)) {
+            (MuteState::Unmuted, true) => self.add_cough_mute(source.into(), None),
+            (MuteState::Unmuted, false) => None,
+            (state, true) => Some(self.get_targets_for_action(source.into(), state.into())),
+            (_, false) => Some(vec![]),
+        };
+
+        // Grab the current routing, so we only send it if something actually changes
+        let input = match GoXLR::is_valid_routing_target(source) {
+            true => Some(InputChannels::from(source)),
+            false => None,
+        };
+        let original = input.map(|input| self.get_routing_input_row(input));
+
+        // Only touch the channel mute when switching between 'to all' and 'to targets', so we
+        // don't needlessly toggle the Microphone's mute effect.
+        let muted = self.mute_state[source] == Some(Muted);
+        match targets {
+            Some(targets) if targets.is_empty() => {
+                if !muted {
+                    self.mute_to_all(source).await?;
+                }
+                self.restore_routing_from_profile(source)?;
+            }
+            Some(targets) => {
+                if muted {
+                    self.unmute(source).await?;
+                }
+                self.mute_to_targets(source, targets).await?;
+            }
+            None => {
+                if muted {
+                    self.unmute(source).await?;
+                }
+                self.restore_routing_from_profile(source)?;
+            }
+        }
+
+        if let Some(input) = input {
+            if original != Some(self.get_routing_input_row(input)) {
+                let changes = MuteChanges {
+                    routing: vec![input],
+                };
+                self.apply_mute_changes(changes).await?;
+            }
+        }
+
+        // Moving in or out of 'Mute to All' affects the fader colours
+        self.set_fader_colours(source, true).await?;
+        self.queue_scribble_update(source);
+        Ok(())
+    }
+
+    async fn set_mute_targets(
+        &mut self,
+        source: MuteSource,
+        action: MuteAction,
+        targets: Target,
+    ) -> Result<()> {
+        self.profile.channels.mute_actions[source].actions[action] = dedup_targets(targets);
+        self.sync_mute_state(source.into()).await
+    }
+
+    async fn set_cough_mute_targets(&mut self, action: MuteAction, targets: Target) -> Result<()> {
+        self.profile.cough.mute_actions[action] = dedup_targets(targets);
+
+        let source = self.profile.cough.channel_assignment;
+        self.sync_mute_state(source.into()).await
+    }
 
     /// Code which triggers when a channel is changed to a 'Pressed' state, primarily it'll
     /// either unmute the channel if it's muted, or will mute to targets in the base state.
@@ -230,6 +314,43 @@ impl MuteHandler for GoXLR {
         Ok(())
     }
 
+    async fn set_cough_behaviour(&mut self, behaviour: CoughBehaviour) -> Result<()> {
+        if self.profile.cough.cough_behaviour == behaviour {
+            return Ok(());
+        }
+        self.profile.cough.cough_behaviour = behaviour;
+
+        // A latched cough would be inverted by the new behaviour, so start from unmuted
+        self.set_cough_state(MuteState::Unmuted).await
+    }
+
+    async fn set_cough_channel(&mut self, channel: MuteSource) -> Result<()> {
+        let previous = self.profile.cough.channel_assignment;
+        if previous == channel {
+            return Ok(());
+        }
+        self.profile.cough.channel_assignment = channel;
+
+        // If the cough is active, the mute needs to move from the old channel to the new one
+        self.sync_mute_state(previous.into()).await?;
+        self.sync_mute_state(channel.into()).await
+    }
+
+    async fn set_cough_state(&mut self, state: MuteState) -> Result<()> {
+        let behaviour = self.profile.cough.cough_behaviour;
+        if behaviour == CoughBehaviour::Hold && state == MuteState::Held {
+            bail!("Cough cannot be Held when using the Hold behaviour");
+        }
+        self.profile.cough.mute_state = state;
+
+        let source = self.profile.cough.channel_assignment.into();
+        self.sync_mute_state(source).await?;
+
+        let cough_state = self.get_cough_button_state();
+        self.button_states.set_state(CoughButton, cough_state);
+        self.apply_button_states().await
+    }
+
     fn get_mute_button_state(&self, source: Source) -> State {
         let channel = self.profile.channels.configs[source].clone();
 
@@ -280,6 +401,18 @@ impl MuteHandler for GoXLR {
             false => true,
         }
     }
+
+    fn get_active_mute_targets(&self, source: Source) -> Target {
+        if !MuteSource::can_from(source) {
+            return vec![];
+        }
+
+        let state = self.profile.channels.configs[source].mute_state;
+        match state {
+            MuteState::Unmuted => self.add_cough_mute(source.into(), None).unwrap_or_default(),
+            _ => self.get_targets_for_action(source.into(), MuteAction::from(state)),
+        }
+    }
 }
 
 pub(crate) trait MuteHandlerCrate {
@@ -456,6 +589,7 @@ impl MuteHandlerLocal for GoXLR {
 
         // Either way, replace the mute state in the struct with our new state.
         self.mute_state[source].replace(state);
+        self.queue_scribble_update(source);
         Ok(())
     }
 
@@ -541,11 +675,11 @@ impl MuteHandlerLocal for GoXLR {
 
                 // Because muting will never affect a routing value that's set to 'Off', we don't
                 // need to worry too much about handling false here.
-                if profile_value {
+                if profile_value.enabled {
                     // Compare it against the routing table..
                     match active {
                         RouteValue::Off => {
-                            if self.set_route(source, route, RouteValue::On)? {
+                            if self.set_route(source, route, profile_value.value())? {
                                 debug!("Removing Transient Mute {:?} to {:?}", source, route);
                                 if !updated_routes.contains(&source) {
                                     updated_routes.push(source);
@@ -567,6 +701,17 @@ impl MuteHandlerLocal for GoXLR {
     }
 }
 
+/// Removes any duplicate targets, while keeping them in the order they were provided
+fn dedup_targets(targets: Target) -> Target {
+    let mut unique = Vec::with_capacity(targets.len());
+    for target in targets {
+        if !unique.contains(&target) {
+            unique.push(target);
+        }
+    }
+    unique
+}
+
 /// This structure provides a list of things which have been changed by the mute commands,
 /// generally speaking, they'll be followed up by applying them!
 #[derive(Default)]
@@ -2,6 +2,7 @@ use anyhow::{bail, Result};
 use log::{debug, warn};
 use strum::IntoEnumIterator;
 
+use goxlr_ipc::status::Interaction;
 use goxlr_profile::FaderPage;
 use goxlr_shared::channels::fader::FaderChannels;
 use goxlr_shared::faders::Fader;
@@ -101,6 +102,7 @@ impl FaderPages for GoXLR {
         }
         debug!("Changing Fader Page to {}", page);
         self.profile.pages.current = page;
+        self.send_interaction(Interaction::PageChanged(page));
         self.load_current_page(true).await
     }
 
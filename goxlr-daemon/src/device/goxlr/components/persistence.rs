@@ -0,0 +1,125 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use log::{debug, info, warn};
+
+use goxlr_ipc::commands::mic::MicrophoneCommand;
+use goxlr_ipc::commands::scribbles::ScribbleCommand;
+use goxlr_ipc::commands::GoXLRCommand;
+use goxlr_profile::{MicProfile, Profile};
+
+use crate::device::goxlr::device::GoXLR;
+
+/// How long we wait after the last change before writing the profiles to disk, this prevents
+/// us hammering the disk while someone is sliding a fader up and down.
+const SAVE_DELAY: Duration = Duration::from_secs(1);
+
+/// This handles reading and writing the device's profiles from the Profile Store. Reads are
+/// only performed when loading a profile, while writes are queued up and performed once things
+/// have settled down.
+pub(crate) trait ProfilePersistence {
+    async fn read_profile(&mut self) -> Result<()>;
+    async fn read_mic_profile(&mut self) -> Result<()>;
+
+    fn queue_save(&mut self);
+    async fn save_if_due(&mut self);
+    async fn save_profiles(&mut self) -> Result<()>;
+}
+
+impl ProfilePersistence for GoXLR {
+    async fn read_profile(&mut self) -> Result<()> {
+        let serial = self.get_serial()?;
+        let names = self.profile_store.get_device_profiles(&serial).await;
+
+        let name = &names.profile;
+        self.profile = match self.profile_store.load_profile(name).await? {
+            Some(profile) => {
+                debug!("Loaded Profile '{}' for {}", name, serial);
+                profile
+            }
+            None => {
+                info!("Profile '{}' not found, creating from defaults", name);
+                self.queue_save();
+                Profile::default()
+            }
+        };
+
+        self.profile_names.profile = names.profile;
+        Ok(())
+    }
+
+    async fn read_mic_profile(&mut self) -> Result<()> {
+        let serial = self.get_serial()?;
+        let names = self.profile_store.get_device_profiles(&serial).await;
+
+        let name = &names.mic_profile;
+        self.mic_profile = match self.profile_store.load_mic_profile(name).await? {
+            Some(profile) => {
+                debug!("Loaded Mic Profile '{}' for {}", name, serial);
+                profile
+            }
+            None => {
+                info!("Mic Profile '{}' not found, creating from defaults", name);
+                self.queue_save();
+                MicProfile::default()
+            }
+        };
+
+        self.profile_names.mic_profile = names.mic_profile;
+        Ok(())
+    }
+
+    fn queue_save(&mut self) {
+        // Every change pushes the save back, we only write once nothing has changed for a while.
+        self.save_deadline = Some(Instant::now() + SAVE_DELAY);
+    }
+
+    async fn save_if_due(&mut self) {
+        if let Some(deadline) = self.save_deadline {
+            if Instant::now() >= deadline {
+                if let Err(error) = self.save_profiles().await {
+                    warn!("Unable to Save Profiles: {}", error);
+                }
+            }
+        }
+    }
+
+    async fn save_profiles(&mut self) -> Result<()> {
+        // Clear the deadline first, if this fails there's not much value in retrying every tick.
+        self.save_deadline = None;
+
+        let serial = self.get_serial()?;
+        let names = self.profile_names.clone();
+
+        let store = &self.profile_store;
+        store.save_profile(&names.profile, &self.profile).await?;
+        store
+            .save_mic_profile(&names.mic_profile, &self.mic_profile)
+            .await?;
+        store.set_device_profiles(&serial, names).await?;
+
+        Ok(())
+    }
+}
+
+trait ProfilePersistenceLocal {
+    fn get_serial(&self) -> Result<String>;
+}
+
+impl ProfilePersistenceLocal for GoXLR {
+    fn get_serial(&self) -> Result<String> {
+        let device = self.device.as_ref().context("Device not Configured!")?;
+        Ok(device.serial.clone())
+    }
+}
+
+/// Returns whether a command is expected to change the profile, anything that's simply
+/// fetching data from the device doesn't need to trigger a save.
+pub(crate) fn is_state_changing(command: &GoXLRCommand) -> bool {
+    !matches!(
+        command,
+        GoXLRCommand::Microphone(MicrophoneCommand::GetMicLevel)
+            | GoXLRCommand::Scribbles(ScribbleCommand::PushText(..))
+            | GoXLRCommand::Firmware(_)
+    )
+}
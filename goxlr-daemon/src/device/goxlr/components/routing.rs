@@ -0,0 +1,112 @@
+use anyhow::{bail, Result};
+use strum::IntoEnumIterator;
+
+use goxlr_profile::ROUTE_LEVEL_MAX;
+use goxlr_shared::channels::input::InputChannels;
+use goxlr_shared::channels::output::{OutputChannels, RoutingOutput};
+use goxlr_shared::routing::RoutingTable;
+
+use crate::device::goxlr::components::mute_handler::MuteHandler;
+use crate::device::goxlr::components::routing_handler::RoutingHandler;
+use crate::device::goxlr::device::GoXLR;
+
+type In = InputChannels;
+type Out = OutputChannels;
+
+/*
+    These are the user facing routing changes, they update the routing in the profile and then
+    rebuild the routing table from it. The routing table isn't always a direct copy of the profile,
+    channels which are muted to specific targets do so by disabling the routes to those targets,
+    so once the profile has been applied, any active mutes are re-applied on top.
+*/
+
+pub(crate) trait Routing {
+    async fn set_routing(&mut self, input: In, output: Out, enabled: bool) -> Result<()>;
+    async fn toggle_routing(&mut self, input: In, output: Out) -> Result<()>;
+    async fn set_routing_input(&mut self, input: In, outputs: Vec<Out>) -> Result<()>;
+    async fn set_routing_output(&mut self, output: Out, inputs: Vec<In>) -> Result<()>;
+    async fn set_routing_level(&mut self, input: In, output: Out, level: u8) -> Result<()>;
+}
+
+impl Routing for GoXLR {
+    async fn set_routing(&mut self, input: In, output: Out, enabled: bool) -> Result<()> {
+        check_route(input, output, enabled)?;
+        if self.profile.routing[input][output].enabled == enabled {
+            return Ok(());
+        }
+
+        self.profile.routing[input][output].enabled = enabled;
+        self.sync_routing_row(input).await
+    }
+
+    async fn toggle_routing(&mut self, input: In, output: Out) -> Result<()> {
+        let enabled = !self.profile.routing[input][output].enabled;
+        self.set_routing(input, output, enabled).await
+    }
+
+    async fn set_routing_input(&mut self, input: In, outputs: Vec<Out>) -> Result<()> {
+        for output in &outputs {
+            check_route(input, *output, true)?;
+        }
+
+        for output in Out::iter() {
+            self.profile.routing[input][output].enabled = outputs.contains(&output);
+        }
+        self.sync_routing_row(input).await
+    }
+
+    async fn set_routing_output(&mut self, output: Out, inputs: Vec<In>) -> Result<()> {
+        for input in &inputs {
+            check_route(*input, output, true)?;
+        }
+
+        for input in In::iter() {
+            let enabled = inputs.contains(&input);
+            if self.profile.routing[input][output].enabled != enabled {
+                self.profile.routing[input][output].enabled = enabled;
+                self.sync_routing_row(input).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn set_routing_level(&mut self, input: In, output: Out, level: u8) -> Result<()> {
+        if level > ROUTE_LEVEL_MAX {
+            bail!("Level must be <= {}, received: {}", ROUTE_LEVEL_MAX, level);
+        }
+
+        let route = &mut self.profile.routing[input][output];
+        if route.level == level {
+            return Ok(());
+        }
+        route.level = level;
+
+        // The level is kept while a route is disabled, so only send it if it's in use
+        if route.enabled {
+            self.sync_routing_row(input).await?;
+        }
+        Ok(())
+    }
+}
+
+trait RoutingLocal {
+    async fn sync_routing_row(&mut self, input: In) -> Result<()>;
+}
+
+impl RoutingLocal for GoXLR {
+    /// Rebuilds an input's routing from the profile, re-applies its mute state, and sends it
+    async fn sync_routing_row(&mut self, input: In) -> Result<()> {
+        self.set_routing_row_from_profile(input, self.profile.routing[input]);
+        for target in self.get_active_mute_targets(input.into()) {
+            self.disable_route(input, target.into())?;
+        }
+        self.apply_routing_for_channel(input).await
+    }
+}
+
+fn check_route(input: In, output: Out, enabled: bool) -> Result<()> {
+    if enabled && !RoutingTable::is_supported(input, RoutingOutput::from(output)) {
+        bail!("{:?} can not be routed to {:?}", input, output);
+    }
+    Ok(())
+}
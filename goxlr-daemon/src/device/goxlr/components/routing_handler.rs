@@ -6,6 +6,7 @@ use goxlr_shared::channels::output::{OutputChannels, RoutingOutput};
 use log::debug;
 use strum::IntoEnumIterator;
 
+use goxlr_profile::Route;
 use goxlr_shared::routing::RouteValue;
 use goxlr_usb::events::commands::BasicResultCommand;
 
@@ -24,7 +25,7 @@ pub(crate) trait RoutingHandler {
     fn disable_route(&mut self, input: In, out: Out) -> Result<bool>;
     fn set_route_value(&mut self, input: In, out: Out, value: u8) -> Result<bool>;
     fn set_route(&mut self, input: In, out: Out, value: Value) -> Result<bool>;
-    fn set_routing_row_from_profile(&mut self, input: In, values: EnumMap<OutputChannels, bool>);
+    fn set_routing_row_from_profile(&mut self, input: In, values: EnumMap<OutputChannels, Route>);
     fn get_routing_input_row(&self, input: In) -> Row;
 
     // Commands for actually sending routing information to the GoXLR..
@@ -70,16 +71,12 @@ impl RoutingHandler for GoXLR {
         Ok(true)
     }
 
-    fn set_routing_row_from_profile(&mut self, input: In, values: EnumMap<OutputChannels, bool>) {
+    fn set_routing_row_from_profile(&mut self, input: In, values: EnumMap<OutputChannels, Route>) {
         for output in OutputChannels::iter() {
             let route_out = RoutingOutput::from(output);
-            let out_value = match values[output] {
-                true => RouteValue::On,
-                false => RouteValue::Off,
-            };
 
-            // This wont throw an error, we're not setting RouteValue::Value.
-            let _ = self.set_route(input, route_out, out_value);
+            // This wont throw an error, profile levels are never above the maximum.
+            let _ = self.set_route(input, route_out, values[output].value());
         }
     }
 
@@ -0,0 +1,313 @@
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use chrono::Local;
+use goxlr_scribbles::get_scribble;
+use log::debug;
+use strum::IntoEnumIterator;
+
+use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::channels::output::OutputChannels;
+use goxlr_shared::device::DeviceType;
+use goxlr_shared::faders::Fader;
+use goxlr_shared::mute::ChannelMuteState;
+use goxlr_shared::scribbles::ScribbleContent;
+use goxlr_usb::events::commands::BasicResultCommand;
+
+use crate::device::goxlr::components::fader::DeviceFader;
+use crate::device::goxlr::components::firmware::FirmwareUpdate;
+use crate::device::goxlr::components::mute_handler::MuteHandler;
+use crate::device::goxlr::device::GoXLR;
+
+/// The shortest time between updates to a single Scribble caused by dynamic content changes
+const SCRIBBLE_UPDATE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The Scribbles are the small screens above the faders on the Full Sized GoXLR. Their content
+/// is rendered from the channel's Screen config into a 1024 byte image whenever the channel is
+/// assigned to a fader, or when any of its screen settings change.
+///
+/// Channels using dynamic content (volume, mute state, pushed text or the clock) are re-rendered
+/// as that content changes, the image is only sent to the device if it's actually different.
+pub(crate) trait Scribbles {
+    async fn set_scribble_text(
+        &mut self,
+        channel: FaderChannels,
+        text: Option<String>,
+    ) -> Result<()>;
+    async fn set_scribble_label(
+        &mut self,
+        channel: FaderChannels,
+        label: Option<char>,
+    ) -> Result<()>;
+    async fn set_scribble_image(
+        &mut self,
+        channel: FaderChannels,
+        image: Option<PathBuf>,
+    ) -> Result<()>;
+    async fn set_scribble_inverted(&mut self, channel: FaderChannels, inverted: bool)
+        -> Result<()>;
+    async fn set_scribble_content(
+        &mut self,
+        channel: FaderChannels,
+        content: ScribbleContent,
+    ) -> Result<()>;
+
+    /// Sets the runtime text displayed by channels using ScribbleContent::Custom
+    async fn push_scribble_text(
+        &mut self,
+        channel: FaderChannels,
+        text: Option<String>,
+    ) -> Result<()>;
+
+    /// Renders and sends the scribble for a fader, based on the channel assigned to it
+    async fn apply_scribble(&mut self, fader: Fader, channel: FaderChannels) -> Result<()>;
+
+    /// Flags a channel's scribble as needing a re-render on the next flush
+    fn queue_scribble_update(&mut self, channel: FaderChannels);
+
+    /// Re-renders any queued scribbles which haven't been updated too recently
+    async fn flush_scribbles(&mut self) -> Result<()>;
+}
+
+impl Scribbles for GoXLR {
+    async fn set_scribble_text(
+        &mut self,
+        channel: FaderChannels,
+        text: Option<String>,
+    ) -> Result<()> {
+        self.profile.channels.configs[channel]
+            .display
+            .screen_display
+            .text = text;
+        self.refresh_scribble(channel).await
+    }
+
+    async fn set_scribble_label(
+        &mut self,
+        channel: FaderChannels,
+        label: Option<char>,
+    ) -> Result<()> {
+        self.profile.channels.configs[channel]
+            .display
+            .screen_display
+            .label = label;
+        self.refresh_scribble(channel).await
+    }
+
+    async fn set_scribble_image(
+        &mut self,
+        channel: FaderChannels,
+        image: Option<PathBuf>,
+    ) -> Result<()> {
+        if let Some(image) = &image {
+            let path = self.profile_store.icon_path(image).await;
+            if !path.is_file() {
+                bail!("Icon {:?} not found", path);
+            }
+        }
+
+        self.profile.channels.configs[channel]
+            .display
+            .screen_display
+            .image = image;
+        self.refresh_scribble(channel).await
+    }
+
+    async fn set_scribble_inverted(
+        &mut self,
+        channel: FaderChannels,
+        inverted: bool,
+    ) -> Result<()> {
+        self.profile.channels.configs[channel]
+            .display
+            .screen_display
+            .inverted = inverted;
+        self.refresh_scribble(channel).await
+    }
+
+    async fn set_scribble_content(
+        &mut self,
+        channel: FaderChannels,
+        content: ScribbleContent,
+    ) -> Result<()> {
+        self.profile.channels.configs[channel]
+            .display
+            .screen_display
+            .content = content;
+        self.refresh_scribble(channel).await
+    }
+
+    async fn push_scribble_text(
+        &mut self,
+        channel: FaderChannels,
+        text: Option<String>,
+    ) -> Result<()> {
+        self.scribble_text[channel] = text;
+        self.queue_scribble_update(channel);
+        Ok(())
+    }
+
+    async fn apply_scribble(&mut self, fader: Fader, channel: FaderChannels) -> Result<()> {
+        self.scribble_states[fader].pending = false;
+
+        // The Mini doesn't have any screens..
+        let device = self.device.as_ref().context("Device Not Found!")?;
+        if device.device_type == DeviceType::Mini {
+            return Ok(());
+        }
+
+        let screen = self.profile.channels.configs[channel]
+            .display
+            .screen_display
+            .clone();
+        debug!("Rendering Scribble for {:?}: {:?}", fader, screen);
+
+        let image = match &screen.image {
+            Some(image) => Some(self.profile_store.icon_path(image).await),
+            None => None,
+        };
+        let text = self.get_scribble_text(channel);
+        let label = screen.label.map(String::from);
+
+        let scribble = get_scribble(image, text, label, screen.inverted);
+
+        // Don't bother the device if nothing has actually changed
+        if self.scribble_states[fader].sent == Some(scribble) {
+            return Ok(());
+        }
+
+        let command = BasicResultCommand::SetScribble(fader, scribble);
+        self.send_no_result(command).await?;
+
+        let state = &mut self.scribble_states[fader];
+        state.sent = Some(scribble);
+        state.sent_time = Some(Instant::now());
+        Ok(())
+    }
+
+    fn queue_scribble_update(&mut self, channel: FaderChannels) {
+        if let Some(fader) = self.get_fader_for_channel(channel) {
+            self.scribble_states[fader].pending = true;
+        }
+    }
+
+    async fn flush_scribbles(&mut self) -> Result<()> {
+        // Leave anything pending until the firmware update has finished
+        if self.is_updating_firmware() {
+            return Ok(());
+        }
+
+        // Clocks only need redrawing when the minute changes
+        let time = clock_text();
+        if self.scribble_clock.as_ref() != Some(&time) {
+            self.scribble_clock = Some(time);
+            for channel in FaderChannels::iter() {
+                let screen = &self.profile.channels.configs[channel]
+                    .display
+                    .screen_display;
+                if screen.content == ScribbleContent::Clock {
+                    self.queue_scribble_update(channel);
+                }
+            }
+        }
+
+        let page = &self.profile.pages.page_list[self.profile.pages.current];
+        let faders = page.faders;
+
+        for fader in Fader::iter() {
+            let state = self.scribble_states[fader];
+            if !state.pending {
+                continue;
+            }
+
+            let due = state
+                .sent_time
+                .is_none_or(|sent| sent.elapsed() >= SCRIBBLE_UPDATE_INTERVAL);
+            if due {
+                self.apply_scribble(fader, faders[fader]).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+trait ScribblesLocal {
+    /// Re-renders a channel's scribble, if it's currently assigned to a fader
+    async fn refresh_scribble(&mut self, channel: FaderChannels) -> Result<()>;
+
+    /// Works out the text to display, based on the screen's content setting
+    fn get_scribble_text(&self, channel: FaderChannels) -> Option<String>;
+    fn get_mute_badge(&self, channel: FaderChannels) -> Option<String>;
+}
+
+impl ScribblesLocal for GoXLR {
+    async fn refresh_scribble(&mut self, channel: FaderChannels) -> Result<()> {
+        if let Some(fader) = self.get_fader_for_channel(channel) {
+            self.apply_scribble(fader, channel).await?;
+        }
+        Ok(())
+    }
+    fn get_scribble_text(&self, channel: FaderChannels) -> Option<String> {
+        let screen = &self.profile.channels.configs[channel]
+            .display
+            .screen_display;
+        let volume = self.profile.channels.volumes[channel.into()];
+
+        match screen.content {
+            ScribbleContent::Text => screen.text.clone(),
+            ScribbleContent::VolumePercent => {
+                let percent = (volume as f64 / 255.0 * 100.0).round();
+                Some(format!("{}%", percent))
+            }
+            ScribbleContent::VolumeDecibels => match volume_to_decibels(volume) {
+                Some(decibels) => Some(format!("{:.1} dB", decibels)),
+                None => Some(String::from("-inf dB")),
+            },
+            ScribbleContent::MuteState => {
+                self.get_mute_badge(channel).or_else(|| screen.text.clone())
+            }
+            ScribbleContent::Custom => self.scribble_text[channel]
+                .clone()
+                .or_else(|| screen.text.clone()),
+            ScribbleContent::Clock => Some(clock_text()),
+        }
+    }
+
+    fn get_mute_badge(&self, channel: FaderChannels) -> Option<String> {
+        if self.mute_state[channel] == Some(ChannelMuteState::Muted) {
+            return Some(String::from("MUTED"));
+        }
+
+        let targets = self.get_active_mute_targets(channel);
+        match targets.as_slice() {
+            [] => None,
+            [target] => {
+                let name = match target {
+                    OutputChannels::Headphones => "HEADPHONES",
+                    OutputChannels::StreamMix => "STREAM",
+                    OutputChannels::LineOut => "LINE OUT",
+                    OutputChannels::ChatMic => "CHAT MIC",
+                    OutputChannels::Sampler => "SAMPLER",
+                };
+                Some(format!("MUTED TO {}", name))
+            }
+            targets => Some(format!("MUTED TO {} OUTPUTS", targets.len())),
+        }
+    }
+}
+
+/// The GoXLR doesn't document the gain curve of its faders, and neither the official app nor
+/// the device report a volume in decibels, so this is an approximation. The volume is treated
+/// as a linear gain from silent (0) to unity (255), making 128 roughly -6 dB. Returns None for
+/// silence (-inf dB).
+fn volume_to_decibels(volume: u8) -> Option<f64> {
+    match volume {
+        0 => None,
+        _ => Some(20.0 * (volume as f64 / 255.0).log10()),
+    }
+}
+
+fn clock_text() -> String {
+    Local::now().format("%H:%M").to_string()
+}
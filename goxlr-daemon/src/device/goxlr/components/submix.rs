@@ -1,6 +1,8 @@
 use std::cmp;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use enum_map::EnumMap;
+use goxlr_ipc::status::SubMixState;
 use goxlr_shared::channels::output::OutputChannels;
 use goxlr_shared::channels::sub_mix::SubMixChannels;
 use goxlr_shared::channels::volume::VolumeChannels;
@@ -38,25 +40,61 @@ use crate::device::goxlr::device::GoXLR;
 */
 
 pub trait SubMix {
+    async fn set_sub_mix_enabled(&mut self, enabled: bool) -> Result<()>;
     async fn set_sub_mix_mix(&mut self, channel: OutputChannels, mix: Mix) -> Result<()>;
+    async fn set_monitor_mix(&mut self, mix: Option<Mix>) -> Result<()>;
     async fn set_sub_mix_volume(&mut self, channel: SubMixChannels, volume: u8) -> Result<()>;
     async fn set_sub_mix_linked(&mut self, channel: SubMixChannels, linked: bool) -> Result<()>;
 
     async fn sync_sub_mix_volume(&mut self, channel: SubMixChannels) -> Result<()>;
     async fn load_sub_mix_assignments(&mut self) -> Result<()>;
+    async fn load_sub_mix_volumes(&mut self) -> Result<()>;
+
+    fn get_sub_mix_state(&self) -> SubMixState;
 }
 
 impl SubMix for GoXLR {
+    async fn set_sub_mix_enabled(&mut self, enabled: bool) -> Result<()> {
+        if !has_feature(&self.device, GoXLRFeature::SubMix)? {
+            bail!("Sub Mixes are not supported on this device");
+        }
+
+        if self.profile.sub_mix.enabled == enabled {
+            return Ok(());
+        }
+
+        self.profile.sub_mix.enabled = enabled;
+        self.load_sub_mix_assignments().await?;
+
+        // The Mix B volumes aren't sent while disabled, so bring them up to date
+        self.load_sub_mix_volumes().await
+    }
+
     async fn set_sub_mix_mix(&mut self, channel: OutputChannels, mix: Mix) -> Result<()> {
+        if !has_feature(&self.device, GoXLRFeature::SubMix)? {
+            bail!("Sub Mixes are not supported on this device");
+        }
+
         self.profile.outputs[channel].mix_assignment = mix;
         self.load_sub_mix_assignments().await
     }
 
+    async fn set_monitor_mix(&mut self, mix: Option<Mix>) -> Result<()> {
+        if !has_feature(&self.device, GoXLRFeature::SubMix)? {
+            bail!("Sub Mixes are not supported on this device");
+        }
+
+        self.profile.sub_mix.monitor_mix = mix;
+        self.load_sub_mix_assignments().await
+    }
+
     async fn set_sub_mix_volume(&mut self, channel: SubMixChannels, volume: u8) -> Result<()> {
         self.profile.channels.sub_mix[channel].volume = volume;
 
-        let command = BasicResultCommand::SetSubMixVolume(channel, volume);
-        self.send_no_result(command).await?;
+        if self.is_sub_mix_enabled()? {
+            let command = BasicResultCommand::SetSubMixVolume(channel, volume);
+            self.send_no_result(command).await?;
+        }
 
         // Now sync the Mix::A volume
         if VolumeChannels::can_from(channel) {
@@ -94,8 +132,8 @@ impl SubMix for GoXLR {
             // Set the new volume in the profile..
             self.profile.channels.sub_mix[channel].volume = linked_volume;
 
-            // If submixes aren't supported, simply bail.
-            if !has_feature(&self.device, GoXLRFeature::SubMix)? {
+            // If submixes aren't supported or enabled, simply bail.
+            if !self.is_sub_mix_enabled()? {
                 return Ok(());
             }
 
@@ -111,13 +149,22 @@ impl SubMix for GoXLR {
             return Ok(());
         }
 
-        // Ok, sub mixing is enabled, assign things to the correct channel
+        // When disabled, everything is simply assigned to Mix A
+        let enabled = self.profile.sub_mix.enabled;
+        let assignments = self.get_effective_mixes();
         let mut mix_a = vec![];
         let mut mix_b = vec![];
 
+        // If the monitored mix is overridden, the USB crate shouldn't set it from the headphones
+        let monitor_mix = self.profile.sub_mix.monitor_mix.filter(|_| enabled);
+
         // Iterate the outputs, and push them into the correct mix
         for channel in OutputChannels::iter() {
-            match self.profile.outputs[channel].mix_assignment {
+            if channel == OutputChannels::Headphones && monitor_mix.is_some() {
+                continue;
+            }
+
+            match assignments[channel] {
                 Mix::A => mix_a.push(channel),
                 Mix::B => mix_b.push(channel),
             }
@@ -125,6 +172,62 @@ impl SubMix for GoXLR {
 
         // Send the command across
         let command = BasicResultCommand::SetSubMixMix(mix_a, mix_b);
-        self.send_no_result(command).await
+        self.send_no_result(command).await?;
+
+        if let Some(mix) = monitor_mix {
+            let command = BasicResultCommand::SetMonitorMix(mix);
+            self.send_no_result(command).await?;
+        }
+        Ok(())
+    }
+
+    async fn load_sub_mix_volumes(&mut self) -> Result<()> {
+        if !self.is_sub_mix_enabled()? {
+            return Ok(());
+        }
+
+        for channel in SubMixChannels::iter() {
+            let volume = self.profile.channels.sub_mix[channel].volume;
+            let command = BasicResultCommand::SetSubMixVolume(channel, volume);
+            self.send_no_result(command).await?;
+        }
+        Ok(())
+    }
+
+    fn get_sub_mix_state(&self) -> SubMixState {
+        let configured = EnumMap::from_fn(|channel| self.profile.outputs[channel].mix_assignment);
+        let effective = self.get_effective_mixes();
+
+        SubMixState {
+            supported: has_feature(&self.device, GoXLRFeature::SubMix).unwrap_or(false),
+            configured,
+            effective,
+            monitor_mix: effective[OutputChannels::Headphones],
+        }
+    }
+}
+
+trait SubMixLocal {
+    fn is_sub_mix_enabled(&self) -> Result<bool>;
+    fn get_effective_mixes(&self) -> EnumMap<OutputChannels, Mix>;
+}
+
+impl SubMixLocal for GoXLR {
+    fn is_sub_mix_enabled(&self) -> Result<bool> {
+        let supported = has_feature(&self.device, GoXLRFeature::SubMix)?;
+        Ok(supported && self.profile.sub_mix.enabled)
+    }
+
+    fn get_effective_mixes(&self) -> EnumMap<OutputChannels, Mix> {
+        if !self.is_sub_mix_enabled().unwrap_or(false) {
+            return EnumMap::from_fn(|_| Mix::A);
+        }
+        let mut mixes = EnumMap::from_fn(|channel| self.profile.outputs[channel].mix_assignment);
+
+        // An overridden monitor mix is what the headphones actually hear
+        if let Some(mix) = self.profile.sub_mix.monitor_mix {
+            mixes[OutputChannels::Headphones] = mix;
+        }
+        mixes
     }
 }
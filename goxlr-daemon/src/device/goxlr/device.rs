@@ -5,17 +5,19 @@ use std::time::{Duration, Instant};
 use anyhow::{bail, Context, Result};
 use enum_map::EnumMap;
 use goxlr_ipc::commands::GoXLRCommandResponse;
-use goxlr_ipc::status::Configuration;
+use goxlr_ipc::status::{Configuration, Interaction, RuntimeState};
 use log::{debug, error, trace, warn};
 use tokio::sync::{mpsc, oneshot};
 use tokio::{join, select, task, time};
 
 use goxlr_profile::{MicProfile, Profile};
+use goxlr_scribbles::canvas::SCRIBBLE_SIZE;
 use goxlr_shared::buttons::Buttons;
 use goxlr_shared::channels::fader::FaderChannels;
 use goxlr_shared::colours::ColourScheme;
 use goxlr_shared::device::DeviceInfo;
 use goxlr_shared::faders::Fader;
+use goxlr_shared::firmware::FirmwareUpdateStatus;
 use goxlr_shared::mute::ChannelMuteState;
 use goxlr_shared::routing::RoutingTable;
 use goxlr_shared::states::ButtonDisplayStates;
@@ -25,11 +27,17 @@ use goxlr_usb::runners::device::DeviceMessage;
 use goxlr_usb::runners::device::{start_usb_device_runner, GoXLRUSBConfiguration};
 
 use crate::device::device_manager::{ManagerMessage, RunnerMessage, RunnerState};
+use crate::device::goxlr::components::firmware::{next_firmware_status, FirmwareUpdate};
 use crate::device::goxlr::components::interactions::Interactions;
 use crate::device::goxlr::components::load_profile::LoadProfile;
 use crate::device::goxlr::components::mic::load_profile::LoadMicProfile;
-use crate::device::goxlr::device_config::GoXLRDeviceConfiguration;
+use crate::device::goxlr::components::mic::meter::{MeterState, MicMeter};
+use crate::device::goxlr::components::persistence::{is_state_changing, ProfilePersistence};
+use crate::device::goxlr::components::scribbles::Scribbles;
+use crate::device::goxlr::components::submix::SubMix;
+use crate::device::goxlr::device_config::{GoXLRDeviceConfiguration, MicMeterBroadcast};
 use crate::device::goxlr::ipc::handler::IPCCommandHandler;
+use crate::profiles::{DeviceProfiles, ProfileStore};
 use crate::stop::Stop;
 
 pub(crate) struct GoXLR {
@@ -41,6 +49,11 @@ pub(crate) struct GoXLR {
     pub profile: Profile,
     pub mic_profile: MicProfile,
 
+    // Where the profiles live, and what they're called..
+    pub(crate) profile_store: ProfileStore,
+    pub(crate) profile_names: DeviceProfiles,
+    pub(crate) save_deadline: Option<Instant>,
+
     // These are 'caches' of the state which are manipulated directly.
     pub colour_scheme: ColourScheme,
     pub button_states: ButtonDisplayStates,
@@ -51,6 +64,18 @@ pub(crate) struct GoXLR {
     // For tracking button 'held' state..
     pub button_down_states: EnumMap<Buttons, Option<ButtonState>>,
 
+    // What's on the scribbles, text pushed to them at runtime, and the time on Clock scribbles
+    pub(crate) scribble_states: EnumMap<Fader, ScribbleState>,
+    pub(crate) scribble_text: EnumMap<FaderChannels, Option<String>>,
+    pub(crate) scribble_clock: Option<String>,
+
+    // Progress of a running firmware update
+    pub(crate) firmware_update: Option<mpsc::Receiver<FirmwareUpdateStatus>>,
+
+    // Where Mic Levels are sent, and the meter's state while anyone is subscribed
+    pub(crate) mic_meter: MicMeterBroadcast,
+    pub(crate) mic_meter_state: Option<MeterState>,
+
     config: GoXLRDeviceConfiguration,
     shutdown: Stop,
 }
@@ -66,11 +91,20 @@ impl GoXLR {
             colour_scheme: Default::default(),
             profile: Default::default(),
             mic_profile: Default::default(),
+            profile_store: config.profile_store.clone(),
+            profile_names: Default::default(),
+            save_deadline: None,
             button_states: Default::default(),
             routing_state: Default::default(),
             mute_state: Default::default(),
             fader_state: Default::default(),
             button_down_states: Default::default(),
+            scribble_states: Default::default(),
+            scribble_text: Default::default(),
+            scribble_clock: None,
+            firmware_update: None,
+            mic_meter: config.mic_meter.clone(),
+            mic_meter_state: None,
 
             config,
             shutdown,
@@ -103,6 +137,26 @@ impl GoXLR {
         }
     }
 
+    /// Broadcasts an interaction to any subscribed clients
+    pub(crate) fn send_interaction(&self, interaction: Interaction) {
+        if self.config.interactions.receiver_count() == 0 {
+            return;
+        }
+
+        if let Some(device) = &self.device {
+            // Named in full, as the USB crate has an InteractionEvent of its own
+            let event = goxlr_ipc::status::InteractionEvent {
+                serial: device.serial.clone(),
+                interaction,
+            };
+            let _ = self.config.interactions.send(event);
+        }
+    }
+
+    pub(crate) async fn send_manager_message(&self, message: RunnerMessage) {
+        let _ = self.config.manager_sender.send(message).await;
+    }
+
     pub async fn run(&mut self) -> Result<()> {
         debug!("[GoXLR]{} Starting Event Loop", self.config.device);
 
@@ -132,6 +186,7 @@ impl GoXLR {
             device_event: event_send,
             command_receiver: command_recv,
             stop: stop_recv,
+            capture: self.config.capture.clone(),
         };
         let runner = task::spawn(start_usb_device_runner(configuration, ready_send));
 
@@ -172,6 +227,8 @@ impl GoXLR {
         // shut down the runners. We shouldn't just bail if there's an error above, as the USB
         // runtime has already been started, the easiest way to stop it is to just jump to the end.
         if !load_fail {
+            self.send_interaction(Interaction::DeviceAttached);
+
             // Sit and wait for various signals to come, and process them as they do.
             loop {
                 select! {
@@ -188,8 +245,20 @@ impl GoXLR {
                             ManagerMessage::GetDevice(tx) => {
                                 let _ = tx.send(self.device.as_ref().unwrap().clone());
                             }
+                            ManagerMessage::GetState(tx) => {
+                                let state = RuntimeState {
+                                    sub_mix: self.get_sub_mix_state(),
+                                };
+                                let _ = tx.send(state);
+                            }
+                            ManagerMessage::GetProfileNames(tx) => {
+                                let _ = tx.send(self.profile_names.clone());
+                            }
                             ManagerMessage::Execute(command, tx) => {
                                 debug!("Handling IPC Command: {:?}", command);
+                                if is_state_changing(&command) {
+                                    self.queue_save();
+                                }
 
                                 let result = self.handle_ipc_command(command).await;
                                 let message = result.unwrap_or_else(|e| {
@@ -234,24 +303,22 @@ impl GoXLR {
                         if let Err(error) = result {
                             warn!("Error Handling Button Press: {:?}", error);
                         }
+                        self.queue_save();
 
                         let _ = self.send_device_update().await;
                     }
+                    status = next_firmware_status(&mut self.firmware_update) => {
+                        // If the runner went away without finishing, the update didn't complete
+                        let error = String::from("Firmware Update Interrupted");
+                        let status = status.unwrap_or_else(|| FirmwareUpdateStatus::failed(error));
+                        self.on_firmware_status(status).await;
+                    }
                     _ = ticker.tick() => {
                         // Things to do every 20ms..
                         let _ = self.check_held().await;
-
-                        // // Lets grab the current db value of the Microphone..
-                        // let (msg_send, msg_receive) = oneshot::channel();
-                        //
-                        // if let Some(sender) = self.command_sender.clone() {
-                        //     let command = CommandSender::GetMicLevel(msg_send);
-                        //     let _ = sender.send(command).await;
-                        //
-                        //     if let Ok(Ok(value)) = msg_receive.await {
-                        //         debug!("{}", value);
-                        //     }
-                        // }
+                        let _ = self.flush_scribbles().await;
+                        let _ = self.sample_mic_level().await;
+                        self.save_if_due().await;
                     }
                     _ = self.shutdown.recv() => {
                         debug!("[GoXLR]{} Shutdown Triggered!", self.config.device);
@@ -263,6 +330,17 @@ impl GoXLR {
                     }
                 }
             }
+            self.send_interaction(Interaction::DeviceRemoved);
+        }
+
+        // The runner stops once a firmware update has finished, make sure the result is sent
+        self.flush_firmware_status().await;
+
+        // If there are any pending changes, make sure they hit the disk before we go.
+        if self.save_deadline.is_some() && !load_fail {
+            if let Err(error) = self.save_profiles().await {
+                warn!("Unable to Save Profiles: {}", error);
+            }
         }
 
         // Our loop has been broken (or never started), let the device know we're done..
@@ -304,3 +382,12 @@ pub(crate) struct ButtonState {
     pub(crate) skip_release: bool,
     pub(crate) hold_handled: bool,
 }
+
+/// Tracks what was last sent to a Scribble, so it's only re-sent when the image changes, and
+/// dynamic content (such as the volume) doesn't flood the device while a fader is moving.
+#[derive(Debug, Default, Copy, Clone)]
+pub(crate) struct ScribbleState {
+    pub(crate) sent: Option<[u8; SCRIBBLE_SIZE]>,
+    pub(crate) sent_time: Option<Instant>,
+    pub(crate) pending: bool,
+}
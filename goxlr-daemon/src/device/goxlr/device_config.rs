@@ -1,8 +1,15 @@
+use std::path::PathBuf;
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
+
+use tokio::sync::broadcast;
 use tokio::sync::mpsc::{Receiver, Sender};
 
+use goxlr_ipc::status::{InteractionEvent, MicLevelMeter};
 use goxlr_usb::USBLocation;
 
 use crate::device::device_manager::{ManagerMessage, RunnerMessage};
+use crate::profiles::ProfileStore;
 use crate::stop::Stop;
 
 pub struct GoXLRDeviceConfiguration {
@@ -11,4 +18,15 @@ pub struct GoXLRDeviceConfiguration {
     pub(crate) update_sender: Sender<()>,
     pub(crate) manager_sender: Sender<RunnerMessage>,
     pub(crate) manager_recv: Receiver<ManagerMessage>,
+    pub(crate) profile_store: ProfileStore,
+    pub(crate) capture: Option<PathBuf>,
+    pub(crate) mic_meter: MicMeterBroadcast,
+    pub(crate) interactions: broadcast::Sender<InteractionEvent>,
+}
+
+/// Where Mic Level readings are sent, and how often (in milliseconds) they should be sampled
+#[derive(Clone)]
+pub struct MicMeterBroadcast {
+    pub(crate) sender: broadcast::Sender<MicLevelMeter>,
+    pub(crate) interval: Arc<AtomicU64>,
 }
@@ -26,6 +26,11 @@ impl IPCChannelHandler for GoXLR {
                 debug!("Applying Mute State..");
                 self.set_mute_state(params.channel, params.state).await?;
             }
+            Command::MuteTargets(params) => {
+                let (channel, action) = (params.channel, params.action);
+                self.set_mute_targets(channel, action, params.targets)
+                    .await?;
+            }
 
             Command::SubMix(command) => {
                 let channel = command.channel;
@@ -38,6 +43,11 @@ impl IPCChannelHandler for GoXLR {
                     }
                 }
             }
+            Command::OutputMix(params) => {
+                self.set_sub_mix_mix(params.output, params.mix).await?;
+            }
+            Command::MonitorMix(mix) => self.set_monitor_mix(mix).await?,
+            Command::SubMixEnabled(enabled) => self.set_sub_mix_enabled(enabled).await?,
         }
         Ok(GoXLRCommandResponse::Ok)
     }
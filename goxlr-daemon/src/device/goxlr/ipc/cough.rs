@@ -0,0 +1,27 @@
+use goxlr_ipc::commands::cough::CoughCommand;
+use goxlr_ipc::commands::GoXLRCommandResponse;
+
+use crate::device::goxlr::components::mute_handler::MuteHandler;
+use crate::device::goxlr::device::GoXLR;
+use crate::device::goxlr::ipc::handler::Response;
+
+type Command = CoughCommand;
+
+pub trait IPCCoughHandler {
+    async fn ipc_cough(&mut self, command: Command) -> Response;
+}
+
+impl IPCCoughHandler for GoXLR {
+    async fn ipc_cough(&mut self, command: Command) -> Response {
+        match command {
+            Command::Behaviour(behaviour) => self.set_cough_behaviour(behaviour).await?,
+            Command::Channel(channel) => self.set_cough_channel(channel).await?,
+            Command::MuteTargets(action, targets) => {
+                self.set_cough_mute_targets(action, targets).await?
+            }
+            Command::State(state) => self.set_cough_state(state).await?,
+        }
+
+        Ok(GoXLRCommandResponse::Ok)
+    }
+}
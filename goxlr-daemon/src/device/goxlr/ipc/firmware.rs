@@ -0,0 +1,21 @@
+use goxlr_ipc::commands::firmware::FirmwareCommand;
+use goxlr_ipc::commands::GoXLRCommandResponse;
+
+use crate::device::goxlr::components::firmware::FirmwareUpdate;
+use crate::device::goxlr::device::GoXLR;
+use crate::device::goxlr::ipc::handler::Response;
+
+type Command = FirmwareCommand;
+
+pub trait IPCFirmwareHandler {
+    async fn ipc_firmware(&mut self, command: Command) -> Response;
+}
+
+impl IPCFirmwareHandler for GoXLR {
+    async fn ipc_firmware(&mut self, command: Command) -> Response {
+        match command {
+            Command::Update(path) => self.begin_firmware_update(&path).await?,
+        }
+        Ok(GoXLRCommandResponse::Ok)
+    }
+}
@@ -1,12 +1,18 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 
 use goxlr_ipc::commands::{GoXLRCommand, GoXLRCommandResponse};
 
+use crate::device::goxlr::components::firmware::FirmwareUpdate;
 use crate::device::goxlr::device::GoXLR;
 use crate::device::goxlr::ipc::channels::IPCChannelHandler;
 use crate::device::goxlr::ipc::configuration::IPCConfigurationHandler;
+use crate::device::goxlr::ipc::cough::IPCCoughHandler;
+use crate::device::goxlr::ipc::firmware::IPCFirmwareHandler;
+use crate::device::goxlr::ipc::lighting::IPCLightingHandler;
 use crate::device::goxlr::ipc::microphone::IPCMicrophoneHandler;
 use crate::device::goxlr::ipc::pages::IPCPageHandler;
+use crate::device::goxlr::ipc::routing::IPCRoutingHandler;
+use crate::device::goxlr::ipc::scribbles::IPCScribbleHandler;
 
 pub type Response = Result<GoXLRCommandResponse>;
 
@@ -16,11 +22,21 @@ pub trait IPCCommandHandler {
 
 impl IPCCommandHandler for GoXLR {
     async fn handle_ipc_command(&mut self, command: GoXLRCommand) -> Response {
+        // The device is unavailable until the update has finished
+        if self.is_updating_firmware() {
+            bail!("Firmware Update in progress");
+        }
+
         match command {
             GoXLRCommand::Configuration(command) => self.ipc_configuration(command).await,
             GoXLRCommand::Channels(command) => self.ipc_channel(command).await,
+            GoXLRCommand::Cough(command) => self.ipc_cough(command).await,
             GoXLRCommand::Pages(command) => self.ipc_page(command).await,
             GoXLRCommand::Microphone(command) => self.ipc_microphone(command).await,
+            GoXLRCommand::Routing(command) => self.ipc_routing(command).await,
+            GoXLRCommand::Lighting(command) => self.ipc_lighting(command).await,
+            GoXLRCommand::Scribbles(command) => self.ipc_scribble(command).await,
+            GoXLRCommand::Firmware(command) => self.ipc_firmware(command).await,
         }
     }
 }
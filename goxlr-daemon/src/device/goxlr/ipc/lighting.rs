@@ -0,0 +1,43 @@
+use goxlr_ipc::commands::lighting::LightingCommand;
+use goxlr_ipc::commands::GoXLRCommandResponse;
+
+use crate::device::goxlr::components::lighting::Lighting;
+use crate::device::goxlr::device::GoXLR;
+use crate::device::goxlr::ipc::handler::Response;
+
+type Command = LightingCommand;
+
+pub trait IPCLightingHandler {
+    async fn ipc_lighting(&mut self, command: Command) -> Response;
+}
+
+impl IPCLightingHandler for GoXLR {
+    async fn ipc_lighting(&mut self, command: Command) -> Response {
+        match command {
+            Command::FaderColours(channel, colours) => {
+                self.set_channel_fader_colours(channel, colours).await?
+            }
+            Command::MuteColours(channel, colours) => {
+                self.set_channel_mute_colours(channel, colours).await?
+            }
+            Command::ScribbleColour(channel, colour) => {
+                self.set_channel_scribble_colour(channel, colour).await?
+            }
+            Command::SwearColours(colours) => self.set_swear_colours(colours).await?,
+            Command::CoughColours(colours) => self.set_cough_colours(colours).await?,
+            Command::ButtonColours(button, colours) => {
+                self.set_button_colours(button, colours).await?
+            }
+            Command::EncoderColours(encoder, colours) => {
+                self.set_encoder_colours(encoder, colours).await?
+            }
+            Command::SaveTheme(name, theme) => self.save_lighting_theme(name, theme)?,
+            Command::RemoveTheme(name) => self.remove_lighting_theme(&name)?,
+            Command::ApplyTheme(name) => self.apply_lighting_theme(&name).await?,
+            Command::CopyChannelColours(channel) => self.copy_channel_colours(channel).await?,
+            Command::Animation(animation) => self.set_animation(animation).await?,
+        }
+
+        Ok(GoXLRCommandResponse::Ok)
+    }
+}
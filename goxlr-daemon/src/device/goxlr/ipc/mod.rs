@@ -1,5 +1,10 @@
 pub(crate) mod channels;
+mod cough;
+mod firmware;
 pub(crate) mod handler;
+mod lighting;
 mod microphone;
 mod pages;
+mod routing;
+mod scribbles;
 mod configuration;
@@ -0,0 +1,32 @@
+use goxlr_ipc::commands::routing::RoutingCommand;
+use goxlr_ipc::commands::GoXLRCommandResponse;
+
+use crate::device::goxlr::components::routing::Routing;
+use crate::device::goxlr::device::GoXLR;
+use crate::device::goxlr::ipc::handler::Response;
+
+type Command = RoutingCommand;
+
+pub trait IPCRoutingHandler {
+    async fn ipc_routing(&mut self, command: Command) -> Response;
+}
+
+impl IPCRoutingHandler for GoXLR {
+    async fn ipc_routing(&mut self, command: Command) -> Response {
+        match command {
+            Command::SetRoute(route) => {
+                self.set_routing(route.input, route.output, route.enabled)
+                    .await?
+            }
+            Command::ToggleRoute(input, output) => self.toggle_routing(input, output).await?,
+            Command::SetInput(input, outputs) => self.set_routing_input(input, outputs).await?,
+            Command::SetOutput(output, inputs) => self.set_routing_output(output, inputs).await?,
+            Command::SetLevel(route) => {
+                self.set_routing_level(route.input, route.output, route.level)
+                    .await?
+            }
+        }
+
+        Ok(GoXLRCommandResponse::Ok)
+    }
+}
@@ -0,0 +1,31 @@
+use goxlr_ipc::commands::scribbles::ScribbleCommand;
+use goxlr_ipc::commands::GoXLRCommandResponse;
+
+use crate::device::goxlr::components::scribbles::Scribbles;
+use crate::device::goxlr::device::GoXLR;
+use crate::device::goxlr::ipc::handler::Response;
+
+type Command = ScribbleCommand;
+
+pub trait IPCScribbleHandler {
+    async fn ipc_scribble(&mut self, command: Command) -> Response;
+}
+
+impl IPCScribbleHandler for GoXLR {
+    async fn ipc_scribble(&mut self, command: Command) -> Response {
+        match command {
+            Command::Text(channel, text) => self.set_scribble_text(channel, text).await?,
+            Command::Label(channel, label) => self.set_scribble_label(channel, label).await?,
+            Command::Image(channel, image) => self.set_scribble_image(channel, image).await?,
+            Command::Inverted(channel, inverted) => {
+                self.set_scribble_inverted(channel, inverted).await?
+            }
+            Command::Content(channel, content) => {
+                self.set_scribble_content(channel, content).await?
+            }
+            Command::PushText(channel, text) => self.push_scribble_text(channel, text).await?,
+        }
+
+        Ok(GoXLRCommandResponse::Ok)
+    }
+}
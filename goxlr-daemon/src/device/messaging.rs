@@ -1,11 +1,18 @@
-use tokio::sync::oneshot;
+use tokio::sync::{broadcast, oneshot};
 
 use goxlr_ipc::commands::{
     DaemonCommand, DaemonResponse, DaemonStatus, GoXLRCommand, GoXLRCommandResponse,
 };
 
+use crate::servers::http_server::PatchEvent;
+
 pub enum DeviceMessage {
     GetStatus(oneshot::Sender<DaemonStatus>),
     RunDaemon(DaemonCommand, oneshot::Sender<DaemonResponse>),
     RunDevice(String, GoXLRCommand, oneshot::Sender<GoXLRCommandResponse>),
+
+    /// Subscribes to patches, along with the status they'll be applied to
+    SubscribePatches(oneshot::Sender<PatchSubscription>),
 }
+
+pub type PatchSubscription = (DaemonStatus, broadcast::Receiver<PatchEvent>);
@@ -1,10 +1,10 @@
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use tokio::sync::mpsc::Sender;
 use tokio::sync::oneshot;
 
 use goxlr_ipc::commands::{DaemonRequest, DaemonResponse, DeviceCommand};
 
-use crate::device::messaging::DeviceMessage;
+use crate::device::messaging::{DeviceMessage, PatchSubscription};
 
 pub type Messenger = Sender<DeviceMessage>;
 type Response = Result<DaemonResponse>;
@@ -34,8 +34,8 @@ pub async fn handle_packet(request: DaemonRequest, sender: Messenger) -> Respons
                 .map_err(|e| anyhow!(e.to_string()))
                 .context("Failed to send message to device manager")?;
 
-            rx.await.context("Error from device manager")?;
-            Ok(DaemonResponse::Ok)
+            let result = rx.await.context("Error from device manager")?;
+            Ok(result)
         }
         DaemonRequest::DeviceCommand(command) => {
             let DeviceCommand { serial, command } = command;
@@ -51,5 +51,22 @@ pub async fn handle_packet(request: DaemonRequest, sender: Messenger) -> Respons
                 Ok(DaemonResponse::DeviceCommand(result))
             }
         }
+        DaemonRequest::Subscribe(_) | DaemonRequest::Unsubscribe(_) => {
+            // These are tied to a connection, so need to be handled by the server itself
+            bail!("Subscriptions are only available on the Websocket and IPC Socket")
+        }
     }
 }
+
+/// Subscriptions are tied to a connection, so rather than going through `handle_packet` the
+/// servers use this to subscribe to patches.
+pub async fn subscribe_patches(sender: Messenger) -> Result<PatchSubscription> {
+    let (tx, rx) = oneshot::channel();
+    sender
+        .send(DeviceMessage::SubscribePatches(tx))
+        .await
+        .map_err(|e| anyhow!(e.to_string()))
+        .context("Failed to send message to device manager")?;
+
+    rx.await.context("Error from device manager")
+}
@@ -0,0 +1,115 @@
+/*
+   The daemon is built as a library so that it can be started in-process (for example, by the
+   integration tests against a virtual device). main.rs simply handles the command line, logging
+   and platform signals before handing over to run_daemon.
+*/
+
+use std::sync::atomic::{AtomicBool, AtomicU64};
+use std::sync::Arc;
+
+use anyhow::Result;
+use log::debug;
+use tokio::sync::{broadcast, mpsc};
+use tokio::{join, task};
+
+use crate::device::device_manager::start_device_manager;
+use crate::device::goxlr::device_config::MicMeterBroadcast;
+use crate::profiles::ProfileStore;
+use crate::servers::http_server::spawn_http_server;
+use crate::servers::ipc_server::{bind_socket, spawn_ipc_server};
+use crate::settings::{spawn_settings_watcher, LiveSettings, SettingsHandle};
+
+pub use crate::stop::Stop;
+
+pub mod cli;
+mod device;
+pub mod platform;
+mod profiles;
+mod servers;
+pub mod settings;
+mod stop;
+
+/// Starts the IPC and HTTP servers, and the device manager, and runs until the shutdown is
+/// triggered (either by the caller, or by a Shutdown command).
+pub async fn run_daemon(settings_handle: SettingsHandle, shutdown: Stop) -> Result<()> {
+    let settings = settings_handle.get().await;
+
+    // Create the Global Manager Channels..
+    let (manager_send, manager_recv) = mpsc::channel(32);
+
+    // Mic Levels are broadcast to subscribed clients, devices only sample while someone listens
+    let (meter_tx, meter_rx) = broadcast::channel(64);
+    drop(meter_rx);
+    let meter_interval = Arc::new(AtomicU64::new(settings.mic_meter_interval));
+
+    // Physical interactions with devices, broadcast to subscribed clients
+    let (interaction_tx, interaction_rx) = broadcast::channel(128);
+    drop(interaction_rx);
+
+    // Prepare the IPC Socket..
+    let ipc_socket = bind_socket(&settings.socket_path).await?;
+    let communications_handle = tokio::spawn(spawn_ipc_server(
+        ipc_socket,
+        settings.socket_path.clone(),
+        manager_send.clone(),
+        meter_tx.clone(),
+        interaction_tx.clone(),
+        shutdown.clone(),
+    ));
+
+    // Prepare the HTTP Server..
+    let (broadcast_tx, broadcast_rx) = broadcast::channel(16);
+    drop(broadcast_rx);
+
+    let cors_enabled = Arc::new(AtomicBool::new(settings.http.cors_enabled));
+    let mut http_server = None;
+    if settings.http.enabled {
+        let (httpd_tx, httpd_rx) = tokio::sync::oneshot::channel();
+        tokio::spawn(spawn_http_server(
+            manager_send.clone(),
+            httpd_tx,
+            broadcast_tx.clone(),
+            meter_tx.clone(),
+            interaction_tx.clone(),
+            settings.http.clone(),
+            cors_enabled.clone(),
+        ));
+        http_server = Some(httpd_rx.await?);
+    }
+
+    // Prepare the Profile Store..
+    let profile_store = ProfileStore::new(settings.profile_directory.clone());
+
+    // Watch for changes to the settings..
+    let live_settings = LiveSettings {
+        cors_enabled,
+        profile_store: profile_store.clone(),
+        mic_meter_interval: meter_interval.clone(),
+    };
+    let settings_watcher = task::spawn(spawn_settings_watcher(
+        settings_handle.clone(),
+        live_settings,
+        shutdown.clone(),
+    ));
+
+    let task = task::spawn(start_device_manager(
+        manager_recv,
+        shutdown.clone(),
+        broadcast_tx.clone(),
+        MicMeterBroadcast {
+            sender: meter_tx,
+            interval: meter_interval,
+        },
+        interaction_tx,
+        profile_store,
+        settings_handle,
+    ));
+
+    let _ = join!(task, communications_handle, settings_watcher);
+    if let Some(http_server) = http_server {
+        http_server.stop(false).await;
+    }
+
+    debug!("Daemon Stopped");
+    Ok(())
+}
@@ -1,86 +1,54 @@
-use anyhow::{bail, Context, Result};
-use goxlr_ipc::commands::HttpSettings;
-use log::{debug, error, LevelFilter};
+use anyhow::{Context, Result};
+use clap::Parser;
+use log::{debug, error, info, LevelFilter};
 use simplelog::{ColorChoice, CombinedLogger, ConfigBuilder, TermLogger, TerminalMode};
-use tokio::sync::{broadcast, mpsc};
-use tokio::{join, task};
+use tokio::task;
 
-use crate::device::device_manager::start_device_manager;
-use crate::platform::spawn_runtime;
-use crate::servers::http_server::spawn_http_server;
-use crate::servers::ipc_server::{bind_socket, spawn_ipc_server};
-use crate::stop::Stop;
-
-mod device;
-mod servers;
-mod settings;
-mod stop;
-mod platform;
+use goxlr_daemon::cli::Cli;
+use goxlr_daemon::platform::spawn_runtime;
+use goxlr_daemon::settings::{level_to_filter, SettingsHandle};
+use goxlr_daemon::{run_daemon, Stop};
+use goxlr_usb::capture;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let replays = cli.replay.clone();
+    let settings_handle = SettingsHandle::load(cli)?;
+    let settings = settings_handle.get().await;
+
+    // The logger itself accepts everything, the actual level is controlled via the max level so
+    // that it can be changed at runtime.
     CombinedLogger::init(vec![TermLogger::new(
-        LevelFilter::Debug,
+        LevelFilter::Trace,
         ConfigBuilder::new().build(),
         TerminalMode::Mixed,
         ColorChoice::Auto,
     )])
     .context("Could not configure the logger")?;
+    log::set_max_level(level_to_filter(settings.log_level));
+    info!("Loaded Settings from {:?}", settings_handle.path());
 
-    // Spawn the Shutdown Handler..
-    let shutdown = Stop::new();
-
-    // Create the Global Manager Channels..
-    let (manager_send, manager_recv) = mpsc::channel(32);
-
-    // Prepare the IPC Socket..
-    let ipc_socket = bind_socket().await;
-    if ipc_socket.is_err() {
-        error!("Error Starting Daemon: ");
-        bail!("{}", ipc_socket.err().unwrap());
+    // Attach any captures which should be replayed as devices
+    for path in &replays {
+        capture::attach_replay(path)?;
     }
-    let ipc_socket = ipc_socket.unwrap();
-    let communications_handle = tokio::spawn(spawn_ipc_server(
-        ipc_socket,
-        manager_send.clone(),
-        shutdown.clone(),
-    ));
 
-    // Prepare the HTTP Server..
-    let http_settings = HttpSettings {
-        enabled: true,
-        bind_address: "localhost".to_string(),
-        cors_enabled: false,
-        port: 14564,
-    };
-
-    let (httpd_tx, httpd_rx) = tokio::sync::oneshot::channel();
-    let (broadcast_tx, broadcast_rx) = broadcast::channel(16);
-    drop(broadcast_rx);
+    // Spawn the Shutdown Handler..
+    let shutdown = Stop::new();
 
-    tokio::spawn(spawn_http_server(
-        manager_send.clone(),
-        httpd_tx,
-        broadcast_tx.clone(),
-        http_settings,
-    ));
-    let http_server = httpd_rx.await?;
+    let runtime = task::spawn(spawn_runtime(shutdown.clone()));
+    let result = run_daemon(settings_handle, shutdown.clone()).await;
 
-    // We're going to go to sleep, then trigger the shutdown..
-    // sleep(Duration::from_secs(5)).await;
-    // shutdown.trigger();
+    // If the daemon failed to start, the platform runtime will still be waiting, so make sure
+    // it's been told to stop.
+    shutdown.trigger();
+    let _ = runtime.await;
 
-    let task = task::spawn(start_device_manager(
-        manager_recv,
-        shutdown.clone(),
-        broadcast_tx.clone(),
-    ));
-    
-    let runtime = task::spawn(spawn_runtime(shutdown.clone()));
-    
-    let _ = join!(task, communications_handle, runtime);
-    http_server.stop(false).await;
+    if let Err(error) = &result {
+        error!("Error Starting Daemon: {}", error);
+    }
 
     debug!("Should be done!");
-    Ok(())
+    result
 }
@@ -0,0 +1,340 @@
+/*
+   The Profile Store is responsible for everything that touches profiles on disk. Each device
+   serial is mapped to the name of the last active profile and mic profile, which are then loaded
+   from (and saved to) their respective directories when needed.
+
+   All writes go via a temporary file which is flushed and renamed over the original, so if the
+   daemon (or the machine) dies mid-write we'll only ever have either the old or the new profile,
+   never a half written one.
+*/
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use directories::ProjectDirs;
+use log::{debug, warn};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+use goxlr_ipc::commands::{ProfileList, ProfileType};
+use goxlr_profile::{MicProfile, Profile};
+
+static PROFILE_DIR: &str = "profiles";
+static MIC_PROFILE_DIR: &str = "mic-profiles";
+static ICON_DIR: &str = "icons";
+static DEVICE_FILE: &str = "devices.json";
+static EXTENSION: &str = "json";
+
+pub static DEFAULT_PROFILE_NAME: &str = "Default";
+
+// Keeps the temporary file names of concurrent writes apart
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// The profiles which were last active on a specific device
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceProfiles {
+    pub profile: String,
+    pub mic_profile: String,
+}
+
+impl Default for DeviceProfiles {
+    fn default() -> Self {
+        Self {
+            profile: DEFAULT_PROFILE_NAME.to_string(),
+            mic_profile: DEFAULT_PROFILE_NAME.to_string(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ProfileStore {
+    directory: Arc<Mutex<PathBuf>>,
+    devices: Arc<Mutex<BTreeMap<String, DeviceProfiles>>>,
+    list: Arc<Mutex<ProfileList>>,
+}
+
+impl ProfileStore {
+    pub fn new(directory: PathBuf) -> Self {
+        // This is only called during startup, so we can just read this synchronously.
+        let devices = read_devices(&directory);
+        let list = scan_directory(&directory);
+
+        Self {
+            directory: Arc::new(Mutex::new(directory)),
+            devices: Arc::new(Mutex::new(devices)),
+            list: Arc::new(Mutex::new(list)),
+        }
+    }
+
+    /// Moves the store to a new directory, profiles which are already loaded will be saved into
+    /// the new location next time they change.
+    pub async fn set_directory(&self, directory: PathBuf) {
+        let mut current = self.directory.lock().await;
+        *current = directory;
+        drop(current);
+
+        self.rescan().await;
+    }
+
+    /// Re-reads the device mappings and profile list from disk.
+    pub async fn rescan(&self) {
+        let mut devices = self.devices.lock().await;
+        let directory = self.directory.lock().await;
+
+        *devices = read_devices(&directory);
+        *self.list.lock().await = scan_directory(&directory);
+    }
+
+    pub async fn get_profile_list(&self) -> ProfileList {
+        self.list.lock().await.clone()
+    }
+
+    /// Returns the default location for profiles, or the current working directory if for some
+    /// reason the platform doesn't provide one.
+    pub fn default_directory() -> PathBuf {
+        match ProjectDirs::from("org", "GoXLR-on-Linux", "GoXLR-Utility") {
+            Some(dirs) => dirs.data_dir().to_path_buf(),
+            None => PathBuf::from("."),
+        }
+    }
+
+    pub async fn get_device_profiles(&self, serial: &str) -> DeviceProfiles {
+        let devices = self.devices.lock().await;
+        devices.get(serial).cloned().unwrap_or_default()
+    }
+
+    pub async fn set_device_profiles(&self, serial: &str, profiles: DeviceProfiles) -> Result<()> {
+        let mut devices = self.devices.lock().await;
+        devices.insert(serial.to_string(), profiles);
+
+        let content = serde_json::to_vec_pretty(&*devices)?;
+        let path = self.directory.lock().await.join(DEVICE_FILE);
+        write_atomic(&path, &content).await
+    }
+
+    pub async fn load_profile(&self, name: &str) -> Result<Option<Profile>> {
+        load_file(&self.profile_path(ProfileType::Profile, name).await).await
+    }
+
+    pub async fn save_profile(&self, name: &str, profile: &Profile) -> Result<()> {
+        let content = serde_json::to_vec_pretty(profile)?;
+        self.save(ProfileType::Profile, name, &content).await
+    }
+
+    pub async fn load_mic_profile(&self, name: &str) -> Result<Option<MicProfile>> {
+        load_file(&self.profile_path(ProfileType::MicProfile, name).await).await
+    }
+
+    pub async fn save_mic_profile(&self, name: &str, profile: &MicProfile) -> Result<()> {
+        let content = serde_json::to_vec_pretty(profile)?;
+        self.save(ProfileType::MicProfile, name, &content).await
+    }
+
+    /// Scribble icons live in their own directory alongside the profiles, absolute paths are
+    /// used as-is.
+    pub async fn icon_path(&self, image: &Path) -> PathBuf {
+        if image.is_absolute() {
+            return image.to_path_buf();
+        }
+        self.directory.lock().await.join(ICON_DIR).join(image)
+    }
+
+    /// Creates a new profile from the defaults
+    pub async fn create_profile(&self, profile_type: ProfileType, name: &str) -> Result<()> {
+        check_name(name)?;
+        if self.profile_path(profile_type, name).await.exists() {
+            bail!("Profile {} already exists", name);
+        }
+
+        let content = match profile_type {
+            ProfileType::Profile => serde_json::to_vec_pretty(&Profile::default())?,
+            ProfileType::MicProfile => serde_json::to_vec_pretty(&MicProfile::default())?,
+        };
+        self.save(profile_type, name, &content).await
+    }
+
+    /// Profiles active on an attached device shouldn't be deleted or renamed, it's up to the
+    /// device manager to check that first.
+    pub async fn delete_profile(&self, profile_type: ProfileType, name: &str) -> Result<()> {
+        check_name(name)?;
+
+        let path = self.profile_path(profile_type, name).await;
+        if !path.exists() {
+            bail!("Profile {} does not exist", name);
+        }
+        fs::remove_file(path).await?;
+
+        let mut list = self.list.lock().await;
+        names_for(&mut list, profile_type).retain(|entry| entry != name);
+        Ok(())
+    }
+
+    pub async fn rename_profile(
+        &self,
+        profile_type: ProfileType,
+        from: &str,
+        to: &str,
+    ) -> Result<()> {
+        check_name(from)?;
+        check_name(to)?;
+
+        let source = self.profile_path(profile_type, from).await;
+        let target = self.profile_path(profile_type, to).await;
+        if !source.exists() {
+            bail!("Profile {} does not exist", from);
+        }
+        if target.exists() {
+            bail!("Profile {} already exists", to);
+        }
+        fs::rename(source, target).await?;
+
+        let mut list = self.list.lock().await;
+        let names = names_for(&mut list, profile_type);
+        names.retain(|entry| entry != from);
+        add_name(names, to);
+        Ok(())
+    }
+
+    async fn save(&self, profile_type: ProfileType, name: &str, content: &[u8]) -> Result<()> {
+        write_atomic(&self.profile_path(profile_type, name).await, content).await?;
+
+        let mut list = self.list.lock().await;
+        add_name(names_for(&mut list, profile_type), name);
+        Ok(())
+    }
+
+    async fn profile_path(&self, profile_type: ProfileType, name: &str) -> PathBuf {
+        let file = format!("{}.{}", name, EXTENSION);
+        let directory = self.directory.lock().await;
+        directory.join(type_directory(profile_type)).join(file)
+    }
+}
+
+fn type_directory(profile_type: ProfileType) -> &'static str {
+    match profile_type {
+        ProfileType::Profile => PROFILE_DIR,
+        ProfileType::MicProfile => MIC_PROFILE_DIR,
+    }
+}
+
+fn names_for(list: &mut ProfileList, profile_type: ProfileType) -> &mut Vec<String> {
+    match profile_type {
+        ProfileType::Profile => &mut list.profiles,
+        ProfileType::MicProfile => &mut list.mic_profiles,
+    }
+}
+
+fn add_name(names: &mut Vec<String>, name: &str) {
+    if let Err(position) = names.binary_search_by(|entry| entry.as_str().cmp(name)) {
+        names.insert(position, name.to_string());
+    }
+}
+
+/// Names are used directly as file names, so make sure they can't escape the directory.
+fn check_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        bail!("Invalid Profile Name: {}", name);
+    }
+    Ok(())
+}
+
+fn scan_directory(directory: &Path) -> ProfileList {
+    let mut list = ProfileList::default();
+    for profile_type in [ProfileType::Profile, ProfileType::MicProfile] {
+        let path = directory.join(type_directory(profile_type));
+        let Ok(entries) = std::fs::read_dir(&path) else {
+            continue;
+        };
+
+        let names = names_for(&mut list, profile_type);
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(EXTENSION) {
+                continue;
+            }
+            if let Some(name) = path.file_stem().and_then(|n| n.to_str()) {
+                if !name.starts_with('.') {
+                    add_name(names, name);
+                }
+            }
+        }
+    }
+    list
+}
+
+fn read_devices(directory: &Path) -> BTreeMap<String, DeviceProfiles> {
+    let device_file = directory.join(DEVICE_FILE);
+    match std::fs::read(&device_file) {
+        Ok(content) => serde_json::from_slice(&content).unwrap_or_else(|e| {
+            warn!("Unable to parse {:?}, ignoring: {}", device_file, e);
+            BTreeMap::default()
+        }),
+        Err(_) => BTreeMap::default(),
+    }
+}
+
+/// Loads and parses a file, returning None if it doesn't exist. If the file exists but can't be
+/// parsed, it's moved out of the way so it doesn't get overwritten by the next save.
+async fn load_file<T: DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    debug!("Loading {:?}", path);
+    let content = fs::read(path).await?;
+    match serde_json::from_slice(&content) {
+        Ok(value) => Ok(Some(value)),
+        Err(error) => {
+            let backup = path.with_extension("corrupt");
+            warn!(
+                "Unable to parse {:?}, moving to {:?}: {}",
+                path, backup, error
+            );
+            fs::rename(path, backup).await?;
+            Ok(None)
+        }
+    }
+}
+
+/// Writes the file via a temporary file alongside it, this is also used for the settings file.
+pub(crate) async fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+    let parent = path.parent().context("Invalid Path")?;
+    fs::create_dir_all(parent).await?;
+
+    // Several devices can save the same profile at once, so each write needs its own temp file,
+    // otherwise one rename could swap in a file the other is still writing.
+    let file_name = path.file_name().context("Invalid Path")?;
+    let id = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let temp_name = format!(
+        ".{}.{}-{}.tmp",
+        file_name.to_string_lossy(),
+        process::id(),
+        id
+    );
+    let temp_path = parent.join(temp_name);
+
+    // Write out to the temporary file, and make sure it's hit the disk before we swap it in.
+    let mut result = write_synced(&temp_path, content).await;
+    if result.is_ok() {
+        result = fs::rename(&temp_path, path).await;
+    }
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp_path).await;
+        return Err(e.into());
+    }
+    debug!("Saved {:?}", path);
+    Ok(())
+}
+
+async fn write_synced(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let mut file = fs::File::create(path).await?;
+    file.write_all(content).await?;
+    file.sync_all().await
+}
@@ -0,0 +1,123 @@
+/*
+   Actix's Condition middleware decides whether to apply the wrapped middleware when the App is
+   built, which only happens once per worker when the server starts. LiveCondition does the same
+   job, but checks a flag on every request, so settings like CORS can be toggled while the server
+   is running.
+*/
+
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use actix_web::body::EitherBody;
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::Error;
+
+type LocalFuture<T> = Pin<Box<dyn Future<Output = T>>>;
+
+pub(crate) struct LiveCondition<T> {
+    enabled: Arc<AtomicBool>,
+    transformer: T,
+}
+
+impl<T> LiveCondition<T> {
+    pub fn new(enabled: Arc<AtomicBool>, transformer: T) -> Self {
+        Self {
+            enabled,
+            transformer,
+        }
+    }
+}
+
+impl<S, T, B, BT> Transform<S, ServiceRequest> for LiveCondition<T>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    T: Transform<SharedService<S>, ServiceRequest, Response = ServiceResponse<BT>, Error = Error>,
+    T::Transform: 'static,
+    T::InitError: 'static,
+    T::Future: 'static,
+    <T::Transform as Service<ServiceRequest>>::Future: 'static,
+{
+    type Response = ServiceResponse<EitherBody<BT, B>>;
+    type Error = Error;
+    type Transform = LiveConditionMiddleware<T::Transform, S>;
+    type InitError = T::InitError;
+    type Future = LocalFuture<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        // Both paths need the inner service, so it's shared between the middleware and us
+        let service = Rc::new(service);
+        let wrapped = self
+            .transformer
+            .new_transform(SharedService(service.clone()));
+        let enabled = self.enabled.clone();
+
+        Box::pin(async move {
+            Ok(LiveConditionMiddleware {
+                enabled,
+                wrapped: wrapped.await?,
+                service,
+            })
+        })
+    }
+}
+
+pub(crate) struct LiveConditionMiddleware<W, S> {
+    enabled: Arc<AtomicBool>,
+    wrapped: W,
+    service: Rc<S>,
+}
+
+impl<W, S, BW, B> Service<ServiceRequest> for LiveConditionMiddleware<W, S>
+where
+    W: Service<ServiceRequest, Response = ServiceResponse<BW>, Error = Error>,
+    W::Future: 'static,
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse<EitherBody<BW, B>>;
+    type Error = Error;
+    type Future = LocalFuture<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if self.enabled.load(Ordering::Relaxed) {
+            self.wrapped.poll_ready(cx)
+        } else {
+            self.service.poll_ready(cx)
+        }
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        if self.enabled.load(Ordering::Relaxed) {
+            let response = self.wrapped.call(req);
+            Box::pin(async move { Ok(response.await?.map_into_left_body()) })
+        } else {
+            let response = self.service.call(req);
+            Box::pin(async move { Ok(response.await?.map_into_right_body()) })
+        }
+    }
+}
+
+/// Lets the wrapped middleware hold the inner service, while we keep a handle to call it directly
+pub(crate) struct SharedService<S>(Rc<S>);
+
+impl<S, Req> Service<Req> for SharedService<S>
+where
+    S: Service<Req>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_ready(cx)
+    }
+
+    fn call(&self, req: Req) -> Self::Future {
+        self.0.call(req)
+    }
+}
@@ -1,13 +1,14 @@
 use std::ops::DerefMut;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 
 use actix::{
-    Actor, ActorContext, AsyncContext, ContextFutureSpawner, Handler, Message, StreamHandler,
-    WrapFuture,
+    Actor, ActorContext, Addr, AsyncContext, ContextFutureSpawner, Handler, Message, SpawnHandle,
+    StreamHandler, WrapFuture,
 };
 use actix_cors::Cors;
 use actix_web::dev::ServerHandle;
 use actix_web::http::header::ContentType;
-use actix_web::middleware::Condition;
 use actix_web::web::Data;
 use actix_web::{get, post, web, App, HttpRequest, HttpResponse, HttpServer};
 use actix_web_actors::ws;
@@ -18,15 +19,20 @@ use json_patch::Patch;
 use log::{debug, error, info, warn};
 use mime_guess::MimeGuess;
 use serde_json::Value;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver as BroadcastReceiver;
 use tokio::sync::broadcast::Sender as BroadcastSender;
 use tokio::sync::oneshot::Sender;
 use tokio::sync::Mutex;
 
 use goxlr_ipc::commands::{
-    DaemonRequest, DaemonResponse, DaemonStatus, HttpSettings, WebsocketRequest, WebsocketResponse,
+    DaemonRequest, DaemonResponse, DaemonStatus, HttpSettings, Subscription, WebsocketRequest,
+    WebsocketResponse,
 };
+use goxlr_ipc::status::{InteractionEvent, MicLevelMeter};
 
-use crate::device::packet::{handle_packet, Messenger};
+use crate::device::packet::{handle_packet, subscribe_patches, Messenger};
+use crate::servers::condition::LiveCondition;
 
 const WEB_CONTENT: Dir = include_dir!("./goxlr-daemon/web-content/");
 
@@ -38,38 +44,164 @@ pub struct PatchEvent {
 struct Websocket {
     usb_tx: Messenger,
     broadcast_tx: BroadcastSender<PatchEvent>,
-}
+    meter_tx: BroadcastSender<MicLevelMeter>,
+    interaction_tx: BroadcastSender<InteractionEvent>,
 
-impl Actor for Websocket {
-    type Context = ws::WebsocketContext<Self>;
+    // The futures forwarding events, while this client is subscribed
+    patch_handle: Option<SpawnHandle>,
+    meter_handle: Option<SpawnHandle>,
+    interaction_handle: Option<SpawnHandle>,
+}
 
-    fn started(&mut self, ctx: &mut Self::Context) {
+impl Websocket {
+    fn subscribe(
+        &mut self,
+        id: u64,
+        subscription: Subscription,
+        ctx: &mut <Self as Actor>::Context,
+    ) {
         let address = ctx.address();
-        let mut broadcast_rx = self.broadcast_tx.subscribe();
-
-        // Create a future that simply monitors the global broadcast bus, and pushes any changes
-        // out to the WebSocket.
-        let future = Box::pin(async move {
-            loop {
-                if let Ok(event) = broadcast_rx.recv().await {
-                    // We've received a message, attempt to trigger the WsMessage Handle..
-                    if let Err(error) = address.clone().try_send(WsResponse(WebsocketResponse {
-                        id: u64::MAX,
-                        data: DaemonResponse::Patch(event.data),
-                    })) {
-                        error!(
-                            "Error Occurred when sending message to websocket: {:?}",
-                            error
-                        );
-                        warn!("Aborting Websocket pushes for this client.");
+        match subscription {
+            Subscription::Patches => {
+                // Any existing subscription is replaced, the new status is the baseline for
+                // the patches which follow it.
+                if let Some(handle) = self.patch_handle.take() {
+                    ctx.cancel_future(handle);
+                }
+
+                let usb_tx = self.usb_tx.clone();
+                let future = Box::pin(async move {
+                    let (status, patch_rx) = match subscribe_patches(usb_tx.clone()).await {
+                        Ok(subscription) => subscription,
+                        Err(error) => {
+                            address.do_send(WsResponse(WebsocketResponse {
+                                id,
+                                data: DaemonResponse::Err(error.to_string()),
+                            }));
+                            return;
+                        }
+                    };
+                    address.do_send(WsResponse(WebsocketResponse {
+                        id,
+                        data: DaemonResponse::Status(status),
+                    }));
+                    forward_patches(address, usb_tx, patch_rx).await;
+                });
+                self.patch_handle = Some(ctx.spawn(future.into_actor(self)));
+            }
+            Subscription::MicLevel => {
+                if self.meter_handle.is_none() {
+                    let meter_rx = self.meter_tx.subscribe();
+                    let future = forward_events(address, meter_rx, DaemonResponse::MicLevel);
+                    self.meter_handle = Some(ctx.spawn(Box::pin(future).into_actor(self)));
+                }
+                ctx.address().do_send(WsResponse(WebsocketResponse {
+                    id,
+                    data: DaemonResponse::Ok,
+                }));
+            }
+            Subscription::Interactions => {
+                if self.interaction_handle.is_none() {
+                    let interaction_rx = self.interaction_tx.subscribe();
+                    let future =
+                        forward_events(address, interaction_rx, DaemonResponse::Interaction);
+                    self.interaction_handle = Some(ctx.spawn(Box::pin(future).into_actor(self)));
+                }
+                ctx.address().do_send(WsResponse(WebsocketResponse {
+                    id,
+                    data: DaemonResponse::Ok,
+                }));
+            }
+        }
+    }
+
+    fn unsubscribe(&mut self, subscription: Subscription, ctx: &mut <Self as Actor>::Context) {
+        // Dropping the future also drops the receiver, so the device can stop sampling
+        let handle = match subscription {
+            Subscription::Patches => self.patch_handle.take(),
+            Subscription::MicLevel => self.meter_handle.take(),
+            Subscription::Interactions => self.interaction_handle.take(),
+        };
+        if let Some(handle) = handle {
+            ctx.cancel_future(handle);
+        }
+    }
+}
+
+/// Pushes events from a broadcast bus out to the WebSocket, until either side goes away
+async fn forward_events<T: Clone>(
+    address: Addr<Websocket>,
+    mut receiver: BroadcastReceiver<T>,
+    event: fn(T) -> DaemonResponse,
+) {
+    loop {
+        let data = match receiver.recv().await {
+            Ok(data) => event(data),
+            Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => break,
+        };
+
+        if !push_event(&address, data) {
+            break;
+        }
+    }
+}
+
+/// Pushes patches out to the WebSocket. If the client falls behind the patches it missed are
+/// gone, so it's sent a fresh Status to carry on from instead.
+async fn forward_patches(
+    address: Addr<Websocket>,
+    usb_tx: Messenger,
+    mut receiver: BroadcastReceiver<PatchEvent>,
+) {
+    loop {
+        let data = match receiver.recv().await {
+            Ok(event) => DaemonResponse::Patch(event.data),
+            Err(RecvError::Lagged(count)) => {
+                warn!("Websocket Client fell behind, {} patches dropped", count);
+                match subscribe_patches(usb_tx.clone()).await {
+                    Ok((status, patch_rx)) => {
+                        receiver = patch_rx;
+                        DaemonResponse::Status(status)
+                    }
+                    Err(error) => {
+                        error!("Unable to resync Websocket client: {:?}", error);
                         break;
                     }
                 }
             }
-        });
+            Err(RecvError::Closed) => break,
+        };
 
-        let future = future.into_actor(self);
-        ctx.spawn(future);
+        if !push_event(&address, data) {
+            break;
+        }
+    }
+}
+
+/// Sends an event to the WebSocket, returns false if it's gone
+fn push_event(address: &Addr<Websocket>, data: DaemonResponse) -> bool {
+    let response = WsResponse(WebsocketResponse { id: u64::MAX, data });
+    if let Err(error) = address.try_send(response) {
+        error!(
+            "Error Occurred when sending message to websocket: {:?}",
+            error
+        );
+        warn!("Aborting Websocket pushes for this client.");
+        return false;
+    }
+    true
+}
+
+impl Actor for Websocket {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        // Clients are subscribed to patches from the start, a Subscribe request is only needed
+        // to get a status which is in step with them.
+        let broadcast_rx = self.broadcast_tx.subscribe();
+        let future = forward_patches(ctx.address(), self.usb_tx.clone(), broadcast_rx);
+        self.patch_handle = Some(ctx.spawn(Box::pin(future).into_actor(self)));
     }
 }
 
@@ -93,6 +225,20 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for Websocket {
             Ok(ws::Message::Ping(msg)) => ctx.pong(&msg),
             Ok(ws::Message::Text(text)) => {
                 match serde_json::from_slice::<WebsocketRequest>(text.as_ref()) {
+                    Ok(WebsocketRequest {
+                        id,
+                        data: DaemonRequest::Subscribe(subscription),
+                    }) => self.subscribe(id, subscription, ctx),
+                    Ok(WebsocketRequest {
+                        id,
+                        data: DaemonRequest::Unsubscribe(subscription),
+                    }) => {
+                        self.unsubscribe(subscription, ctx);
+                        ctx.address().do_send(WsResponse(WebsocketResponse {
+                            id,
+                            data: DaemonResponse::Ok,
+                        }));
+                    }
                     Ok(request) => {
                         let recipient = ctx.address().recipient();
                         let usb_tx = self.usb_tx.clone();
@@ -197,13 +343,18 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for Websocket {
 struct AppData {
     messenger: Messenger,
     broadcast_tx: BroadcastSender<PatchEvent>,
+    meter_tx: BroadcastSender<MicLevelMeter>,
+    interaction_tx: BroadcastSender<InteractionEvent>,
 }
 
 pub async fn spawn_http_server(
     messenger: Messenger,
     handle_tx: Sender<ServerHandle>,
     broadcast_tx: tokio::sync::broadcast::Sender<PatchEvent>,
+    meter_tx: BroadcastSender<MicLevelMeter>,
+    interaction_tx: BroadcastSender<InteractionEvent>,
     settings: HttpSettings,
+    cors_enabled: Arc<AtomicBool>,
 ) {
     let server = HttpServer::new(move || {
         let cors = Cors::default()
@@ -215,9 +366,11 @@ pub async fn spawn_http_server(
             .allow_any_header()
             .max_age(300);
         App::new()
-            .wrap(Condition::new(settings.cors_enabled, cors))
+            .wrap(LiveCondition::new(cors_enabled.clone(), cors))
             .app_data(Data::new(Mutex::new(AppData {
                 broadcast_tx: broadcast_tx.clone(),
+                meter_tx: meter_tx.clone(),
+                interaction_tx: interaction_tx.clone(),
                 messenger: messenger.clone(),
             })))
             .service(execute_command)
@@ -260,6 +413,11 @@ async fn websocket(
         Websocket {
             usb_tx: data.messenger.clone(),
             broadcast_tx: data.broadcast_tx.clone(),
+            meter_tx: data.meter_tx.clone(),
+            interaction_tx: data.interaction_tx.clone(),
+            patch_handle: None,
+            meter_handle: None,
+            interaction_handle: None,
         },
         &req,
         stream,
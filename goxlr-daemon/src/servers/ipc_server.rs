@@ -1,22 +1,27 @@
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 use interprocess::local_socket::tokio::{LocalSocketListener, LocalSocketStream};
 use interprocess::local_socket::NameTypeSupport;
 use log::{debug, info, warn};
 use std::fs;
 use std::path::Path;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
 
 use NameTypeSupport::*;
 
-use crate::device::packet::{handle_packet, Messenger};
+use crate::device::packet::{handle_packet, subscribe_patches, Messenger};
+use crate::servers::http_server::PatchEvent;
 use goxlr_ipc::clients::ipc::ipc_socket::Socket;
-use goxlr_ipc::commands::{DaemonRequest, DaemonResponse};
+use goxlr_ipc::commands::{
+    DaemonRequest, DaemonResponse, IPCRequest, IPCResponse, Subscription, WebsocketResponse,
+};
+use goxlr_ipc::status::{InteractionEvent, MicLevelMeter};
 
 use crate::Stop;
 
-static SOCKET_PATH: &str = "/tmp/goxlr.socket";
 static NAMED_PIPE: &str = "@goxlr.socket";
 
-async fn ipc_tidy() -> Result<()> {
+async fn ipc_tidy(socket_path: &str) -> Result<()> {
     // We only need a possible cleanup if we're using file based sockets..
     let socket_type = NameTypeSupport::query();
     if socket_type == OnlyNamespaced {
@@ -24,16 +29,16 @@ async fn ipc_tidy() -> Result<()> {
     }
 
     // Check to see if the socket exists,
-    if !Path::new(SOCKET_PATH).exists() {
+    if !Path::new(socket_path).exists() {
         return Ok(());
     }
 
     debug!("Existing Socket Present, testing..");
     // Try sending a message to the socket, see if we get a reply..
-    let connection = LocalSocketStream::connect(SOCKET_PATH).await;
+    let connection = LocalSocketStream::connect(socket_path).await;
     if connection.is_err() {
         debug!("Unable to connect to the socket, removing..");
-        fs::remove_file(SOCKET_PATH)?;
+        fs::remove_file(socket_path)?;
         return Ok(());
     }
 
@@ -42,7 +47,7 @@ async fn ipc_tidy() -> Result<()> {
     let mut socket: Socket<DaemonResponse, DaemonRequest> = Socket::new(connection);
     if socket.send(DaemonRequest::Ping).await.is_err() {
         debug!("Socket Not Active, removing file..");
-        fs::remove_file(SOCKET_PATH)?;
+        fs::remove_file(socket_path)?;
         return Ok(());
     }
 
@@ -50,12 +55,12 @@ async fn ipc_tidy() -> Result<()> {
     bail!("The GoXLR Daemon is already running.");
 }
 
-pub async fn bind_socket() -> Result<LocalSocketListener> {
-    ipc_tidy().await?;
+pub async fn bind_socket(socket_path: &str) -> Result<LocalSocketListener> {
+    ipc_tidy(socket_path).await?;
 
     let name = {
         match NameTypeSupport::query() {
-            OnlyPaths | Both => SOCKET_PATH,
+            OnlyPaths | Both => socket_path,
             OnlyNamespaced => NAMED_PIPE,
         }
     };
@@ -67,7 +72,10 @@ pub async fn bind_socket() -> Result<LocalSocketListener> {
 
 pub async fn spawn_ipc_server(
     listener: LocalSocketListener,
+    socket_path: String,
     usb_tx: Messenger,
+    meter_tx: broadcast::Sender<MicLevelMeter>,
+    interaction_tx: broadcast::Sender<InteractionEvent>,
     mut shutdown_signal: Stop,
 ) {
     debug!("Running IPC Server..");
@@ -76,15 +84,19 @@ pub async fn spawn_ipc_server(
             Ok(connection) = listener.accept() => {
                 let socket = Socket::new(connection);
                 let usb_tx = usb_tx.clone();
+                let broadcasts = Broadcasts {
+                    meter_tx: meter_tx.clone(),
+                    interaction_tx: interaction_tx.clone(),
+                };
                 tokio::spawn(async move {
-                    handle_connection(socket, usb_tx).await;
+                    handle_connection(socket, usb_tx, broadcasts).await;
                 });
             }
             () = shutdown_signal.recv() => {
                 // If we're using a unix domain socket, remove it.
                 match NameTypeSupport::query() {
                     OnlyPaths | Both => {
-                        let _ = fs::remove_file(SOCKET_PATH);
+                        let _ = fs::remove_file(&socket_path);
                     },
                     OnlyNamespaced => {},
                 }
@@ -94,25 +106,169 @@ pub async fn spawn_ipc_server(
     }
 }
 
-async fn handle_connection(mut socket: Socket<DaemonRequest, DaemonResponse>, usb_tx: Messenger) {
-    while let Some(msg) = socket.read().await {
-        match msg {
-            Ok(msg) => match handle_packet(msg, usb_tx.clone()).await {
-                Ok(response) => {
-                    if let Err(e) = socket.send(response).await {
-                        warn!("Couldn't reply to {:?}: {}", socket.address(), e);
-                        return;
+// Events pushed to subscribers are tagged with this, rather than a request id
+const EVENT_ID: u64 = u64::MAX;
+
+/// The events a connection is subscribed to, the receivers only exist while subscribed
+#[derive(Default)]
+struct Subscriptions {
+    // Whether events should be tagged, this is set by the connection's first Subscribe request
+    tagged: Option<bool>,
+
+    patches: Option<broadcast::Receiver<PatchEvent>>,
+    mic_level: Option<broadcast::Receiver<MicLevelMeter>>,
+    interactions: Option<broadcast::Receiver<InteractionEvent>>,
+}
+
+/// The event buses a connection can subscribe to, other than patches (which come from the manager)
+struct Broadcasts {
+    meter_tx: broadcast::Sender<MicLevelMeter>,
+    interaction_tx: broadcast::Sender<InteractionEvent>,
+}
+
+async fn handle_connection(
+    mut socket: Socket<IPCRequest, IPCResponse>,
+    usb_tx: Messenger,
+    broadcasts: Broadcasts,
+) {
+    let mut subscriptions = Subscriptions::default();
+
+    loop {
+        let event = tokio::select! {
+            msg = socket.read() => {
+                let Some(msg) = msg else {
+                    break;
+                };
+
+                let msg = match msg {
+                    Ok(msg) => msg,
+                    Err(e) => {
+                        warn!("Invalid message from {:?}: {}", socket.address(), e);
+                        continue;
+                    }
+                };
+
+                let (id, request) = match msg {
+                    IPCRequest::Tagged(request) => (Some(request.id), request.data),
+                    IPCRequest::Plain(request) => (None, request),
+                };
+
+                let response = match request {
+                    DaemonRequest::Subscribe(subscription) => {
+                        // Events all go down the same socket, so they can't be tagged for some
+                        // subscriptions and not others
+                        let tagged = *subscriptions.tagged.get_or_insert(id.is_some());
+                        if tagged == id.is_some() {
+                            subscribe(&mut subscriptions, subscription, &usb_tx, &broadcasts).await
+                        } else {
+                            Err(anyhow!("Subscriptions must all be tagged, or all be plain"))
+                        }
                     }
+                    DaemonRequest::Unsubscribe(subscription) => {
+                        unsubscribe(&mut subscriptions, subscription);
+                        Ok(DaemonResponse::Ok)
+                    }
+                    request => handle_packet(request, usb_tx.clone()).await,
+                };
+
+                let data = response.unwrap_or_else(|e| DaemonResponse::Err(e.to_string()));
+                let response = match id {
+                    Some(id) => IPCResponse::Tagged(WebsocketResponse { id, data }),
+                    None => IPCResponse::Plain(data),
+                };
+
+                if let Err(e) = socket.send(response).await {
+                    warn!("Couldn't reply to {:?}: {}", socket.address(), e);
+                    return;
                 }
-                Err(e) => {
-                    if let Err(e) = socket.send(DaemonResponse::Err(e.to_string())).await {
-                        warn!("Couldn't reply to {:?}: {}", socket.address(), e);
-                        return;
+                continue;
+            }
+            patch = next_event(&mut subscriptions.patches) => match patch {
+                Some(patch) => DaemonResponse::Patch(patch.data),
+                None => {
+                    // The missed patches are gone, so send a fresh status to carry on from
+                    let resync = Subscription::Patches;
+                    match subscribe(&mut subscriptions, resync, &usb_tx, &broadcasts).await {
+                        Ok(status) => status,
+                        Err(e) => {
+                            warn!("Couldn't resync {:?}: {}", socket.address(), e);
+                            subscriptions.patches = None;
+                            continue;
+                        }
                     }
                 }
             },
-            Err(e) => warn!("Invalid message from {:?}: {}", socket.address(), e),
+            meter = next_event(&mut subscriptions.mic_level) => match meter {
+                Some(meter) => DaemonResponse::MicLevel(meter),
+                None => continue,
+            },
+            event = next_event(&mut subscriptions.interactions) => match event {
+                Some(event) => DaemonResponse::Interaction(event),
+                None => continue,
+            },
+        };
+
+        let event = match subscriptions.tagged == Some(true) {
+            true => IPCResponse::Tagged(WebsocketResponse {
+                id: EVENT_ID,
+                data: event,
+            }),
+            false => IPCResponse::Plain(event),
+        };
+        if let Err(e) = socket.send(event).await {
+            warn!("Couldn't send event to {:?}: {}", socket.address(), e);
+            return;
         }
     }
     debug!("Disconnected {:?}", socket.address());
 }
+
+async fn subscribe(
+    subscriptions: &mut Subscriptions,
+    subscription: Subscription,
+    usb_tx: &Messenger,
+    broadcasts: &Broadcasts,
+) -> Result<DaemonResponse> {
+    match subscription {
+        Subscription::Patches => {
+            // Re-subscribing provides a new status, so start again from there
+            let (status, receiver) = subscribe_patches(usb_tx.clone()).await?;
+            subscriptions.patches = Some(receiver);
+            Ok(DaemonResponse::Status(status))
+        }
+        Subscription::MicLevel => {
+            let mic_level = &mut subscriptions.mic_level;
+            mic_level.get_or_insert_with(|| broadcasts.meter_tx.subscribe());
+            Ok(DaemonResponse::Ok)
+        }
+        Subscription::Interactions => {
+            let interactions = &mut subscriptions.interactions;
+            interactions.get_or_insert_with(|| broadcasts.interaction_tx.subscribe());
+            Ok(DaemonResponse::Ok)
+        }
+    }
+}
+
+fn unsubscribe(subscriptions: &mut Subscriptions, subscription: Subscription) {
+    match subscription {
+        Subscription::Patches => subscriptions.patches = None,
+        Subscription::MicLevel => subscriptions.mic_level = None,
+        Subscription::Interactions => subscriptions.interactions = None,
+    }
+}
+
+/// Waits for the next event from a subscription, if we're not subscribed this never completes.
+/// Returns None if the client fell behind, and some events were dropped.
+async fn next_event<T: Clone>(receiver: &mut Option<broadcast::Receiver<T>>) -> Option<T> {
+    if let Some(receiver) = receiver {
+        match receiver.recv().await {
+            Ok(event) => return Some(event),
+            Err(RecvError::Lagged(count)) => {
+                warn!("IPC Client fell behind, {} events dropped", count);
+                return None;
+            }
+            Err(RecvError::Closed) => {}
+        }
+    }
+    std::future::pending().await
+}
@@ -1,2 +1,3 @@
+mod condition;
 pub(crate) mod http_server;
 pub(crate) mod ipc_server;
@@ -0,0 +1,336 @@
+/*
+   Daemon Settings, these are stored as JSON in the user's config directory, and can be overridden
+   by command line arguments (see cli.rs).
+
+   The file is watched while the daemon is running, changes to the log level, CORS, profile
+   directory and mic meter interval are applied immediately, the capture directory applies to
+   devices connected after the change, while the HTTP and socket configuration are only read on
+   startup and require a restart.
+*/
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use directories::ProjectDirs;
+use log::{debug, info, warn, LevelFilter};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, RwLock};
+
+use goxlr_ipc::commands::{HttpSettings, LogLevel};
+
+use crate::cli::Cli;
+use crate::profiles::{write_atomic, ProfileStore};
+use crate::stop::Stop;
+
+static SETTINGS_FILE: &str = "settings.json";
+static DEFAULT_SOCKET_PATH: &str = "/tmp/goxlr.socket";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub http: HttpSettings,
+    pub socket_path: String,
+    pub log_level: LogLevel,
+    pub profile_directory: PathBuf,
+    pub capture_directory: Option<PathBuf>,
+
+    /// How often the Microphone level is sampled while clients are subscribed, in milliseconds
+    pub mic_meter_interval: u64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            http: HttpSettings {
+                enabled: true,
+                bind_address: "localhost".to_string(),
+                cors_enabled: false,
+                port: 14564,
+            },
+            socket_path: DEFAULT_SOCKET_PATH.to_string(),
+            log_level: LogLevel::Debug,
+            profile_directory: ProfileStore::default_directory(),
+            capture_directory: None,
+            mic_meter_interval: 50,
+        }
+    }
+}
+
+impl Settings {
+    fn apply_overrides(&mut self, cli: &Cli) {
+        if cli.http_disable {
+            self.http.enabled = false;
+        }
+        if let Some(address) = &cli.http_bind_address {
+            self.http.bind_address = address.clone();
+        }
+        if let Some(port) = cli.http_port {
+            self.http.port = port;
+        }
+        if cli.http_enable_cors {
+            self.http.cors_enabled = true;
+        }
+        if let Some(path) = &cli.socket_path {
+            self.socket_path = path.clone();
+        }
+        if let Some(level) = cli.log_level {
+            self.log_level = level_from_filter(level);
+        }
+        if let Some(directory) = &cli.profile_directory {
+            self.profile_directory = directory.clone();
+        }
+        if let Some(directory) = &cli.capture_directory {
+            self.capture_directory = Some(directory.clone());
+        }
+    }
+
+    /// Returns the names of the settings which differ between the two
+    fn differences(&self, other: &Settings) -> Vec<&'static str> {
+        let mut names = vec![];
+        if self.http.enabled != other.http.enabled {
+            names.push("http.enabled");
+        }
+        if self.http.bind_address != other.http.bind_address {
+            names.push("http.bind_address");
+        }
+        if self.http.cors_enabled != other.http.cors_enabled {
+            names.push("http.cors_enabled");
+        }
+        if self.http.port != other.http.port {
+            names.push("http.port");
+        }
+        if self.socket_path != other.socket_path {
+            names.push("socket_path");
+        }
+        if self.log_level != other.log_level {
+            names.push("log_level");
+        }
+        if self.profile_directory != other.profile_directory {
+            names.push("profile_directory");
+        }
+        if self.capture_directory != other.capture_directory {
+            names.push("capture_directory");
+        }
+        if self.mic_meter_interval != other.mic_meter_interval {
+            names.push("mic_meter_interval");
+        }
+        names
+    }
+}
+
+#[derive(Clone)]
+pub struct SettingsHandle {
+    path: PathBuf,
+    overrides: Arc<Cli>,
+    settings: Arc<RwLock<Settings>>,
+}
+
+impl SettingsHandle {
+    /// Loads the settings from disk, creating the file with the defaults if it doesn't exist.
+    pub fn load(cli: Cli) -> Result<Self> {
+        let path = match &cli.config {
+            Some(path) => path.clone(),
+            None => default_settings_path(),
+        };
+
+        // This is only called during startup, so we can just do this synchronously.
+        let mut settings = if path.exists() {
+            let content = std::fs::read(&path)?;
+            serde_json::from_slice(&content)
+                .with_context(|| format!("Unable to parse settings file {:?}", path))?
+        } else {
+            let settings = Settings::default();
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, serde_json::to_vec_pretty(&settings)?)?;
+            settings
+        };
+        settings.apply_overrides(&cli);
+
+        Ok(Self {
+            path,
+            overrides: Arc::new(cli),
+            settings: Arc::new(RwLock::new(settings)),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub async fn get(&self) -> Settings {
+        self.settings.read().await.clone()
+    }
+
+    /// Updates the settings file, the watcher will then pick up and apply the change. Changes to
+    /// settings which are overridden on the command line are refused, as they'd never apply.
+    pub async fn update<F: FnOnce(&mut Settings)>(&self, change: F) -> Result<()> {
+        let content = tokio::fs::read(&self.path).await?;
+        let original: Settings = serde_json::from_slice(&content)?;
+
+        let mut settings = original.clone();
+        change(&mut settings);
+
+        let mut effective = settings.clone();
+        effective.apply_overrides(&self.overrides);
+
+        let overridden = settings.differences(&effective);
+        let blocked: Vec<_> = original
+            .differences(&settings)
+            .into_iter()
+            .filter(|name| overridden.contains(name))
+            .collect();
+        if !blocked.is_empty() {
+            bail!(
+                "Unable to change {}, overridden on the command line",
+                blocked.join(", ")
+            );
+        }
+
+        write_atomic(&self.path, &serde_json::to_vec_pretty(&settings)?).await
+    }
+
+    /// Re-reads the settings file, returning the old and new settings if anything has changed.
+    async fn reload(&self) -> Result<Option<(Settings, Settings)>> {
+        let content = tokio::fs::read(&self.path).await?;
+        let mut settings: Settings = serde_json::from_slice(&content)?;
+        settings.apply_overrides(&self.overrides);
+
+        let mut current = self.settings.write().await;
+        if *current == settings {
+            return Ok(None);
+        }
+
+        let old = std::mem::replace(&mut *current, settings.clone());
+        Ok(Some((old, settings)))
+    }
+}
+
+/// Values which can be changed while the daemon is running, these are shared with anything that
+/// needs to respond to the changes.
+#[derive(Clone)]
+pub struct LiveSettings {
+    pub cors_enabled: Arc<AtomicBool>,
+    pub profile_store: ProfileStore,
+    pub mic_meter_interval: Arc<AtomicU64>,
+}
+
+pub async fn spawn_settings_watcher(
+    handle: SettingsHandle,
+    live: LiveSettings,
+    mut stop: Stop,
+) -> Result<()> {
+    let (tx, mut rx) = mpsc::channel(8);
+
+    // Editors tend to replace files rather than writing to them, so we watch the directory and
+    // filter events for our file.
+    let file_name = handle.path.file_name().map(|name| name.to_os_string());
+    let mut watcher = RecommendedWatcher::new(
+        move |event: notify::Result<notify::Event>| {
+            if let Ok(event) = event {
+                let ours = event
+                    .paths
+                    .iter()
+                    .any(|path| path.file_name().map(|n| n.to_os_string()) == file_name);
+                if ours {
+                    let _ = tx.blocking_send(());
+                }
+            }
+        },
+        notify::Config::default(),
+    )?;
+
+    let directory = handle.path.parent().context("Invalid Settings Path")?;
+    watcher.watch(directory, RecursiveMode::NonRecursive)?;
+    debug!("Watching {:?} for changes", handle.path);
+
+    loop {
+        tokio::select! {
+            Some(()) = rx.recv() => {
+                match handle.reload().await {
+                    Ok(Some((old, new))) => apply_changes(&old, &new, &live).await,
+                    Ok(None) => {}
+                    Err(error) => warn!("Unable to reload settings, keeping existing: {}", error),
+                }
+            }
+            () = stop.recv() => {
+                break;
+            }
+        }
+    }
+
+    debug!("Settings Watcher Stopped");
+    Ok(())
+}
+
+async fn apply_changes(old: &Settings, new: &Settings, live: &LiveSettings) {
+    if old.log_level != new.log_level {
+        info!("Log Level changed to {:?}", new.log_level);
+        log::set_max_level(level_to_filter(new.log_level));
+    }
+
+    if old.http.cors_enabled != new.http.cors_enabled {
+        info!("CORS Enabled: {}", new.http.cors_enabled);
+        let cors = new.http.cors_enabled;
+        live.cors_enabled.store(cors, Ordering::Relaxed);
+    }
+
+    if old.profile_directory != new.profile_directory {
+        info!("Profile Directory changed to {:?}", new.profile_directory);
+        let directory = new.profile_directory.clone();
+        live.profile_store.set_directory(directory).await;
+    }
+
+    if old.mic_meter_interval != new.mic_meter_interval {
+        info!("Mic Meter Interval changed to {}ms", new.mic_meter_interval);
+        let interval = new.mic_meter_interval;
+        live.mic_meter_interval.store(interval, Ordering::Relaxed);
+    }
+
+    if old.capture_directory != new.capture_directory {
+        match &new.capture_directory {
+            Some(directory) => info!("New devices will be captured to {:?}", directory),
+            None => info!("USB Capture Disabled"),
+        }
+    }
+
+    let http_changed = old.http.enabled != new.http.enabled
+        || old.http.bind_address != new.http.bind_address
+        || old.http.port != new.http.port;
+    if http_changed || old.socket_path != new.socket_path {
+        warn!("HTTP and Socket changes will be applied when the daemon is restarted");
+    }
+}
+
+fn default_settings_path() -> PathBuf {
+    match ProjectDirs::from("org", "GoXLR-on-Linux", "GoXLR-Utility") {
+        Some(dirs) => dirs.config_dir().join(SETTINGS_FILE),
+        None => PathBuf::from(SETTINGS_FILE),
+    }
+}
+
+pub fn level_to_filter(level: LogLevel) -> LevelFilter {
+    match level {
+        LogLevel::Off => LevelFilter::Off,
+        LogLevel::Error => LevelFilter::Error,
+        LogLevel::Warn => LevelFilter::Warn,
+        LogLevel::Info => LevelFilter::Info,
+        LogLevel::Debug => LevelFilter::Debug,
+        LogLevel::Trace => LevelFilter::Trace,
+    }
+}
+
+fn level_from_filter(level: LevelFilter) -> LogLevel {
+    match level {
+        LevelFilter::Off => LogLevel::Off,
+        LevelFilter::Error => LogLevel::Error,
+        LevelFilter::Warn => LogLevel::Warn,
+        LevelFilter::Info => LogLevel::Info,
+        LevelFilter::Debug => LogLevel::Debug,
+        LevelFilter::Trace => LogLevel::Trace,
+    }
+}
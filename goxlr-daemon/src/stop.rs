@@ -35,6 +35,12 @@ impl Stop {
     }
 }
 
+impl Default for Stop {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Clone for Stop {
     fn clone(&self) -> Self {
         let sender = self.sender.clone();
@@ -1,4 +1,4 @@
-use crate::commands::{DaemonRequest, DaemonStatus, GoXLRCommand};
+use crate::commands::{DaemonCommand, DaemonRequest, DaemonStatus, GoXLRCommand};
 use anyhow::Result;
 use async_trait::async_trait;
 
@@ -7,5 +7,6 @@ pub trait Client {
     async fn send(&mut self, request: DaemonRequest) -> Result<()>;
     async fn poll_status(&mut self) -> Result<()>;
     async fn command(&mut self, serial: &str, command: GoXLRCommand) -> Result<()>;
+    async fn daemon(&mut self, command: DaemonCommand) -> Result<()>;
     fn status(&self) -> &DaemonStatus;
 }
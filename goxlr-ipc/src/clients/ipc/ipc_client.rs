@@ -1,56 +1,195 @@
+use std::collections::VecDeque;
+
 use crate::client::Client;
 use crate::clients::ipc::ipc_socket::Socket;
 use crate::commands::{
-    DaemonRequest, DaemonResponse, DaemonStatus, DeviceCommand, GoXLRCommand, GoXLRCommandResponse,
+    DaemonCommand, DaemonRequest, DaemonResponse, DaemonStatus, DeviceCommand, GoXLRCommand,
+    GoXLRCommandResponse, Subscription, WebsocketRequest, WebsocketResponse,
 };
+use crate::status::{InteractionEvent, MicLevelMeter};
 use anyhow::{anyhow, bail, Context, Result};
 use async_trait::async_trait;
 use interprocess::local_socket::tokio::LocalSocketStream;
 use interprocess::local_socket::NameTypeSupport;
+use json_patch::Patch;
 
 static SOCKET_PATH: &str = "/tmp/goxlr.socket";
 static NAMED_PIPE: &str = "@goxlr.socket";
 
+// Events are tagged with this instead of a request id
+const EVENT_ID: u64 = u64::MAX;
+
+// How many unread events to keep before dropping the oldest
+const EVENT_BUFFER: usize = 256;
+
+/// A client for the IPC Socket. Requests are tagged with an id, so events from any subscriptions
+/// can arrive between them. Patches are applied to the status as they arrive, so once subscribed
+/// to `Subscription::Patches` the status is kept up to date.
 #[derive(Debug)]
 pub struct IPCClient {
-    socket: Socket<DaemonResponse, DaemonRequest>,
+    socket: Socket<WebsocketResponse, WebsocketRequest>,
     status: DaemonStatus,
+    next_id: u64,
+
+    // Set if a patch couldn't be applied, and the status needs fetching again
+    resync: bool,
+
+    // Events which haven't yet been read
+    patches: VecDeque<Patch>,
+    mic_levels: VecDeque<MicLevelMeter>,
+    interactions: VecDeque<InteractionEvent>,
 }
 
 impl IPCClient {
     pub async fn connect() -> Result<Self> {
+        Self::connect_to(SOCKET_PATH).await
+    }
+
+    /// Connects to a daemon using a non-default socket path (ignored when using Named Pipes)
+    pub async fn connect_to(socket_path: &str) -> Result<Self> {
         let connection = LocalSocketStream::connect(match NameTypeSupport::query() {
-            NameTypeSupport::OnlyPaths | NameTypeSupport::Both => SOCKET_PATH,
+            NameTypeSupport::OnlyPaths | NameTypeSupport::Both => socket_path,
             NameTypeSupport::OnlyNamespaced => NAMED_PIPE,
         })
         .await?;
-        let socket: Socket<DaemonResponse, DaemonRequest> = Socket::new(connection);
+        let socket: Socket<WebsocketResponse, WebsocketRequest> = Socket::new(connection);
 
         Ok(IPCClient::start(socket))
     }
 
-    fn start(socket: Socket<DaemonResponse, DaemonRequest>) -> Self {
+    fn start(socket: Socket<WebsocketResponse, WebsocketRequest>) -> Self {
         Self {
             socket,
             status: DaemonStatus::default(),
+            next_id: 0,
+            resync: false,
+            patches: VecDeque::new(),
+            mic_levels: VecDeque::new(),
+            interactions: VecDeque::new(),
         }
     }
-}
 
-#[async_trait]
-impl Client for IPCClient {
-    async fn send(&mut self, request: DaemonRequest) -> Result<()> {
-        println!("Sending: {:?}", serde_json::to_string(&request));
+    pub async fn subscribe(&mut self, subscription: Subscription) -> Result<()> {
+        self.send(DaemonRequest::Subscribe(subscription)).await
+    }
+
+    pub async fn unsubscribe(&mut self, subscription: Subscription) -> Result<()> {
+        self.send(DaemonRequest::Unsubscribe(subscription)).await
+    }
+
+    /// Waits for the next Patch, by the time this returns it has already been applied to the
+    /// status. This requires a `Subscription::Patches`.
+    pub async fn next_patch(&mut self) -> Result<Patch> {
+        while self.patches.is_empty() {
+            self.read_event().await?;
+        }
+        Ok(self.patches.pop_front().unwrap())
+    }
+
+    /// Waits for the next Mic Level reading, this requires a `Subscription::MicLevel`
+    pub async fn next_mic_level(&mut self) -> Result<MicLevelMeter> {
+        while self.mic_levels.is_empty() {
+            self.read_event().await?;
+        }
+        Ok(self.mic_levels.pop_front().unwrap())
+    }
+
+    /// Waits for the next Interaction, this requires a `Subscription::Interactions`
+    pub async fn next_interaction(&mut self) -> Result<InteractionEvent> {
+        while self.interactions.is_empty() {
+            self.read_event().await?;
+        }
+        Ok(self.interactions.pop_front().unwrap())
+    }
+
+    /// Sends a request, and returns its response. Any events received while waiting are handled.
+    async fn request(&mut self, data: DaemonRequest) -> Result<DaemonResponse> {
+        let id = self.next_id;
+        self.next_id = (self.next_id + 1) % EVENT_ID;
+
         self.socket
-            .send(request)
+            .send(WebsocketRequest { id, data })
             .await
             .context("Failed to send a command to the GoXLR daemon process")?;
-        let result = self
-            .socket
+
+        loop {
+            let response = self.read().await?;
+            if response.id == id {
+                return Ok(response.data);
+            }
+            if response.id == EVENT_ID {
+                self.handle_event(response.data);
+            }
+        }
+    }
+
+    async fn read_event(&mut self) -> Result<()> {
+        let response = self.read().await?;
+        if response.id == EVENT_ID {
+            self.handle_event(response.data);
+        }
+        self.resync_status().await
+    }
+
+    /// If a patch failed to apply our status is out of date, so simply fetch a new one
+    async fn resync_status(&mut self) -> Result<()> {
+        while self.resync {
+            self.resync = false;
+            if let DaemonResponse::Status(status) = self.request(DaemonRequest::GetStatus).await? {
+                self.status = status;
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: DaemonResponse) {
+        match event {
+            DaemonResponse::Patch(patch) => {
+                if self.apply_patch(&patch).is_err() {
+                    self.resync = true;
+                }
+                push_event(&mut self.patches, patch);
+            }
+            // Sent in place of patches we fell behind on, so it replaces the status outright
+            DaemonResponse::Status(status) => {
+                self.status = status;
+                self.resync = false;
+            }
+            DaemonResponse::MicLevel(meter) => push_event(&mut self.mic_levels, meter),
+            DaemonResponse::Interaction(event) => push_event(&mut self.interactions, event),
+            _ => {}
+        }
+    }
+
+    fn apply_patch(&mut self, patch: &Patch) -> Result<()> {
+        let mut status = serde_json::to_value(&self.status)?;
+        json_patch::patch(&mut status, patch)?;
+        self.status = serde_json::from_value(status)?;
+        Ok(())
+    }
+
+    async fn read(&mut self) -> Result<WebsocketResponse> {
+        self.socket
             .read()
             .await
             .context("Failed to retrieve the command result from the GoXLR daemon process")?
-            .context("Failed to parse the command result from the GoXLR daemon process")?;
+            .context("Failed to parse the command result from the GoXLR daemon process")
+    }
+}
+
+fn push_event<T>(queue: &mut VecDeque<T>, event: T) {
+    if queue.len() == EVENT_BUFFER {
+        queue.pop_front();
+    }
+    queue.push_back(event);
+}
+
+#[async_trait]
+impl Client for IPCClient {
+    async fn send(&mut self, request: DaemonRequest) -> Result<()> {
+        println!("Sending: {:?}", serde_json::to_string(&request));
+        let result = self.request(request).await?;
+        self.resync_status().await?;
 
         match result {
             DaemonResponse::Status(status) => {
@@ -60,6 +199,8 @@ impl Client for IPCClient {
             DaemonResponse::Ok => Ok(()),
             DaemonResponse::Err(error) => bail!("{}", error),
             DaemonResponse::Patch(_) => bail!("Unexpected PATCH"),
+            DaemonResponse::MicLevel(_) => bail!("Unexpected MicLevel"),
+            DaemonResponse::Interaction(_) => bail!("Unexpected Interaction"),
             DaemonResponse::DeviceCommand(response) => match response {
                 GoXLRCommandResponse::Ok => Ok(()),
                 GoXLRCommandResponse::MicLevel(_) => bail!("Unexpected MicLevel"),
@@ -81,6 +222,10 @@ impl Client for IPCClient {
         self.send(command).await
     }
 
+    async fn daemon(&mut self, command: DaemonCommand) -> Result<()> {
+        self.send(DaemonRequest::Daemon(command)).await
+    }
+
     fn status(&self) -> &DaemonStatus {
         &self.status
     }
@@ -2,7 +2,8 @@ use crate::client::Client;
 use anyhow::Result;
 
 use crate::commands::{
-    DaemonRequest, DaemonResponse, DaemonStatus, DeviceCommand, GoXLRCommand, GoXLRCommandResponse,
+    DaemonCommand, DaemonRequest, DaemonResponse, DaemonStatus, DeviceCommand, GoXLRCommand,
+    GoXLRCommandResponse,
 };
 use anyhow::bail;
 use async_trait::async_trait;
@@ -46,6 +47,8 @@ impl Client for WebClient {
             DaemonResponse::Ok => Ok(()),
             DaemonResponse::Err(error) => bail!("{}", error),
             DaemonResponse::Patch(_) => bail!("Received PATCH!"),
+            DaemonResponse::MicLevel(_) => bail!("Received MicLevel!"),
+            DaemonResponse::Interaction(_) => bail!("Received Interaction!"),
             DaemonResponse::DeviceCommand(response) => match response {
                 GoXLRCommandResponse::Ok => Ok(()),
                 GoXLRCommandResponse::MicLevel(_) => bail!("Unexpected MicLevel"),
@@ -66,6 +69,10 @@ impl Client for WebClient {
         self.send(command).await
     }
 
+    async fn daemon(&mut self, command: DaemonCommand) -> anyhow::Result<()> {
+        self.send(DaemonRequest::Daemon(command)).await
+    }
+
     fn status(&self) -> &DaemonStatus {
         &self.status
     }
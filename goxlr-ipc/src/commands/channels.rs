@@ -1,14 +1,28 @@
+use goxlr_profile::MuteAction;
 use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::channels::mute::MuteActionChannels;
+use goxlr_shared::channels::output::OutputChannels;
 use goxlr_shared::channels::sub_mix::SubMixChannels;
 use goxlr_shared::channels::volume::VolumeChannels;
 use goxlr_shared::mute::MuteState;
+use goxlr_shared::submix::Mix;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ChannelCommands {
     Volume(ChannelVolume),
     Mute(MuteCommand),
+    MuteTargets(MuteTargets),
     SubMix(SubMix),
+
+    /// Assigns an Output to a Sub Mix
+    OutputMix(OutputMix),
+
+    /// Sets the Sub Mix sent to the Headphones, None follows the Headphones' own assignment
+    MonitorMix(Option<Mix>),
+
+    /// Enables or Disables Sub Mixing, when disabled all outputs are sent through Mix A
+    SubMixEnabled(bool),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,12 +37,27 @@ pub struct MuteCommand {
     pub state: MuteState,
 }
 
+/// Sets the outputs a channel is muted to when its Mute button is pressed or held, an empty
+/// list of targets will mute the channel to everything.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MuteTargets {
+    pub channel: MuteActionChannels,
+    pub action: MuteAction,
+    pub targets: Vec<OutputChannels>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubMix {
     pub channel: SubMixChannels,
     pub command: SubMixCommands,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputMix {
+    pub output: OutputChannels,
+    pub mix: Mix,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SubMixCommands {
     Volume(u8),
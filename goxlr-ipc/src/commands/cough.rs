@@ -0,0 +1,21 @@
+use goxlr_profile::{CoughBehaviour, MuteAction};
+use goxlr_shared::channels::mute::MuteActionChannels;
+use goxlr_shared::channels::output::OutputChannels;
+use goxlr_shared::mute::MuteState;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum CoughCommand {
+    /// Whether the Cough button latches on press, or only mutes while it's held down
+    Behaviour(CoughBehaviour),
+
+    /// The channel muted by the Cough button
+    Channel(MuteActionChannels),
+
+    /// Sets the outputs muted by a press or hold of the Cough button, an empty list of targets
+    /// will mute the assigned channel to everything.
+    MuteTargets(MuteAction, Vec<OutputChannels>),
+
+    /// Triggers the Cough button, 'Held' is only available when using the Press behaviour
+    State(MuteState),
+}
@@ -0,0 +1,10 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum FirmwareCommand {
+    /// Flashes the firmware image at this path (on the daemon's machine) to the device, progress
+    /// can be followed in the DaemonStatus
+    Update(PathBuf),
+}
@@ -0,0 +1,34 @@
+use goxlr_profile::{Animation, ButtonColourSet, EncoderColourSet, FaderColourSet, LightingTheme};
+use goxlr_shared::buttons::LightingButtons;
+use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::colours::Colour;
+use goxlr_shared::encoders::Encoders;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum LightingCommand {
+    /// The Colours of a channel's fader, displayed while it's assigned
+    FaderColours(FaderChannels, FaderColourSet),
+
+    /// The Colours and inactive behaviour of a channel's mute button
+    MuteColours(FaderChannels, ButtonColourSet),
+
+    /// The Background Colour of a channel's scribble screen
+    ScribbleColour(FaderChannels, Colour),
+
+    SwearColours(ButtonColourSet),
+    CoughColours(ButtonColourSet),
+    ButtonColours(LightingButtons, ButtonColourSet),
+    EncoderColours(Encoders, EncoderColourSet),
+
+    /// Stores a theme in the profile, replacing any existing theme with the same name
+    SaveTheme(String, LightingTheme),
+    RemoveTheme(String),
+    ApplyTheme(String),
+
+    /// Copies a channel's fader, mute button and scribble colours to all other channels
+    CopyChannelColours(FaderChannels),
+
+    /// Sets the lighting animation, this will fail if the device's firmware doesn't support them
+    Animation(Animation),
+}
@@ -3,16 +3,28 @@ use std::collections::BTreeMap;
 use json_patch::Patch;
 use serde::{Deserialize, Serialize};
 
+use goxlr_shared::firmware::FirmwareUpdateStatus;
+
 use crate::commands::channels::ChannelCommands;
 use crate::commands::configuration::ConfigurationCommand;
+use crate::commands::cough::CoughCommand;
+use crate::commands::firmware::FirmwareCommand;
+use crate::commands::lighting::LightingCommand;
 use crate::commands::mic::MicrophoneCommand;
 use crate::commands::pages::PageCommand;
-use crate::status::DeviceStatus;
+use crate::commands::routing::RoutingCommand;
+use crate::commands::scribbles::ScribbleCommand;
+use crate::status::{DeviceStatus, InteractionEvent, MicLevelMeter};
 
 pub mod channels;
 pub mod configuration;
+pub mod cough;
+pub mod firmware;
+pub mod lighting;
 pub mod mic;
 pub mod pages;
+pub mod routing;
+pub mod scribbles;
 
 /// This is the base IPC request structure, it's async driven so each request will require a
 /// response 'oneshot' channel for receiving a reply, this allows us to better manage a request /
@@ -27,8 +39,31 @@ pub enum DaemonRequest {
 
     Daemon(DaemonCommand),
     DeviceCommand(DeviceCommand),
+
+    /// Starts pushing events to this connection, only available on the Websocket and IPC Socket
+    Subscribe(Subscription),
+
+    /// Stops pushing events to this connection
+    Unsubscribe(Subscription),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Subscription {
+    /// Patches to the DaemonStatus, the subscribe response is the Status the patches apply to.
+    /// Websocket clients are subscribed to these when they connect. If a client falls behind
+    /// the patches it missed are dropped, and a new Status is pushed for the following patches.
+    Patches,
+
+    /// Microphone level readings from all devices, these are only sampled while subscribed
+    MicLevel,
+
+    /// Button presses, fader moves and encoder turns from all devices, as well as devices being
+    /// attached or removed
+    Interactions,
 }
 
+/// A request tagged with an id, which will be included in its response. These are used by the
+/// Websocket, and optionally by the IPC Socket. Events pushed to subscribers have an id of u64::MAX.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebsocketRequest {
     pub id: u64,
@@ -42,6 +77,8 @@ pub enum DaemonResponse {
     Patch(Patch),
     Status(DaemonStatus),
     DeviceCommand(GoXLRCommandResponse),
+    MicLevel(MicLevelMeter),
+    Interaction(InteractionEvent),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,8 +87,57 @@ pub struct WebsocketResponse {
     pub data: DaemonResponse,
 }
 
+/// Messages on the IPC Socket can either be plain, or tagged with a request id. Responses are sent
+/// in the same form as the request, and events in the same form as the connection's first
+/// Subscribe. Later Subscribe requests in the other form are rejected.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum DaemonCommand {}
+#[serde(untagged)]
+pub enum IPCRequest {
+    Tagged(WebsocketRequest),
+    Plain(DaemonRequest),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum IPCResponse {
+    Tagged(WebsocketResponse),
+    Plain(DaemonResponse),
+}
+
+/// Commands which affect the daemon as a whole, rather than a specific device
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DaemonCommand {
+    /// Sets the Log Level, this is persisted to the settings file. As with the other settings,
+    /// this fails if the value is overridden on the command line.
+    SetLogLevel(LogLevel),
+
+    /// Updates the HTTP Settings, changes other than CORS take effect on restart
+    SetHttpSettings(HttpSettings),
+
+    /// Sets how often (in milliseconds) the Microphone level is sampled for subscribers
+    SetMicMeterInterval(u64),
+
+    /// Creates a new profile from the defaults
+    CreateProfile(ProfileType, String),
+
+    /// Deletes a profile, this will fail if the profile is in use by a device
+    DeleteProfile(ProfileType, String),
+
+    /// Renames a profile (from, to), this will fail if the profile is in use by a device
+    RenameProfile(ProfileType, String, String),
+
+    /// Re-reads the profile directory, picking up any profiles added or removed externally
+    RescanProfiles,
+
+    /// Cleanly stops all devices and shuts down the daemon
+    Shutdown,
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum ProfileType {
+    Profile,
+    MicProfile,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeviceCommand {
@@ -64,7 +150,12 @@ pub enum GoXLRCommand {
     Configuration(ConfigurationCommand),
     Microphone(MicrophoneCommand),
     Channels(ChannelCommands),
+    Cough(CoughCommand),
     Pages(PageCommand),
+    Routing(RoutingCommand),
+    Lighting(LightingCommand),
+    Scribbles(ScribbleCommand),
+    Firmware(FirmwareCommand),
 }
 
 /// The GoXLR Command Response will contain command specific responses, generally not much more
@@ -79,12 +170,34 @@ pub enum GoXLRCommandResponse {
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct DaemonStatus {
     pub devices: BTreeMap<String, DeviceStatus>,
+    pub profiles: ProfileList,
+
+    /// The state of the most recent firmware update for each device, by serial. These persist
+    /// while the device reboots, so clients can follow an update through to the end.
+    pub firmware_updates: BTreeMap<String, FirmwareUpdateStatus>,
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// The names of all profiles currently stored by the daemon
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileList {
+    pub profiles: Vec<String>,
+    pub mic_profiles: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct HttpSettings {
     pub enabled: bool,
     pub bind_address: String,
     pub cors_enabled: bool,
     pub port: u16,
 }
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
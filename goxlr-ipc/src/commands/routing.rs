@@ -0,0 +1,33 @@
+use goxlr_shared::channels::input::InputChannels;
+use goxlr_shared::channels::output::OutputChannels;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RoutingCommand {
+    SetRoute(SetRoute),
+    ToggleRoute(InputChannels, OutputChannels),
+
+    /// Routes an input to exactly these outputs, all others are disabled
+    SetInput(InputChannels, Vec<OutputChannels>),
+
+    /// Routes exactly these inputs to an output, all others are disabled
+    SetOutput(OutputChannels, Vec<InputChannels>),
+
+    /// Sets the level a route is sent at, from 0 to 32 (full volume). This is a linear gain,
+    /// so 16 is roughly -6 dB (see `goxlr_profile::Route::level_from_decibels`)
+    SetLevel(SetRouteLevel),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetRoute {
+    pub input: InputChannels,
+    pub output: OutputChannels,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetRouteLevel {
+    pub input: InputChannels,
+    pub output: OutputChannels,
+    pub level: u8,
+}
@@ -0,0 +1,27 @@
+use std::path::PathBuf;
+
+use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::scribbles::ScribbleContent;
+use serde::{Deserialize, Serialize};
+
+/// Changes to a channel's scribble screen, these are re-rendered immediately if the channel is
+/// currently assigned to a fader. The screen's colour is handled by LightingCommand.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ScribbleCommand {
+    /// The main text, displayed in the centre (or under the icon, if one is set)
+    Text(FaderChannels, Option<String>),
+
+    /// A single character displayed in the top left corner
+    Label(FaderChannels, Option<char>),
+
+    /// The icon to display, relative paths are loaded from the 'icons' directory
+    Image(FaderChannels, Option<PathBuf>),
+
+    Inverted(FaderChannels, bool),
+
+    /// Where the text comes from, dynamic content is updated as the channel changes
+    Content(FaderChannels, ScribbleContent),
+
+    /// Runtime text for channels using ScribbleContent::Custom, this isn't saved to the profile
+    PushText(FaderChannels, Option<String>),
+}
@@ -0,0 +1,26 @@
+use enum_map::EnumMap;
+use goxlr_shared::channels::output::OutputChannels;
+use goxlr_shared::submix::Mix;
+use serde::{Deserialize, Serialize};
+
+/// Runtime state of the device, as opposed to what's configured in the profile
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RuntimeState {
+    pub sub_mix: SubMixState,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SubMixState {
+    /// Whether Sub Mixes are available on this device
+    pub supported: bool,
+
+    /// The Mix each Output is assigned to in the profile
+    pub configured: EnumMap<OutputChannels, Mix>,
+
+    /// The Mix each Output is actually receiving, all outputs use Mix A when disabled. The
+    /// Headphones receive the monitor mix when it's overridden.
+    pub effective: EnumMap<OutputChannels, Mix>,
+
+    /// The Mix currently being sent to the Headphones
+    pub monitor_mix: Mix,
+}
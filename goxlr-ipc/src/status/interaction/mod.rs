@@ -0,0 +1,39 @@
+use goxlr_shared::buttons::Buttons;
+use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::encoders::Encoders;
+use goxlr_shared::faders::Fader;
+use serde::{Deserialize, Serialize};
+
+/// Something which physically happened on a device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InteractionEvent {
+    pub serial: String,
+    pub interaction: Interaction,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Interaction {
+    ButtonDown(Buttons),
+    ButtonUp(Buttons),
+
+    /// Sent once a button has been held for the configured hold time, unless the press has
+    /// already been used for something else (such as changing the page)
+    ButtonHeld(Buttons),
+
+    /// A fader was moved, along with the channel assigned to it on the current page
+    FaderMoved {
+        fader: Fader,
+        channel: FaderChannels,
+        value: u8,
+    },
+    EncoderChanged {
+        encoder: Encoders,
+        value: i8,
+    },
+
+    /// The current fader page changed, either from the buttons or a command
+    PageChanged(usize),
+
+    DeviceAttached,
+    DeviceRemoved,
+}
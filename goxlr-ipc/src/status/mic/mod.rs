@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// A reading from a device's Microphone level meter, all levels are in dBFS
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MicLevelMeter {
+    pub serial: String,
+
+    /// The most recent level sampled from the device
+    pub level: f64,
+
+    /// The highest recent level, this is held for a short time before falling back
+    pub peak: f64,
+
+    /// The RMS level, smoothed over recent samples
+    pub rms: f64,
+}
@@ -1,6 +1,11 @@
 mod device;
+mod interaction;
 mod mic;
 
+pub use device::{RuntimeState, SubMixState};
+pub use interaction::{Interaction, InteractionEvent};
+pub use mic::MicLevelMeter;
+
 use goxlr_profile::{MicProfile, Profile};
 use goxlr_shared::device::DeviceInfo;
 use serde::{Deserialize, Serialize};
@@ -10,6 +15,7 @@ pub struct DeviceStatus {
     pub hardware: DeviceInfo,
     pub serial: String,
     pub config: Configuration,
+    pub state: RuntimeState,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
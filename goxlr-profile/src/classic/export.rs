@@ -0,0 +1,319 @@
+use std::io::{Read, Seek};
+
+use anyhow::Result;
+use strum::IntoEnumIterator;
+
+use goxlr_profile_loader::components::colours::{ColourDisplay, ColourMap, ColourOffStyle};
+use goxlr_profile_loader::components::mute::MuteFunction;
+use goxlr_profile_loader::components::simple::SimpleElements;
+use goxlr_profile_loader::mic_profile::MicProfileSettings;
+use goxlr_profile_loader::profile::Profile as ClassicProfile;
+use goxlr_shared::buttons::InactiveButtonBehaviour;
+use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::channels::input::InputChannels;
+use goxlr_shared::channels::mute::MuteActionChannels;
+use goxlr_shared::channels::output::OutputChannels;
+use goxlr_shared::channels::sub_mix::SubMixChannels;
+use goxlr_shared::channels::volume::VolumeChannels;
+use goxlr_shared::channels::CanFrom;
+use goxlr_shared::colours::FaderDisplayMode;
+use goxlr_shared::eq_frequencies::{Frequencies as F, MiniFrequencies as M};
+use goxlr_shared::faders::Fader;
+use goxlr_shared::microphone::MicrophoneType;
+
+use crate::classic::ConversionWarning::{
+    ChannelNotExported, CoughChannelAssignment, ExtraPagesDropped, SubMixLinkRatio,
+    UnsupportedMuteTargets, UnusedCoughTargets,
+};
+use crate::classic::{
+    colour_to_classic, fader_channel_to_classic, fader_to_classic, input_to_classic,
+    mute_function_from_targets, output_to_classic, volume_to_classic, Conversion,
+    ConversionWarning, LIGHTING_ELEMENTS,
+};
+use crate::{ButtonColourSet, CoughBehaviour, MicProfile, MuteAction, Profile, ROUTE_LEVEL_MAX};
+
+/// In the classic format a fully enabled route has a value of 8192, each of our levels is worth
+/// this much
+const ROUTE_STEP: u16 = 8192 / ROUTE_LEVEL_MAX as u16;
+
+/// Exports a Profile into the classic .goxlr format.
+///
+/// The classic format contains a lot of settings we don't track (samples, effects, etc), so
+/// rather than building one from scratch, our settings are applied on top of an existing
+/// classic profile (such as the default) which is then returned ready to be saved.
+pub fn export_profile<R: Read + Seek>(
+    profile: &Profile,
+    template: R,
+) -> Result<Conversion<ClassicProfile>> {
+    let mut classic = ClassicProfile::load(template)?;
+    let settings = classic.settings_mut();
+    let mut warnings = vec![];
+
+    // The classic profile only has a single set of faders, so everything collapses onto the
+    // first page.
+    let page = profile.pages.page_list.first().cloned().unwrap_or_default();
+    if profile.pages.page_list.len() > 1 {
+        warnings.push(ExtraPagesDropped {
+            count: profile.pages.page_list.len() - 1,
+        });
+    }
+
+    for fader in Fader::iter() {
+        let channel = page.faders[fader];
+        let classic_fader = fader_to_classic(fader);
+        let display = &profile.channels.configs[channel].display;
+        let name = format!("Fader {:?}", fader);
+
+        let classic_channel = settings.fader_mut(classic_fader);
+        classic_channel.set_channel(fader_channel_to_classic(channel));
+
+        let map = classic_channel.colour_map_mut();
+        map.set_colour_display(Some(display_mode_to_classic(&display.fader_display_mode)));
+
+        let colours = &display.fader_colours;
+        map.set_colour(0, colour_to_classic(&colours.top_colour)?)?;
+        map.set_colour(1, colour_to_classic(&colours.bottom_colour)?)?;
+
+        let mute = settings.mute_button_mut(classic_fader);
+        save_button_colours(mute.colour_map_mut(), &display.mute_colours)?;
+
+        if MuteActionChannels::can_from(channel) {
+            let actions = &profile.channels.mute_actions[channel.into()].actions;
+            let name = format!("{} Mute", name);
+            let function = mute_function(&actions[MuteAction::Press], &name, &mut warnings);
+            mute.set_mute_function(function);
+
+            // Holding the mute button in the classic app always mutes to all..
+            if !actions[MuteAction::Hold].is_empty() {
+                warnings.push(UnsupportedMuteTargets {
+                    setting: format!("{} (Hold)", name),
+                    targets: actions[MuteAction::Hold].clone(),
+                });
+            }
+        }
+
+        let screen = &display.screen_display;
+        let scribble = settings.scribble_mut(classic_fader);
+        scribble.set_style_invert(screen.inverted);
+        scribble.set_text_bottom_middle(screen.text.clone());
+        scribble.set_text_top_left(screen.label.map(String::from));
+        scribble.set_icon_file(
+            screen
+                .image
+                .as_ref()
+                .map(|path| path.to_string_lossy().to_string()),
+        );
+        scribble
+            .colour_map_mut()
+            .set_colour(0, colour_to_classic(&screen.colour)?)?;
+    }
+
+    // Anything that's not on the first page has nowhere to store its display settings.
+    for channel in FaderChannels::iter() {
+        if !page.faders.values().any(|assigned| *assigned == channel) {
+            warnings.push(ChannelNotExported { channel });
+        }
+    }
+
+    // Volumes..
+    let mixer = settings.mixer_mut();
+    for channel in VolumeChannels::iter() {
+        let volume = profile.channels.volumes[channel];
+        mixer.set_channel_volume(volume_to_classic(channel), volume)?;
+    }
+
+    // Routing..
+    let table = mixer.mixer_table_mut();
+    for input in InputChannels::iter() {
+        for output in OutputChannels::iter() {
+            let route = profile.routing[input][output];
+            let value = match route.enabled {
+                true => route.level as u16 * ROUTE_STEP,
+                false => 0,
+            };
+            table[input_to_classic(input)][output_to_classic(output)] = value;
+        }
+    }
+
+    // The classic format has no concept of Sub Mixes, the volumes are simply dropped, but a
+    // non-default link ratio is worth letting the user know about.
+    for channel in SubMixChannels::iter() {
+        if let Some(ratio) = profile.channels.sub_mix[channel].linked {
+            if ratio != 1. {
+                warnings.push(SubMixLinkRatio { channel, ratio });
+            }
+        }
+    }
+
+    // The Cough Button..
+    let cough = &profile.cough;
+    if cough.channel_assignment != MuteActionChannels::Microphone {
+        warnings.push(CoughChannelAssignment {
+            channel: cough.channel_assignment,
+        });
+    }
+
+    let mute_chat = settings.mute_chat_mut();
+    mute_chat.set_cough_toggle(cough.cough_behaviour == CoughBehaviour::Press);
+
+    // The classic cough button only has a single target, so use the one for the behaviour. The
+    // other action's targets are lost, unless they're the same or were never changed.
+    let (action, unused) = match cough.cough_behaviour {
+        CoughBehaviour::Press => (MuteAction::Press, MuteAction::Hold),
+        CoughBehaviour::Hold => (MuteAction::Hold, MuteAction::Press),
+    };
+    let targets = &cough.mute_actions[unused];
+    let default = &Profile::default().cough.mute_actions[unused];
+    if targets != &cough.mute_actions[action] && targets != default {
+        warnings.push(UnusedCoughTargets {
+            action: unused,
+            targets: targets.clone(),
+        });
+    }
+    let function = mute_function(&cough.mute_actions[action], "Cough", &mut warnings);
+    mute_chat.set_cough_mute_source(function);
+    save_button_colours(mute_chat.colour_map_mut(), &cough.colours)?;
+
+    // The Swear Button..
+    let swear = settings.simple_element_mut(SimpleElements::Swear);
+    save_button_colours(swear.colour_map_mut(), &profile.swear.colours)?;
+
+    // The Sampler Bank and FX buttons..
+    for (button, element) in LIGHTING_ELEMENTS {
+        let map = settings.simple_element_mut(element).colour_map_mut();
+        save_button_colours(map, &profile.lighting.buttons[button])?;
+    }
+
+    Ok(Conversion {
+        profile: classic,
+        warnings,
+    })
+}
+
+/// Exports a MicProfile into the classic .goxlrMicProfile format, as with the profile this is
+/// applied on top of an existing mic profile.
+pub fn export_mic_profile<R: Read>(
+    profile: &MicProfile,
+    template: R,
+) -> Result<Conversion<MicProfileSettings>> {
+    let mut classic = MicProfileSettings::load(template)?;
+
+    // Microphone Setup, the classic gains are ordered Dynamic (XLR), Condenser, Jack
+    let mic = &profile.microphone;
+    classic.set_mic_type(match mic.mic_type {
+        MicrophoneType::XLR => 0,
+        MicrophoneType::Phantom => 1,
+        MicrophoneType::Jack => 2,
+    });
+    classic.set_mic_gains([
+        mic.mic_gains[MicrophoneType::XLR] as u16,
+        mic.mic_gains[MicrophoneType::Phantom] as u16,
+        mic.mic_gains[MicrophoneType::Jack] as u16,
+    ]);
+
+    // Equaliser..
+    let value = &profile.equalizer;
+    let eq = classic.equalizer_mut();
+    eq.set_eq_31h_gain(value[F::Eq31h].gain);
+    eq.set_eq_31h_freq(value[F::Eq31h].frequency);
+    eq.set_eq_63h_gain(value[F::Eq63h].gain);
+    eq.set_eq_63h_freq(value[F::Eq63h].frequency);
+    eq.set_eq_125h_gain(value[F::Eq125h].gain);
+    eq.set_eq_125h_freq(value[F::Eq125h].frequency);
+    eq.set_eq_250h_gain(value[F::Eq250h].gain);
+    eq.set_eq_250h_freq(value[F::Eq250h].frequency);
+    eq.set_eq_500h_gain(value[F::Eq500h].gain);
+    eq.set_eq_500h_freq(value[F::Eq500h].frequency);
+    eq.set_eq_1k_gain(value[F::Eq1kh].gain);
+    eq.set_eq_1k_freq(value[F::Eq1kh].frequency);
+    eq.set_eq_2k_gain(value[F::Eq2kh].gain);
+    eq.set_eq_2k_freq(value[F::Eq2kh].frequency);
+    eq.set_eq_4k_gain(value[F::Eq4kh].gain);
+    eq.set_eq_4k_freq(value[F::Eq4kh].frequency);
+    eq.set_eq_8k_gain(value[F::Eq8kh].gain);
+    eq.set_eq_8k_freq(value[F::Eq8kh].frequency);
+    eq.set_eq_16k_gain(value[F::Eq16kh].gain);
+    eq.set_eq_16k_freq(value[F::Eq16kh].frequency);
+
+    let value = &profile.equalizer_mini;
+    let eq = classic.equalizer_mini_mut();
+    eq.set_eq_90h_gain(value[M::Eq90h].gain);
+    eq.set_eq_90h_freq(value[M::Eq90h].frequency);
+    eq.set_eq_250h_gain(value[M::Eq250h].gain);
+    eq.set_eq_250h_freq(value[M::Eq250h].frequency);
+    eq.set_eq_500h_gain(value[M::Eq500h].gain);
+    eq.set_eq_500h_freq(value[M::Eq500h].frequency);
+    eq.set_eq_1k_gain(value[M::Eq1kh].gain);
+    eq.set_eq_1k_freq(value[M::Eq1kh].frequency);
+    eq.set_eq_3k_gain(value[M::Eq3kh].gain);
+    eq.set_eq_3k_freq(value[M::Eq3kh].frequency);
+    eq.set_eq_8k_gain(value[M::Eq8kh].gain);
+    eq.set_eq_8k_freq(value[M::Eq8kh].frequency);
+
+    // Compressor, the classic format stores the times and ratio as an index..
+    let value = &profile.compressor;
+    let compressor = classic.compressor_mut();
+    compressor.set_threshold(value.threshold);
+    compressor.set_ratio(value.ratio as u8);
+    compressor.set_attack(value.attack as u8);
+    compressor.set_release(value.release as u8);
+    compressor.set_makeup(value.makeup_gain);
+
+    // Gate..
+    let value = &profile.gate;
+    let gate = classic.gate_mut();
+    gate.set_enabled(value.enabled);
+    gate.set_threshold(value.threshold);
+    gate.set_attack(value.attack as u8);
+    gate.set_release(value.release as u8);
+    gate.set_attenuation(value.attenuation);
+
+    classic.set_deess(profile.deess);
+    classic.set_bleep_level(profile.bleep_volume);
+
+    Ok(Conversion {
+        profile: classic,
+        warnings: vec![],
+    })
+}
+
+fn display_mode_to_classic(modes: &[FaderDisplayMode]) -> ColourDisplay {
+    let gradient = modes.contains(&FaderDisplayMode::Gradient);
+    let meter = modes.contains(&FaderDisplayMode::Meter);
+
+    match (gradient, meter) {
+        (true, true) => ColourDisplay::GradientMeter,
+        (true, false) => ColourDisplay::Gradient,
+        (false, true) => ColourDisplay::Meter,
+        (false, false) => ColourDisplay::TwoColour,
+    }
+}
+
+fn behaviour_to_classic(behaviour: InactiveButtonBehaviour) -> ColourOffStyle {
+    match behaviour {
+        InactiveButtonBehaviour::DimActive => ColourOffStyle::Dimmed,
+        InactiveButtonBehaviour::InactiveColour => ColourOffStyle::Colour2,
+        InactiveButtonBehaviour::DimInactive => ColourOffStyle::DimmedColour2,
+    }
+}
+
+fn save_button_colours(map: &mut ColourMap, colours: &ButtonColourSet) -> Result<()> {
+    map.set_colour(0, colour_to_classic(&colours.active_colour)?)?;
+    map.set_colour(1, colour_to_classic(&colours.inactive_colour)?)?;
+    map.set_off_style(behaviour_to_classic(colours.inactive_behaviour));
+    Ok(())
+}
+
+fn mute_function(
+    targets: &[OutputChannels],
+    setting: &str,
+    warnings: &mut Vec<ConversionWarning>,
+) -> MuteFunction {
+    mute_function_from_targets(targets).unwrap_or_else(|| {
+        warnings.push(UnsupportedMuteTargets {
+            setting: setting.to_string(),
+            targets: targets.to_vec(),
+        });
+        MuteFunction::All
+    })
+}
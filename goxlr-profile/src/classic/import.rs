@@ -0,0 +1,433 @@
+use std::collections::BTreeSet;
+use std::io::{Read, Seek};
+use std::path::PathBuf;
+
+use anyhow::Result;
+use enum_map::EnumMap;
+use strum::IntoEnumIterator;
+use xml::reader::{EventReader, XmlEvent};
+use zip::ZipArchive;
+
+use goxlr_profile_loader::components::colours::{ColourDisplay, ColourMap, ColourOffStyle};
+use goxlr_profile_loader::components::simple::SimpleElements;
+use goxlr_profile_loader::mic_profile::MicProfileSettings;
+use goxlr_profile_loader::profile::Profile as ClassicProfile;
+use goxlr_shared::buttons::InactiveButtonBehaviour;
+use goxlr_shared::channels::input::InputChannels;
+use goxlr_shared::channels::mute::MuteActionChannels;
+use goxlr_shared::channels::output::OutputChannels;
+use goxlr_shared::channels::volume::VolumeChannels;
+use goxlr_shared::channels::CanFrom;
+use goxlr_shared::colours::{Colour, FaderDisplayMode};
+use goxlr_shared::eq_frequencies::{Frequencies, MiniFrequencies};
+use goxlr_shared::faders::Fader;
+use goxlr_shared::microphone::MicrophoneType;
+
+use crate::classic::ConversionWarning::{
+    InvalidColour, InvalidValue, LabelTruncated, PartialRoute, UnassignableFaderChannel,
+    UnsupportedFeature,
+};
+use crate::classic::{
+    colour_from_classic, fader_channel_from_classic, fader_to_classic, input_to_classic,
+    output_to_classic, targets_from_mute_function, volume_to_classic, Conversion,
+    ConversionWarning, LIGHTING_ELEMENTS,
+};
+use crate::{
+    ButtonColourSet, CoughBehaviour, FaderPage, FaderPages, MicProfile, MuteAction, Profile, Route,
+    ROUTE_LEVEL_MAX,
+};
+
+/// In the classic format, a route which is fully enabled has this value
+const ROUTE_ON: u16 = 8192;
+
+/// The classic format has a finer range than ours, each of our levels covers this many values
+const ROUTE_STEP: u16 = ROUTE_ON / ROUTE_LEVEL_MAX as u16;
+
+/// Features which exist in classic profiles, but have no representation in ours (yet!), along
+/// with the elements which store them.
+const UNSUPPORTED: [(&str, &[&str]); 5] = [
+    (
+        "Sampler",
+        &[
+            "sampleTopLeft",
+            "sampleTopRight",
+            "sampleBottomLeft",
+            "sampleBottomRight",
+            "sampleClear",
+        ],
+    ),
+    (
+        "Effects (Megaphone / Robot / Hardtune)",
+        &["megaphoneEffect", "robotEffect", "hardtuneEffect"],
+    ),
+    (
+        "Effect Presets and Encoders",
+        &[
+            "effects1",
+            "effects2",
+            "effects3",
+            "effects4",
+            "effects5",
+            "effects6",
+            "reverbEncoder",
+            "echoEncoder",
+            "pitchEncoder",
+            "genderEncoder",
+        ],
+    ),
+    ("Global and Logo Colours", &["globalColour", "logoX"]),
+    ("Animations", &["animationTree"]),
+];
+
+/// Loads a classic .goxlr profile, and converts it into a Profile
+pub fn import_profile<R: Read + Seek>(mut reader: R) -> Result<Conversion<Profile>> {
+    let elements = profile_elements(&mut reader)?;
+    reader.rewind()?;
+
+    let classic = ClassicProfile::load(reader)?;
+    let settings = classic.settings();
+
+    let mut profile = Profile::default();
+    let mut warnings = vec![];
+
+    // Fader Assignments, the classic profile only has a single page..
+    let mut page = FaderPage::default();
+    for fader in Fader::iter() {
+        let channel = settings.fader(fader_to_classic(fader)).channel();
+        match fader_channel_from_classic(channel) {
+            Some(channel) => page.faders[fader] = channel,
+            None => warnings.push(UnassignableFaderChannel {
+                fader,
+                channel: format!("{:?}", channel),
+            }),
+        }
+    }
+    profile.pages = FaderPages {
+        current: 0,
+        page_list: vec![page.clone()],
+    };
+
+    // Volumes..
+    let mixer = settings.mixer();
+    for channel in VolumeChannels::iter() {
+        profile.channels.volumes[channel] = mixer.channel_volume(volume_to_classic(channel));
+    }
+
+    // Routing..
+    let table = mixer.mixer_table();
+    for input in InputChannels::iter() {
+        for output in OutputChannels::iter() {
+            let value = table[input_to_classic(input)][output_to_classic(output)];
+            if value % ROUTE_STEP != 0 || value > ROUTE_ON {
+                warnings.push(PartialRoute {
+                    input,
+                    output,
+                    value,
+                });
+            }
+
+            // Round to the nearest step, but never round a route which is on down to silence
+            let level = (value.min(ROUTE_ON) + ROUTE_STEP / 2) / ROUTE_STEP;
+            let level = if value > 0 { level.max(1) } else { level };
+            profile.routing[input][output] = Route {
+                enabled: value > 0,
+                level: level as u8,
+            };
+        }
+    }
+
+    // The Faders, Mute Buttons and Scribbles are all attached to the fader in the classic
+    // profile, whereas ours are attached to the channel, so apply them to the assigned channel.
+    for fader in Fader::iter() {
+        let channel = page.faders[fader];
+        let classic_fader = fader_to_classic(fader);
+        let name = format!("Fader {:?}", fader);
+
+        let fader_map = settings.fader(classic_fader).colour_map();
+        let mute = settings.mute_button(classic_fader);
+        let scribble = settings.scribble(classic_fader);
+
+        let display = &mut profile.channels.configs[channel].display;
+        if let Some(style) = fader_map.colour_display() {
+            display.fader_display_mode = display_mode_from_classic(*style);
+        }
+
+        let colours = &mut display.fader_colours;
+        load_colour(fader_map, 0, &mut colours.top_colour, &name, &mut warnings);
+        load_colour(
+            fader_map,
+            1,
+            &mut colours.bottom_colour,
+            &name,
+            &mut warnings,
+        );
+
+        let name = format!("Fader {:?} Mute", fader);
+        let mute_map = mute.colour_map();
+        load_button_colours(mute_map, &mut display.mute_colours, &name, &mut warnings);
+
+        let name = format!("Fader {:?} Scribble", fader);
+        let screen = &mut display.screen_display;
+        load_colour(
+            scribble.colour_map(),
+            0,
+            &mut screen.colour,
+            &name,
+            &mut warnings,
+        );
+        screen.inverted = scribble.is_style_invert();
+        screen.image = scribble.icon_file().map(PathBuf::from);
+        screen.text = scribble.text_bottom_middle();
+        screen.label = None;
+        if let Some(label) = scribble.text_top_left() {
+            if label.chars().count() > 1 {
+                warnings.push(LabelTruncated {
+                    fader,
+                    label: label.clone(),
+                });
+            }
+            screen.label = label.chars().next();
+        }
+
+        // The classic app mutes to the selected target on press, and to all on hold.
+        if MuteActionChannels::can_from(channel) {
+            let actions = &mut profile.channels.mute_actions[channel.into()].actions;
+            actions[MuteAction::Press] = targets_from_mute_function(*mute.mute_function());
+            actions[MuteAction::Hold] = vec![];
+        }
+    }
+
+    // The Cough button, in the classic profile this is always assigned to the Microphone
+    let mute_chat = settings.mute_chat();
+    let cough = &mut profile.cough;
+    cough.channel_assignment = MuteActionChannels::Microphone;
+    cough.cough_behaviour = if mute_chat.is_cough_toggle() {
+        CoughBehaviour::Press
+    } else {
+        CoughBehaviour::Hold
+    };
+
+    let targets = targets_from_mute_function(*mute_chat.cough_mute_source());
+    cough.mute_actions[MuteAction::Press] = targets.clone();
+    cough.mute_actions[MuteAction::Hold] = targets;
+
+    let map = mute_chat.colour_map();
+    load_button_colours(map, &mut cough.colours, "Cough", &mut warnings);
+
+    // The Swear Button..
+    let map = settings.simple_element(SimpleElements::Swear).colour_map();
+    let colours = &mut profile.swear.colours;
+    load_button_colours(map, colours, "Swear", &mut warnings);
+
+    // The Sampler Bank and FX buttons..
+    for (button, element) in LIGHTING_ELEMENTS {
+        let map = settings.simple_element(element).colour_map();
+        let colours = &mut profile.lighting.buttons[button];
+        load_button_colours(map, colours, &format!("{:?}", button), &mut warnings);
+    }
+
+    for (feature, names) in UNSUPPORTED {
+        if names.iter().any(|name| elements.contains(*name)) {
+            warnings.push(UnsupportedFeature(feature.to_string()));
+        }
+    }
+
+    Ok(Conversion { profile, warnings })
+}
+
+/// Loads a classic .goxlrMicProfile, and converts it into a MicProfile
+pub fn import_mic_profile<R: Read>(reader: R) -> Result<Conversion<MicProfile>> {
+    let classic = MicProfileSettings::load(reader)?;
+
+    let mut profile = MicProfile::default();
+    let mut warnings = vec![];
+
+    // Microphone Setup, the classic gains are ordered Dynamic (XLR), Condenser, Jack
+    let mic_type = match classic.mic_type() {
+        0 => Some(MicrophoneType::XLR),
+        1 => Some(MicrophoneType::Phantom),
+        2 => Some(MicrophoneType::Jack),
+        value => {
+            warnings.push(InvalidValue {
+                setting: String::from("Microphone Type"),
+                value: value as i64,
+            });
+            None
+        }
+    };
+    if let Some(mic_type) = mic_type {
+        profile.microphone.mic_type = mic_type;
+    }
+
+    let gains = classic.mic_gains();
+    let mic_types = [
+        MicrophoneType::XLR,
+        MicrophoneType::Phantom,
+        MicrophoneType::Jack,
+    ];
+    for (index, mic_type) in mic_types.iter().enumerate() {
+        match u8::try_from(gains[index]) {
+            Ok(gain) => profile.microphone.mic_gains[*mic_type] = gain,
+            Err(_) => warnings.push(InvalidValue {
+                setting: format!("{:?} Gain", mic_type),
+                value: gains[index] as i64,
+            }),
+        }
+    }
+
+    // Equaliser..
+    let eq = classic.equalizer();
+    let values: EnumMap<Frequencies, (i8, f32)> = EnumMap::from_array([
+        (eq.eq_31h_gain(), eq.eq_31h_freq()),
+        (eq.eq_63h_gain(), eq.eq_63h_freq()),
+        (eq.eq_125h_gain(), eq.eq_125h_freq()),
+        (eq.eq_250h_gain(), eq.eq_250h_freq()),
+        (eq.eq_500h_gain(), eq.eq_500h_freq()),
+        (eq.eq_1k_gain(), eq.eq_1k_freq()),
+        (eq.eq_2k_gain(), eq.eq_2k_freq()),
+        (eq.eq_4k_gain(), eq.eq_4k_freq()),
+        (eq.eq_8k_gain(), eq.eq_8k_freq()),
+        (eq.eq_16k_gain(), eq.eq_16k_freq()),
+    ]);
+    for (frequency, (gain, freq)) in values {
+        profile.equalizer[frequency].gain = gain;
+        profile.equalizer[frequency].frequency = freq;
+    }
+
+    let eq = classic.equalizer_mini();
+    let values: EnumMap<MiniFrequencies, (i8, f32)> = EnumMap::from_array([
+        (eq.eq_90h_gain(), eq.eq_90h_freq()),
+        (eq.eq_250h_gain(), eq.eq_250h_freq()),
+        (eq.eq_500h_gain(), eq.eq_500h_freq()),
+        (eq.eq_1k_gain(), eq.eq_1k_freq()),
+        (eq.eq_3k_gain(), eq.eq_3k_freq()),
+        (eq.eq_8k_gain(), eq.eq_8k_freq()),
+    ]);
+    for (frequency, (gain, freq)) in values {
+        profile.equalizer_mini[frequency].gain = gain;
+        profile.equalizer_mini[frequency].frequency = freq;
+    }
+
+    // Compressor, the classic format stores the times and ratio as an index..
+    let compressor = classic.compressor();
+    let target = &mut profile.compressor;
+    target.threshold = compressor.threshold();
+    target.makeup_gain = compressor.makeup();
+
+    let setting = "Compressor Ratio";
+    load_index(
+        compressor.ratio(),
+        setting,
+        &mut target.ratio,
+        &mut warnings,
+    );
+
+    let setting = "Compressor Attack";
+    load_index(
+        compressor.attack(),
+        setting,
+        &mut target.attack,
+        &mut warnings,
+    );
+
+    let setting = "Compressor Release";
+    load_index(
+        compressor.release(),
+        setting,
+        &mut target.release,
+        &mut warnings,
+    );
+
+    // Gate..
+    let gate = classic.gate();
+    let target = &mut profile.gate;
+    target.enabled = gate.enabled();
+    target.threshold = gate.threshold();
+    target.attenuation = gate.attenuation();
+
+    let setting = "Gate Attack";
+    load_index(gate.attack(), setting, &mut target.attack, &mut warnings);
+
+    let setting = "Gate Release";
+    load_index(gate.release(), setting, &mut target.release, &mut warnings);
+
+    profile.deess = classic.deess();
+    profile.bleep_volume = classic.bleep_level();
+
+    Ok(Conversion { profile, warnings })
+}
+
+/// The loader doesn't tell us which elements were in the profile, so we list them ourselves
+fn profile_elements<R: Read + Seek>(reader: R) -> Result<BTreeSet<String>> {
+    let mut archive = ZipArchive::new(reader)?;
+    let xml = archive.by_name("profile.xml")?;
+
+    let mut elements = BTreeSet::new();
+    for event in EventReader::new(xml) {
+        if let XmlEvent::StartElement { name, .. } = event? {
+            elements.insert(name.local_name);
+        }
+    }
+    Ok(elements)
+}
+
+fn display_mode_from_classic(display: ColourDisplay) -> Vec<FaderDisplayMode> {
+    match display {
+        ColourDisplay::Gradient => vec![FaderDisplayMode::Gradient],
+        ColourDisplay::Meter => vec![FaderDisplayMode::Meter],
+        ColourDisplay::GradientMeter => vec![FaderDisplayMode::Gradient, FaderDisplayMode::Meter],
+        ColourDisplay::TwoColour => vec![],
+    }
+}
+
+fn behaviour_from_classic(style: ColourOffStyle) -> InactiveButtonBehaviour {
+    match style {
+        ColourOffStyle::Dimmed => InactiveButtonBehaviour::DimActive,
+        ColourOffStyle::Colour2 => InactiveButtonBehaviour::InactiveColour,
+        ColourOffStyle::DimmedColour2 => InactiveButtonBehaviour::DimInactive,
+    }
+}
+
+fn load_colour(
+    map: &ColourMap,
+    index: u8,
+    target: &mut Colour,
+    setting: &str,
+    warnings: &mut Vec<ConversionWarning>,
+) {
+    let colour = map.colour(index);
+    match colour_from_classic(colour) {
+        Some(colour) => *target = colour,
+        None => warnings.push(InvalidColour {
+            setting: setting.to_string(),
+            value: colour.to_rgb(),
+        }),
+    }
+}
+
+fn load_button_colours(
+    map: &ColourMap,
+    target: &mut ButtonColourSet,
+    setting: &str,
+    warnings: &mut Vec<ConversionWarning>,
+) {
+    load_colour(map, 0, &mut target.active_colour, setting, warnings);
+    load_colour(map, 1, &mut target.inactive_colour, setting, warnings);
+    target.inactive_behaviour = behaviour_from_classic(*map.get_off_style());
+}
+
+/// Loads an enum value from its index, keeping the current value if it's out of range.
+fn load_index<T: IntoEnumIterator>(
+    index: u8,
+    setting: &str,
+    target: &mut T,
+    warnings: &mut Vec<ConversionWarning>,
+) {
+    match T::iter().nth(index as usize) {
+        Some(value) => *target = value,
+        None => warnings.push(InvalidValue {
+            setting: setting.to_string(),
+            value: index as i64,
+        }),
+    }
+}
@@ -0,0 +1,296 @@
+/*
+   This module handles conversion between our Profile structures, and the 'classic' profile
+   formats used by the official GoXLR App and the 1.x GoXLR Utility (.goxlr and .goxlrMicProfile).
+
+   The classic formats and ours don't line up perfectly, so rather than silently dropping things
+   that can't be converted, every conversion returns a list of warnings alongside the result
+   which can be presented to the user.
+*/
+
+use std::fmt::{Display, Formatter};
+
+use anyhow::Result;
+
+use goxlr_profile_loader::components::colours::Colour as ClassicColour;
+use goxlr_profile_loader::components::mixer::{
+    FullChannelList, InputChannels as In, OutputChannels as Out,
+};
+use goxlr_profile_loader::components::mute::MuteFunction;
+use goxlr_profile_loader::components::simple::SimpleElements;
+use goxlr_profile_loader::Faders;
+use goxlr_shared::buttons::LightingButtons;
+use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::channels::input::InputChannels;
+use goxlr_shared::channels::mute::MuteActionChannels;
+use goxlr_shared::channels::output::OutputChannels;
+use goxlr_shared::channels::sub_mix::SubMixChannels;
+use goxlr_shared::channels::volume::VolumeChannels;
+use goxlr_shared::colours::Colour;
+use goxlr_shared::faders::Fader;
+
+use crate::MuteAction;
+
+mod export;
+mod import;
+
+pub use export::{export_mic_profile, export_profile};
+pub use import::{import_mic_profile, import_profile};
+
+/// The result of a conversion, containing the converted profile and anything that couldn't
+/// be carried across.
+#[derive(Debug)]
+pub struct Conversion<T> {
+    pub profile: T,
+    pub warnings: Vec<ConversionWarning>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConversionWarning {
+    /// A Fader has a channel assigned which can't be assigned in the target format
+    UnassignableFaderChannel { fader: Fader, channel: String },
+
+    /// A route's level can't be represented exactly, it's been rounded to the nearest level
+    PartialRoute {
+        input: InputChannels,
+        output: OutputChannels,
+        value: u16,
+    },
+
+    /// The scribble label is longer than a single character, only the first has been kept
+    LabelTruncated { fader: Fader, label: String },
+
+    /// A colour couldn't be parsed, the default has been kept
+    InvalidColour { setting: String, value: String },
+
+    /// A value was out of range for the setting, the default has been kept
+    InvalidValue { setting: String, value: i64 },
+
+    /// The source has settings for a feature which isn't supported, they've been dropped
+    UnsupportedFeature(String),
+
+    /// Only the first fader page can be exported, the rest have been dropped
+    ExtraPagesDropped { count: usize },
+
+    /// A channel isn't assigned to a fader on the first page, so has nowhere to store its
+    /// colours, mute button behaviour and screen settings
+    ChannelNotExported { channel: FaderChannels },
+
+    /// The channel has a Sub Mix link ratio, which can't be represented
+    SubMixLinkRatio { channel: SubMixChannels, ratio: f64 },
+
+    /// A mute action targets a combination of outputs which can't be represented, this has
+    /// been replaced with 'Mute to All'
+    UnsupportedMuteTargets {
+        setting: String,
+        targets: Vec<OutputChannels>,
+    },
+
+    /// The cough button is assigned to a channel other than the microphone
+    CoughChannelAssignment { channel: MuteActionChannels },
+
+    /// The classic cough button only has targets for its current behaviour, so the targets for
+    /// the other action have been dropped
+    UnusedCoughTargets {
+        action: MuteAction,
+        targets: Vec<OutputChannels>,
+    },
+}
+
+impl Display for ConversionWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConversionWarning::UnassignableFaderChannel { fader, channel } => {
+                write!(
+                    f,
+                    "Fader {:?}: Channel {} cannot be assigned",
+                    fader, channel
+                )
+            }
+            ConversionWarning::PartialRoute {
+                input,
+                output,
+                value,
+            } => {
+                write!(
+                    f,
+                    "Route {:?} -> {:?}: Partial value {} rounded",
+                    input, output, value
+                )
+            }
+            ConversionWarning::LabelTruncated { fader, label } => {
+                write!(f, "Fader {:?}: Label '{}' truncated", fader, label)
+            }
+            ConversionWarning::InvalidColour { setting, value } => {
+                write!(f, "{}: Invalid Colour '{}'", setting, value)
+            }
+            ConversionWarning::InvalidValue { setting, value } => {
+                write!(f, "{}: Invalid Value {}", setting, value)
+            }
+            ConversionWarning::UnsupportedFeature(feature) => {
+                write!(f, "{} is not supported and has been dropped", feature)
+            }
+            ConversionWarning::ExtraPagesDropped { count } => {
+                write!(f, "{} additional fader page(s) dropped", count)
+            }
+            ConversionWarning::ChannelNotExported { channel } => {
+                write!(
+                    f,
+                    "Channel {:?}: Not on the first page, settings dropped",
+                    channel
+                )
+            }
+            ConversionWarning::SubMixLinkRatio { channel, ratio } => {
+                write!(f, "Sub Mix {:?}: Link Ratio {} dropped", channel, ratio)
+            }
+            ConversionWarning::UnsupportedMuteTargets { setting, targets } => {
+                write!(
+                    f,
+                    "{}: Mute Targets {:?} unsupported, muting to All",
+                    setting, targets
+                )
+            }
+            ConversionWarning::CoughChannelAssignment { channel } => {
+                write!(f, "Cough Button: Assignment to {:?} dropped", channel)
+            }
+            ConversionWarning::UnusedCoughTargets { action, targets } => {
+                write!(
+                    f,
+                    "Cough Button: {:?} Mute Targets {:?} dropped",
+                    action, targets
+                )
+            }
+        }
+    }
+}
+
+// Mapping between the classic types and ours, these are shared by both import and export.
+
+/// Buttons which only have lighting settings, along with the classic element that stores them.
+const LIGHTING_ELEMENTS: [(LightingButtons, SimpleElements); 4] = [
+    (LightingButtons::SamplerSelectA, SimpleElements::SampleBankA),
+    (LightingButtons::SamplerSelectB, SimpleElements::SampleBankB),
+    (LightingButtons::SamplerSelectC, SimpleElements::SampleBankC),
+    (LightingButtons::EffectFx, SimpleElements::FxClear),
+];
+
+fn fader_to_classic(fader: Fader) -> Faders {
+    match fader {
+        Fader::A => Faders::A,
+        Fader::B => Faders::B,
+        Fader::C => Faders::C,
+        Fader::D => Faders::D,
+    }
+}
+
+fn input_to_classic(input: InputChannels) -> In {
+    match input {
+        InputChannels::Microphone => In::Mic,
+        InputChannels::Chat => In::Chat,
+        InputChannels::Music => In::Music,
+        InputChannels::Game => In::Game,
+        InputChannels::Console => In::Console,
+        InputChannels::LineIn => In::LineIn,
+        InputChannels::System => In::System,
+        InputChannels::Sample => In::Sample,
+    }
+}
+
+fn output_to_classic(output: OutputChannels) -> Out {
+    match output {
+        OutputChannels::Headphones => Out::Headphones,
+        OutputChannels::StreamMix => Out::Broadcast,
+        OutputChannels::LineOut => Out::LineOut,
+        OutputChannels::ChatMic => Out::ChatMic,
+        OutputChannels::Sampler => Out::Sampler,
+    }
+}
+
+fn volume_to_classic(channel: VolumeChannels) -> FullChannelList {
+    match channel {
+        VolumeChannels::Microphone => FullChannelList::Mic,
+        VolumeChannels::LineIn => FullChannelList::LineIn,
+        VolumeChannels::Console => FullChannelList::Console,
+        VolumeChannels::System => FullChannelList::System,
+        VolumeChannels::Game => FullChannelList::Game,
+        VolumeChannels::Chat => FullChannelList::Chat,
+        VolumeChannels::Sample => FullChannelList::Sample,
+        VolumeChannels::Music => FullChannelList::Music,
+        VolumeChannels::Headphones => FullChannelList::Headphones,
+        VolumeChannels::MicrophoneMonitor => FullChannelList::MicMonitor,
+        VolumeChannels::LineOut => FullChannelList::LineOut,
+    }
+}
+
+fn fader_channel_to_classic(channel: FaderChannels) -> FullChannelList {
+    match channel {
+        FaderChannels::Microphone => FullChannelList::Mic,
+        FaderChannels::LineIn => FullChannelList::LineIn,
+        FaderChannels::Console => FullChannelList::Console,
+        FaderChannels::System => FullChannelList::System,
+        FaderChannels::Game => FullChannelList::Game,
+        FaderChannels::Chat => FullChannelList::Chat,
+        FaderChannels::Sample => FullChannelList::Sample,
+        FaderChannels::Music => FullChannelList::Music,
+        FaderChannels::Headphones => FullChannelList::Headphones,
+        FaderChannels::LineOut => FullChannelList::LineOut,
+    }
+}
+
+fn fader_channel_from_classic(channel: FullChannelList) -> Option<FaderChannels> {
+    match channel {
+        FullChannelList::Mic => Some(FaderChannels::Microphone),
+        FullChannelList::LineIn => Some(FaderChannels::LineIn),
+        FullChannelList::Console => Some(FaderChannels::Console),
+        FullChannelList::System => Some(FaderChannels::System),
+        FullChannelList::Game => Some(FaderChannels::Game),
+        FullChannelList::Chat => Some(FaderChannels::Chat),
+        FullChannelList::Sample => Some(FaderChannels::Sample),
+        FullChannelList::Music => Some(FaderChannels::Music),
+        FullChannelList::Headphones => Some(FaderChannels::Headphones),
+        FullChannelList::LineOut => Some(FaderChannels::LineOut),
+        FullChannelList::MicMonitor => None,
+    }
+}
+
+/// The classic format only supports a single mute target, or muting to everything.
+fn targets_from_mute_function(function: MuteFunction) -> Vec<OutputChannels> {
+    match function {
+        MuteFunction::All => vec![],
+        MuteFunction::ToStream => vec![OutputChannels::StreamMix],
+        MuteFunction::ToVoiceChat => vec![OutputChannels::ChatMic],
+        MuteFunction::ToPhones => vec![OutputChannels::Headphones],
+        MuteFunction::ToLineOut => vec![OutputChannels::LineOut],
+    }
+}
+
+/// The reverse of the above, returns None if the targets can't be represented.
+fn mute_function_from_targets(targets: &[OutputChannels]) -> Option<MuteFunction> {
+    match targets {
+        [] => Some(MuteFunction::All),
+        [OutputChannels::StreamMix] => Some(MuteFunction::ToStream),
+        [OutputChannels::ChatMic] => Some(MuteFunction::ToVoiceChat),
+        [OutputChannels::Headphones] => Some(MuteFunction::ToPhones),
+        [OutputChannels::LineOut] => Some(MuteFunction::ToLineOut),
+        _ => None,
+    }
+}
+
+/// Classic colours are stored as an 'RRGGBB' hex string.
+fn colour_from_classic(colour: &ClassicColour) -> Option<Colour> {
+    let rgb = colour.to_rgb();
+    if rgb.len() != 6 {
+        return None;
+    }
+
+    let value = u32::from_str_radix(&rgb, 16).ok()?;
+    Some(Colour {
+        red: (value >> 16) & 0xFF,
+        green: (value >> 8) & 0xFF,
+        blue: value & 0xFF,
+    })
+}
+
+fn colour_to_classic(colour: &Colour) -> Result<ClassicColour> {
+    let rgb = format!("{:02X}{:02X}{:02X}", colour.red, colour.green, colour.blue);
+    ClassicColour::fake_rgb(&rgb)
+}
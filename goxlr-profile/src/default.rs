@@ -18,9 +18,9 @@ use crate::{
     ButtonColourSet, Channels, Compressor, CoughBehaviour, CoughSettings, EqualizerValue,
     FaderChannel, FaderColourSet, FaderDisplay, FaderPage, FaderPages, Gate,
     InactiveButtonBehaviour, MicProfile, Microphone, MicrophoneType, MuteActionChannel, Profile,
-    Screen,
+    Route, Screen,
 };
-use crate::{Configuration, Fader};
+use crate::{Configuration, EncoderColourSet, Fader, Lighting};
 use crate::{MuteAction, SwearSettings};
 
 /// The default profile if one isn't found..
@@ -61,6 +61,7 @@ impl Default for Profile {
             image: None,
             text: None,
             label: None,
+            content: Default::default(),
         };
 
         let fader_display = FaderDisplay {
@@ -256,21 +257,22 @@ impl Default for Profile {
         };
 
         // Default Routing Table (based on old defaults..)
-        let mut routing: EnumMap<InputChannels, EnumMap<OutputChannels, bool>> = Default::default();
+        let mut routing: EnumMap<InputChannels, EnumMap<OutputChannels, Route>> =
+            Default::default();
 
         // Headphones and Stream Mix go to all..
         for input in InputChannels::iter() {
-            routing[input][OutputChannels::Headphones] = true;
-            routing[input][OutputChannels::StreamMix] = true;
+            routing[input][OutputChannels::Headphones].enabled = true;
+            routing[input][OutputChannels::StreamMix].enabled = true;
         }
 
         // Mic goes to Lineout, Chat Mic and Sampler..
-        routing[InputChannels::Microphone][OutputChannels::LineOut] = true;
-        routing[InputChannels::Microphone][OutputChannels::ChatMic] = true;
-        routing[InputChannels::Microphone][OutputChannels::Sampler] = true;
+        routing[InputChannels::Microphone][OutputChannels::LineOut].enabled = true;
+        routing[InputChannels::Microphone][OutputChannels::ChatMic].enabled = true;
+        routing[InputChannels::Microphone][OutputChannels::Sampler].enabled = true;
 
         // Samples go to Chat Mic..
-        routing[InputChannels::Sample][OutputChannels::ChatMic] = true;
+        routing[InputChannels::Sample][OutputChannels::ChatMic].enabled = true;
 
         // General Configuration
         let configuration = Configuration {
@@ -323,11 +325,46 @@ impl Default for Profile {
             routing,
             swear,
             cough,
+            sub_mix: Default::default(),
+            lighting: Default::default(),
             configuration,
         }
     }
 }
 
+impl Default for Lighting {
+    fn default() -> Self {
+        let cyan = Colour {
+            red: 0,
+            green: 255,
+            blue: 255,
+        };
+
+        let button = ButtonColourSet {
+            active_colour: cyan,
+            inactive_colour: Default::default(),
+            inactive_behaviour: InactiveButtonBehaviour::DimActive,
+        };
+
+        let encoder = EncoderColourSet {
+            left_colour: cyan,
+            right_colour: Default::default(),
+            knob_colour: Colour {
+                red: 255,
+                green: 255,
+                blue: 255,
+            },
+        };
+
+        Self {
+            buttons: EnumMap::from_fn(|_| button),
+            encoders: EnumMap::from_fn(|_| encoder),
+            themes: Default::default(),
+            animation: Default::default(),
+        }
+    }
+}
+
 impl Default for MicProfile {
     fn default() -> Self {
         let eq = enum_map! {
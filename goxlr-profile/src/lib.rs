@@ -1,24 +1,30 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use enum_map::{enum_map, Enum, EnumMap};
 use serde::{Deserialize, Serialize};
 
-use goxlr_shared::buttons::InactiveButtonBehaviour;
+use goxlr_shared::animation::{AnimationMode, WaterfallDirection};
+use goxlr_shared::buttons::{InactiveButtonBehaviour, LightingButtons};
 use goxlr_shared::channels::fader::FaderChannels;
 use goxlr_shared::channels::input::InputChannels;
 use goxlr_shared::channels::mute::MuteActionChannels;
 use goxlr_shared::channels::output::OutputChannels;
 use goxlr_shared::channels::sub_mix::SubMixChannels;
 use goxlr_shared::channels::volume::VolumeChannels;
-use goxlr_shared::colours::{Colour, FaderColour, FaderDisplayMode, TwoColour};
+use goxlr_shared::colours::{Colour, FaderColour, FaderDisplayMode, ThreeColour, TwoColour};
 use goxlr_shared::compressor::{CompressorAttackTime, CompressorRatio, CompressorReleaseTime};
+use goxlr_shared::encoders::Encoders;
 use goxlr_shared::eq_frequencies::{Frequencies, MiniFrequencies};
 use goxlr_shared::faders::Fader;
 use goxlr_shared::gate::GateTimes;
 use goxlr_shared::microphone::MicrophoneType;
 use goxlr_shared::mute::MuteState;
+use goxlr_shared::routing::RouteValue;
+use goxlr_shared::scribbles::ScribbleContent;
 use goxlr_shared::submix::Mix;
 
+pub mod classic;
 mod default;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,25 +38,127 @@ pub struct Profile {
     /// Configuration for the Output Settings..
     pub outputs: EnumMap<OutputChannels, Outputs>,
 
+    /// Sub Mix settings which aren't specific to a channel or output
+    #[serde(default)]
+    pub sub_mix: SubMixSettings,
+
     /// Configuration for the Swear Button
     pub swear: SwearSettings,
 
     /// Configuration for the Cough button
     pub cough: CoughSettings,
 
+    /// Colours for the Buttons and Encoders not covered elsewhere
+    #[serde(default)]
+    pub lighting: Lighting,
+
     /// The Routing Configuration
-    pub routing: EnumMap<InputChannels, EnumMap<OutputChannels, bool>>,
+    pub routing: EnumMap<InputChannels, EnumMap<OutputChannels, Route>>,
 
     /// The General 'Configuration' of the device
     pub configuration: Configuration,
 }
 
+/// The level of a route at full volume, this is the GoXLR's value for 'On'.
+///
+/// Levels are a linear gain, each step being 1/32 of full volume (the official app stores the
+/// same scale as 0 to 8192). So 16 is roughly -6 dB, 8 roughly -12 dB, and 1 roughly -30 dB.
+pub const ROUTE_LEVEL_MAX: u8 = 0x20;
+
+/// A single route from an input to an output
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "RouteFormat")]
+pub struct Route {
+    /// Whether the input is sent to the output
+    pub enabled: bool,
+
+    /// The level the input is sent at when enabled, from 0 to ROUTE_LEVEL_MAX (full volume) on
+    /// a linear scale, see `Route::level_to_decibels`
+    pub level: u8,
+}
+
+impl Route {
+    /// The value the GoXLR should be sent for this route
+    pub fn value(&self) -> RouteValue {
+        match (self.enabled, self.level) {
+            (false, _) => RouteValue::Off,
+            (true, ROUTE_LEVEL_MAX) => RouteValue::On,
+            (true, level) => RouteValue::Value(level),
+        }
+    }
+
+    /// The gain of a route level in decibels, relative to full volume. None is silence (-inf dB)
+    pub fn level_to_decibels(level: u8) -> Option<f64> {
+        match level.min(ROUTE_LEVEL_MAX) {
+            0 => None,
+            level => Some(20.0 * (level as f64 / ROUTE_LEVEL_MAX as f64).log10()),
+        }
+    }
+
+    /// The closest route level to a gain in decibels, anything above 0 dB is full volume
+    pub fn level_from_decibels(decibels: f64) -> u8 {
+        let level = 10f64.powf(decibels / 20.0) * ROUTE_LEVEL_MAX as f64;
+        level.round().clamp(0.0, ROUTE_LEVEL_MAX as f64) as u8
+    }
+}
+
+impl Default for Route {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            level: ROUTE_LEVEL_MAX,
+        }
+    }
+}
+
+/// Older profiles stored routes as a simple on / off, so we need to be able to read both
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RouteFormat {
+    Enabled(bool),
+    Route { enabled: bool, level: u8 },
+}
+
+impl From<RouteFormat> for Route {
+    fn from(value: RouteFormat) -> Self {
+        match value {
+            RouteFormat::Enabled(enabled) => Route {
+                enabled,
+                ..Default::default()
+            },
+            RouteFormat::Route { enabled, level } => Route {
+                enabled,
+                level: level.min(ROUTE_LEVEL_MAX),
+            },
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Outputs {
     /// The Mix this Output is Assigned to when Sub Mixing is enabled
     pub mix_assignment: Mix,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SubMixSettings {
+    /// Whether Sub Mixing is active, when disabled all outputs are sent through Mix A
+    pub enabled: bool,
+
+    /// The Mix sent to the Headphones, if None this follows the Headphones' mix assignment
+    pub monitor_mix: Option<Mix>,
+}
+
+impl Default for SubMixSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            monitor_mix: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FaderPages {
     /// The Currently Active Fader Page
@@ -163,10 +271,14 @@ pub struct Screen {
 
     /// The Charater to display in the top left corner of the screen
     pub label: Option<char>,
+
+    /// What's used as the text on the screen, this may be replaced by dynamic content
+    #[serde(default)]
+    pub content: ScribbleContent,
 }
 
 /// This defines a Buttons colour configuration
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ButtonColourSet {
     /// The Currently Set 'Active' Colour
     pub active_colour: Colour,
@@ -188,7 +300,7 @@ impl From<ButtonColourSet> for TwoColour {
 }
 
 /// Colour's related to the Fader Slider
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FaderColourSet {
     /// The colour displayed above the fader
     pub top_colour: Colour,
@@ -206,8 +318,70 @@ impl From<FaderColourSet> for FaderColour {
     }
 }
 
+/// Lighting for the Sampler, Effects and Encoders, which have no other configuration (yet!)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lighting {
+    /// The Colours of the Sampler, Effect and Preset buttons
+    pub buttons: EnumMap<LightingButtons, ButtonColourSet>,
+
+    /// The Colours of the Encoders
+    pub encoders: EnumMap<Encoders, EncoderColourSet>,
+
+    /// Named themes which can be applied to all the colours at once
+    #[serde(default)]
+    pub themes: BTreeMap<String, LightingTheme>,
+
+    /// The lighting animation, only applied on devices which support them
+    #[serde(default)]
+    pub animation: Animation,
+}
+
+/// The animation settings, the meaning of the two modifiers depends on the mode
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Animation {
+    pub mode: AnimationMode,
+    pub mod1: u8,
+    pub mod2: u8,
+    pub waterfall: WaterfallDirection,
+}
+
+/// A theme recolours everything on the device in one go, updating the relevant colours in the
+/// profile as it's applied
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LightingTheme {
+    /// Sets every colour of every button, fader, scribble and encoder to a single colour, so
+    /// buttons only show their state through their inactive behaviour
+    Solid(Colour),
+
+    /// Sets every channel's fader to run from the top colour to the bottom colour
+    FaderGradient { top: Colour, bottom: Colour },
+}
+
+/// Colour's related to an Encoder
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncoderColourSet {
+    /// The colour of the lights to the left of the current value
+    pub left_colour: Colour,
+
+    /// The colour of the lights to the right of the current value
+    pub right_colour: Colour,
+
+    /// The colour of the light at the current value
+    pub knob_colour: Colour,
+}
+
+impl From<EncoderColourSet> for ThreeColour {
+    fn from(value: EncoderColourSet) -> Self {
+        ThreeColour {
+            left: value.left_colour,
+            right: value.right_colour,
+            knob: value.knob_colour,
+        }
+    }
+}
+
 /// These are the different methods of interacting with Mute Keys
-#[derive(Debug, Copy, Clone, Enum, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Enum, Eq, PartialEq, Serialize, Deserialize)]
 pub enum MuteAction {
     Press,
     Hold,
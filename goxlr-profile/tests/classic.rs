@@ -0,0 +1,309 @@
+/*
+   Tests for importing and exporting the classic profile formats, the fixtures in
+   fixtures/classic were laid out in the same way as the official app's own profiles, with a few
+   oddities (a partial route, a long scribble label) so we can check that the warnings are
+   produced.
+*/
+
+use std::fs::File;
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use goxlr_profile::classic::ConversionWarning::{
+    LabelTruncated, PartialRoute, UnsupportedFeature, UnusedCoughTargets,
+};
+use goxlr_profile::classic::{export_profile, import_mic_profile, import_profile};
+use goxlr_profile::{ButtonColourSet, CoughBehaviour, MuteAction, Route};
+use goxlr_shared::buttons::{InactiveButtonBehaviour, LightingButtons};
+use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::channels::input::InputChannels;
+use goxlr_shared::channels::mute::MuteActionChannels;
+use goxlr_shared::channels::output::OutputChannels;
+use goxlr_shared::channels::volume::VolumeChannels;
+use goxlr_shared::colours::{Colour, FaderDisplayMode};
+use goxlr_shared::compressor::{CompressorAttackTime, CompressorRatio, CompressorReleaseTime};
+use goxlr_shared::eq_frequencies::{Frequencies, MiniFrequencies};
+use goxlr_shared::faders::Fader;
+use goxlr_shared::gate::GateTimes;
+use goxlr_shared::microphone::MicrophoneType;
+
+fn fixture(name: &str) -> Result<File> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures/classic")
+        .join(name);
+    Ok(File::open(path)?)
+}
+
+fn colour(red: u32, green: u32, blue: u32) -> Colour {
+    Colour { red, green, blue }
+}
+
+fn buttons(
+    active: Colour,
+    inactive: Colour,
+    behaviour: InactiveButtonBehaviour,
+) -> ButtonColourSet {
+    ButtonColourSet {
+        active_colour: active,
+        inactive_colour: inactive,
+        inactive_behaviour: behaviour,
+    }
+}
+
+#[test]
+fn import_classic_profile() -> Result<()> {
+    let conversion = import_profile(fixture("Sample.goxlr")?)?;
+    let profile = conversion.profile;
+
+    // The single classic page..
+    assert_eq!(profile.pages.page_list.len(), 1);
+    let faders = profile.pages.page_list[0].faders;
+    assert_eq!(faders[Fader::A], FaderChannels::Microphone);
+    assert_eq!(faders[Fader::B], FaderChannels::Music);
+    assert_eq!(faders[Fader::C], FaderChannels::Chat);
+    assert_eq!(faders[Fader::D], FaderChannels::System);
+
+    let volumes = profile.channels.volumes;
+    assert_eq!(volumes[VolumeChannels::Microphone], 200);
+    assert_eq!(volumes[VolumeChannels::Chat], 180);
+    assert_eq!(volumes[VolumeChannels::Music], 128);
+    assert_eq!(volumes[VolumeChannels::LineIn], 90);
+    assert_eq!(volumes[VolumeChannels::Headphones], 230);
+
+    // Routing, including a half volume route, one which doesn't land on one of our levels, and
+    // one too quiet for our lowest level (which shouldn't become a silent route)
+    let routing = profile.routing;
+    let off = Route {
+        enabled: false,
+        level: 0,
+    };
+    let on = |level| Route {
+        enabled: true,
+        level,
+    };
+    let mic = InputChannels::Microphone;
+    assert_eq!(routing[mic][OutputChannels::Headphones], off);
+    assert_eq!(routing[mic][OutputChannels::StreamMix], on(32));
+    let music = InputChannels::Music;
+    assert_eq!(routing[music][OutputChannels::Headphones], on(16));
+    let game = InputChannels::Game;
+    assert_eq!(routing[game][OutputChannels::StreamMix], on(20));
+    assert_eq!(routing[game][OutputChannels::ChatMic], on(1));
+
+    // Fader Colours and Mute Buttons follow the channel assigned to the fader
+    let display = &profile.channels.configs[FaderChannels::Microphone].display;
+    let expected = vec![FaderDisplayMode::Gradient, FaderDisplayMode::Meter];
+    assert_eq!(display.fader_display_mode, expected);
+    assert_eq!(display.fader_colours.top_colour, colour(255, 0, 0));
+    assert_eq!(display.fader_colours.bottom_colour, colour(0, 0, 255));
+
+    let black = colour(0, 0, 0);
+    let expected = buttons(
+        colour(0, 255, 255),
+        black,
+        InactiveButtonBehaviour::DimActive,
+    );
+    assert_eq!(display.mute_colours, expected);
+
+    let display = &profile.channels.configs[FaderChannels::Music].display;
+    let behaviour = display.mute_colours.inactive_behaviour;
+    assert_eq!(behaviour, InactiveButtonBehaviour::InactiveColour);
+
+    let display = &profile.channels.configs[FaderChannels::Chat].display;
+    let behaviour = display.mute_colours.inactive_behaviour;
+    assert_eq!(behaviour, InactiveButtonBehaviour::DimInactive);
+
+    // Scribbles..
+    let screen = &profile.channels.configs[FaderChannels::Microphone]
+        .display
+        .screen_display;
+    assert_eq!(screen.text.as_deref(), Some("Voice"));
+    assert_eq!(screen.label, Some('M'));
+    assert!(!screen.inverted);
+
+    let screen = &profile.channels.configs[FaderChannels::Music]
+        .display
+        .screen_display;
+    assert_eq!(screen.image, Some(PathBuf::from("Music.png")));
+
+    let screen = &profile.channels.configs[FaderChannels::Chat]
+        .display
+        .screen_display;
+    assert_eq!(screen.text.as_deref(), Some("Discord"));
+    assert_eq!(screen.label, Some('D'));
+    assert!(screen.inverted);
+
+    // Mute Actions, a single target on press, everything on hold
+    let actions = &profile.channels.mute_actions;
+    let mic = &actions[MuteActionChannels::Microphone].actions;
+    assert_eq!(mic[MuteAction::Press], vec![OutputChannels::StreamMix]);
+    assert_eq!(mic[MuteAction::Hold], vec![]);
+    assert_eq!(
+        actions[MuteActionChannels::Music].actions[MuteAction::Press],
+        vec![]
+    );
+    let chat = &actions[MuteActionChannels::Chat].actions;
+    assert_eq!(chat[MuteAction::Press], vec![OutputChannels::Headphones]);
+    let system = &actions[MuteActionChannels::System].actions;
+    assert_eq!(system[MuteAction::Press], vec![OutputChannels::ChatMic]);
+
+    // The Cough and Swear buttons..
+    let cough = &profile.cough;
+    assert_eq!(cough.channel_assignment, MuteActionChannels::Microphone);
+    let stream = vec![OutputChannels::StreamMix];
+    assert_eq!(cough.mute_actions[MuteAction::Press], stream);
+    assert_eq!(cough.mute_actions[MuteAction::Hold], stream);
+    let expected = buttons(
+        colour(255, 0, 255),
+        black,
+        InactiveButtonBehaviour::DimActive,
+    );
+    assert_eq!(cough.colours, expected);
+
+    let expected = buttons(
+        colour(255, 0, 0),
+        colour(64, 0, 0),
+        InactiveButtonBehaviour::InactiveColour,
+    );
+    assert_eq!(profile.swear.colours, expected);
+
+    let expected = buttons(colour(255, 0, 0), black, InactiveButtonBehaviour::DimActive);
+    let bank = profile.lighting.buttons[LightingButtons::SamplerSelectA];
+    assert_eq!(bank, expected);
+
+    // Only the unsupported features present in the profile should be reported
+    let expected = vec![
+        PartialRoute {
+            input: InputChannels::Game,
+            output: OutputChannels::StreamMix,
+            value: 5000,
+        },
+        PartialRoute {
+            input: InputChannels::Game,
+            output: OutputChannels::ChatMic,
+            value: 100,
+        },
+        LabelTruncated {
+            fader: Fader::C,
+            label: String::from("DC"),
+        },
+        UnsupportedFeature(String::from("Effects (Megaphone / Robot / Hardtune)")),
+        UnsupportedFeature(String::from("Animations")),
+    ];
+    assert_eq!(conversion.warnings, expected);
+
+    Ok(())
+}
+
+#[test]
+fn export_classic_profile() -> Result<()> {
+    let mut profile = import_profile(fixture("Sample.goxlr")?)?.profile;
+
+    // Move every button away from the behaviour in the fixture, so we know they've been written
+    let display = &mut profile.channels.configs[FaderChannels::Microphone].display;
+    display.mute_colours.inactive_behaviour = InactiveButtonBehaviour::InactiveColour;
+    profile.cough.colours.inactive_behaviour = InactiveButtonBehaviour::DimInactive;
+    profile.swear.colours.inactive_behaviour = InactiveButtonBehaviour::DimActive;
+
+    let lighting = &mut profile.lighting.buttons;
+    let bank = &mut lighting[LightingButtons::SamplerSelectB];
+    bank.inactive_behaviour = InactiveButtonBehaviour::DimInactive;
+    let fx = &mut lighting[LightingButtons::EffectFx];
+    fx.inactive_behaviour = InactiveButtonBehaviour::InactiveColour;
+    fx.active_colour = colour(0, 128, 255);
+
+    // Only the targets for the cough button's current behaviour can be exported
+    profile.cough.cough_behaviour = CoughBehaviour::Press;
+    profile.cough.mute_actions[MuteAction::Hold] = vec![OutputChannels::LineOut];
+
+    let conversion = export_profile(&profile, fixture("Sample.goxlr")?)?;
+    let expected = UnusedCoughTargets {
+        action: MuteAction::Hold,
+        targets: vec![OutputChannels::LineOut],
+    };
+    assert!(conversion.warnings.contains(&expected));
+
+    let mut exported = conversion.profile;
+    let directory = tempfile::tempdir()?;
+    let path = directory.path().join("Exported.goxlr");
+    exported.save(&path)?;
+
+    let imported = import_profile(File::open(&path)?)?.profile;
+    for channel in [FaderChannels::Microphone, FaderChannels::Music] {
+        let expected = profile.channels.configs[channel].display.mute_colours;
+        let colours = imported.channels.configs[channel].display.mute_colours;
+        assert_eq!(colours, expected, "{:?}", channel);
+    }
+    assert_eq!(imported.cough.colours, profile.cough.colours);
+    assert_eq!(imported.swear.colours, profile.swear.colours);
+
+    let buttons = [
+        LightingButtons::SamplerSelectA,
+        LightingButtons::SamplerSelectB,
+        LightingButtons::SamplerSelectC,
+        LightingButtons::EffectFx,
+    ];
+    for button in buttons {
+        let expected = profile.lighting.buttons[button];
+        assert_eq!(imported.lighting.buttons[button], expected, "{:?}", button);
+    }
+
+    // The rest of the profile should survive the trip too
+    assert_eq!(imported.channels.volumes, profile.channels.volumes);
+    assert_eq!(imported.routing, profile.routing);
+    assert_eq!(
+        imported.pages.page_list[0].faders,
+        profile.pages.page_list[0].faders
+    );
+
+    Ok(())
+}
+
+#[test]
+fn import_classic_mic_profile() -> Result<()> {
+    let conversion = import_mic_profile(fixture("Sample.goxlrMicProfile")?)?;
+    let profile = conversion.profile;
+
+    let microphone = &profile.microphone;
+    assert!(matches!(microphone.mic_type, MicrophoneType::Phantom));
+    assert_eq!(microphone.mic_gains[MicrophoneType::XLR], 40);
+    assert_eq!(microphone.mic_gains[MicrophoneType::Phantom], 30);
+    assert_eq!(microphone.mic_gains[MicrophoneType::Jack], 20);
+
+    let equalizer = &profile.equalizer;
+    assert_eq!(equalizer[Frequencies::Eq31h].gain, 2);
+    assert_eq!(equalizer[Frequencies::Eq31h].frequency, 31.5);
+    assert_eq!(equalizer[Frequencies::Eq1kh].gain, 3);
+    assert_eq!(equalizer[Frequencies::Eq16kh].gain, -9);
+    assert_eq!(equalizer[Frequencies::Eq16kh].frequency, 16000.0);
+
+    let equalizer = &profile.equalizer_mini;
+    assert_eq!(equalizer[MiniFrequencies::Eq90h].gain, 6);
+    assert_eq!(equalizer[MiniFrequencies::Eq250h].gain, -3);
+    assert_eq!(equalizer[MiniFrequencies::Eq3kh].frequency, 3000.0);
+
+    // The compressor and gate times are stored as an index into the list of values
+    let compressor = &profile.compressor;
+    assert_eq!(compressor.threshold, -18);
+    assert_eq!(compressor.makeup_gain, 3);
+    assert!(matches!(compressor.ratio, CompressorRatio::Ratio2_0));
+    assert!(matches!(compressor.attack, CompressorAttackTime::Attack5ms));
+    assert!(matches!(
+        compressor.release,
+        CompressorReleaseTime::Release100ms
+    ));
+
+    let gate = &profile.gate;
+    assert!(gate.enabled);
+    assert_eq!(gate.threshold, -40);
+    assert_eq!(gate.attenuation, 60);
+    assert!(matches!(gate.attack, GateTimes::Time30ms));
+    assert!(matches!(gate.release, GateTimes::Time250ms));
+
+    assert_eq!(profile.deess, 35);
+    assert_eq!(profile.bleep_volume, -20);
+
+    assert_eq!(conversion.warnings, vec![]);
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use anyhow::{bail, Context, Result};
+
+use crate::icon::Icon;
+
+/*
+    A minimal BMP decoder, this handles uncompressed 1, 4, 8, 24 and 32 bit images, as well as
+    32 bit images using bit fields (which is how most tools write images with transparency).
+*/
+
+pub(crate) const SIGNATURE: &[u8] = b"BM";
+
+const MAX_DIMENSION: usize = 4096;
+
+const FILE_HEADER_SIZE: usize = 14;
+const INFO_HEADER_SIZE: usize = 40;
+
+const BI_RGB: u32 = 0;
+const BI_BITFIELDS: u32 = 3;
+
+pub(crate) fn decode(data: &[u8]) -> Result<Icon> {
+    let pixel_offset = read_u32(data, 10)? as usize;
+    let header_size = read_u32(data, 14)? as usize;
+    if header_size < INFO_HEADER_SIZE {
+        bail!("Unsupported BMP Header (size {})", header_size);
+    }
+
+    let width = read_u32(data, 18)? as i32;
+    let height = read_u32(data, 22)? as i32;
+    let bits_per_pixel = read_u16(data, 28)? as usize;
+    let compression = read_u32(data, 30)?;
+    let colours_used = read_u32(data, 46)? as usize;
+
+    if width <= 0 || height == 0 {
+        bail!("Invalid Image Size ({}x{})", width, height);
+    }
+
+    // A negative height indicates that the rows are stored top to bottom
+    let top_down = height < 0;
+    let (width, height) = (width as usize, height.unsigned_abs() as usize);
+    if width > MAX_DIMENSION || height > MAX_DIMENSION {
+        bail!("Image is too large ({}x{})", width, height);
+    }
+
+    let format = match (compression, bits_per_pixel) {
+        (BI_RGB, 1 | 4 | 8) => {
+            let count = if colours_used == 0 {
+                1 << bits_per_pixel
+            } else {
+                colours_used
+            };
+            let start = FILE_HEADER_SIZE + header_size;
+            let palette = data
+                .get(start..start + count * 4)
+                .context("Truncated Palette")?;
+            Format::Indexed(palette)
+        }
+        (BI_RGB, 24) => Format::Bgr,
+        (BI_RGB, 32) => Format::Bgrx,
+        (BI_BITFIELDS, 32) => {
+            // The masks follow the info header, newer headers contain them (and an alpha mask)
+            let masks = FILE_HEADER_SIZE + INFO_HEADER_SIZE;
+            let alpha = match header_size > INFO_HEADER_SIZE {
+                true => read_u32(data, masks + 12)?,
+                false => 0,
+            };
+            Format::BitFields([
+                read_u32(data, masks)?,
+                read_u32(data, masks + 4)?,
+                read_u32(data, masks + 8)?,
+                alpha,
+            ])
+        }
+        (compression, bits) => bail!(
+            "Unsupported BMP format (Compression {}, {} bits per pixel)",
+            compression,
+            bits
+        ),
+    };
+
+    // Rows are padded to a multiple of 4 bytes
+    let stride = (width * bits_per_pixel).div_ceil(32) * 4;
+    let pixel_data = data
+        .get(pixel_offset..pixel_offset + stride * height)
+        .context("Truncated Image Data")?;
+
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..height {
+        let row = if top_down { y } else { height - 1 - y };
+        let row = &pixel_data[row * stride..(row + 1) * stride];
+
+        for x in 0..width {
+            let pixel = match format {
+                Format::Indexed(palette) => {
+                    let bit = x * bits_per_pixel;
+                    let shift = 8 - bits_per_pixel - (bit % 8);
+                    let index = (row[bit / 8] >> shift) as usize & ((1 << bits_per_pixel) - 1);
+                    let colour = palette.get(index * 4..index * 4 + 3).unwrap_or(&[0, 0, 0]);
+                    Icon::luma(colour[2], colour[1], colour[0], 255)
+                }
+                Format::Bgr => {
+                    let colour = &row[x * 3..x * 3 + 3];
+                    Icon::luma(colour[2], colour[1], colour[0], 255)
+                }
+                // The 4th byte is 'reserved' here, plenty of tools leave it as 0
+                Format::Bgrx => {
+                    let colour = &row[x * 4..x * 4 + 3];
+                    Icon::luma(colour[2], colour[1], colour[0], 255)
+                }
+                Format::BitFields(masks) => {
+                    let value = u32::from_le_bytes(row[x * 4..x * 4 + 4].try_into()?);
+                    let [red, green, blue, alpha] = masks.map(|mask| extract(value, mask));
+                    let alpha = if masks[3] == 0 { 255 } else { alpha };
+                    Icon::luma(red, green, blue, alpha)
+                }
+            };
+            pixels.push(pixel);
+        }
+    }
+
+    Ok(Icon {
+        width,
+        height,
+        pixels,
+    })
+}
+
+#[derive(Copy, Clone)]
+enum Format<'a> {
+    Indexed(&'a [u8]),
+    Bgr,
+    Bgrx,
+    BitFields([u32; 4]),
+}
+
+/// Pulls an 8 bit value out from under a mask, scaling it if the mask is narrower than 8 bits
+fn extract(value: u32, mask: u32) -> u8 {
+    if mask == 0 {
+        return 0;
+    }
+
+    let width = mask.count_ones();
+    let value = (value & mask) >> mask.trailing_zeros();
+    match width {
+        8.. => (value >> (width - 8)) as u8,
+        _ => (value * 255 / ((1 << width) - 1)) as u8,
+    }
+}
+
+fn read_u16(data: &[u8], position: usize) -> Result<u16> {
+    let bytes = data
+        .get(position..position + 2)
+        .context("Unexpected End of File")?;
+    Ok(u16::from_le_bytes(bytes.try_into()?))
+}
+
+fn read_u32(data: &[u8], position: usize) -> Result<u32> {
+    let bytes = data
+        .get(position..position + 4)
+        .context("Unexpected End of File")?;
+    Ok(u32::from_le_bytes(bytes.try_into()?))
+}
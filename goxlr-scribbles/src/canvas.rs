@@ -0,0 +1,80 @@
+use crate::font::{glyph, GLYPH_SPACING, GLYPH_WIDTH};
+
+pub const WIDTH: usize = 128;
+pub const HEIGHT: usize = 64;
+
+/// The size of a rendered scribble, as sent to the GoXLR
+pub const SCRIBBLE_SIZE: usize = WIDTH * HEIGHT / 8;
+
+/// A 1-bit 128x64 image, where a set pixel is lit on the screen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    pixels: [[bool; WIDTH]; HEIGHT],
+}
+
+impl Default for Canvas {
+    fn default() -> Self {
+        Self {
+            pixels: [[false; WIDTH]; HEIGHT],
+        }
+    }
+}
+
+impl Canvas {
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.pixels[y][x]
+    }
+
+    /// Sets a pixel, anything outside the canvas is silently ignored
+    pub fn set(&mut self, x: usize, y: usize, lit: bool) {
+        if x < WIDTH && y < HEIGHT {
+            self.pixels[y][x] = lit;
+        }
+    }
+
+    pub fn invert(&mut self) {
+        self.pixels
+            .iter_mut()
+            .flatten()
+            .for_each(|pixel| *pixel = !*pixel);
+    }
+
+    /// Draws a single line of text with its top left corner at x, y
+    pub(crate) fn draw_text(&mut self, x: usize, y: usize, text: &str, scale: usize) {
+        let advance = (GLYPH_WIDTH + GLYPH_SPACING) * scale;
+        for (index, character) in text.chars().enumerate() {
+            let left = x + index * advance;
+            for (row, bits) in glyph(character).iter().enumerate() {
+                for column in 0..GLYPH_WIDTH {
+                    if bits & (1 << (GLYPH_WIDTH - 1 - column)) == 0 {
+                        continue;
+                    }
+                    self.fill(left + column * scale, y + row * scale, scale, scale);
+                }
+            }
+        }
+    }
+
+    fn fill(&mut self, x: usize, y: usize, width: usize, height: usize) {
+        for y in y..y + height {
+            for x in x..x + width {
+                self.set(x, y, true);
+            }
+        }
+    }
+
+    /// Converts the canvas into the format expected by the GoXLR. The screen is addressed in
+    /// columns, each column is 8 bytes running top to bottom, with the topmost pixel of each
+    /// byte in the least significant bit.
+    pub fn to_bytes(&self) -> [u8; SCRIBBLE_SIZE] {
+        let mut bytes = [0; SCRIBBLE_SIZE];
+        for (y, row) in self.pixels.iter().enumerate() {
+            for (x, &lit) in row.iter().enumerate() {
+                if lit {
+                    bytes[x * (HEIGHT / 8) + y / 8] |= 1 << (y % 8);
+                }
+            }
+        }
+        bytes
+    }
+}
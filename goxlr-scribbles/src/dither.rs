@@ -0,0 +1,33 @@
+use crate::canvas::Canvas;
+use crate::icon::Icon;
+
+/// Draws a greyscale icon onto the canvas at x, y using Floyd-Steinberg dithering, so gradients
+/// and anti-aliased edges still come out looking reasonable on a 1-bit screen.
+pub(crate) fn draw_dithered(canvas: &mut Canvas, icon: &Icon, x: usize, y: usize) {
+    let (width, height) = (icon.width, icon.height);
+    let mut values: Vec<i16> = icon.pixels.iter().map(|&pixel| pixel as i16).collect();
+
+    for row in 0..height {
+        for column in 0..width {
+            let index = row * width + column;
+            let value = values[index];
+
+            let lit = value >= 128;
+            canvas.set(x + column, y + row, lit);
+
+            // Push the error onto the pixels we've not yet drawn..
+            let error = value - if lit { 255 } else { 0 };
+            let mut spread = |column: Option<usize>, row: usize, weight: i16| {
+                if let Some(column) = column.filter(|&column| column < width) {
+                    if row < height {
+                        values[row * width + column] += error * weight / 16;
+                    }
+                }
+            };
+            spread(Some(column + 1), row, 7);
+            spread(column.checked_sub(1), row + 1, 3);
+            spread(Some(column), row + 1, 5);
+            spread(Some(column + 1), row + 1, 1);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use crate::{bmp, png};
+
+/// A greyscale image, transparent areas are treated as unlit so are stored as black.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Icon {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl Icon {
+    /// Builds a greyscale pixel from RGBA, blending it against a black background
+    pub(crate) fn luma(red: u8, green: u8, blue: u8, alpha: u8) -> u8 {
+        let luma = (red as u32 * 299 + green as u32 * 587 + blue as u32 * 114) / 1000;
+        (luma * alpha as u32 / 255) as u8
+    }
+
+    fn get(&self, x: usize, y: usize) -> u8 {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Scales the icon to fit inside the given bounds, keeping its aspect ratio. Each target
+    /// pixel is the average of the source pixels it covers, so fine detail isn't simply lost
+    /// when shrinking.
+    pub(crate) fn fit(&self, max_width: usize, max_height: usize) -> Icon {
+        let scale = f64::min(
+            max_width as f64 / self.width as f64,
+            max_height as f64 / self.height as f64,
+        );
+        let width = ((self.width as f64 * scale).round() as usize).clamp(1, max_width);
+        let height = ((self.height as f64 * scale).round() as usize).clamp(1, max_height);
+
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let (top, bottom) = source_range(y, height, self.height);
+            for x in 0..width {
+                let (left, right) = source_range(x, width, self.width);
+
+                let mut total = 0;
+                for source_y in top..bottom {
+                    for source_x in left..right {
+                        total += self.get(source_x, source_y) as usize;
+                    }
+                }
+                pixels.push((total / ((bottom - top) * (right - left))) as u8);
+            }
+        }
+
+        Icon {
+            width,
+            height,
+            pixels,
+        }
+    }
+}
+
+/// The range of source pixels covered by a target pixel, always at least one pixel wide
+fn source_range(target: usize, target_size: usize, source_size: usize) -> (usize, usize) {
+    let start = target * source_size / target_size;
+    let end = ((target + 1) * source_size / target_size).max(start + 1);
+    (start, end.min(source_size))
+}
+
+/// Loads a PNG or BMP icon from disk, the format is detected from the file's content
+pub fn load_icon(path: &Path) -> Result<Icon> {
+    let data = std::fs::read(path).with_context(|| format!("Unable to read {:?}", path))?;
+    decode_icon(&data).with_context(|| format!("Unable to load {:?}", path))
+}
+
+pub fn decode_icon(data: &[u8]) -> Result<Icon> {
+    let icon = if data.starts_with(png::SIGNATURE) {
+        png::decode(data)?
+    } else if data.starts_with(bmp::SIGNATURE) {
+        bmp::decode(data)?
+    } else {
+        bail!("Unsupported image format, only PNG and BMP are supported");
+    };
+
+    if icon.width == 0 || icon.height == 0 {
+        bail!("Image has no content");
+    }
+    Ok(icon)
+}
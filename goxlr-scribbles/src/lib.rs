@@ -0,0 +1,116 @@
+/*
+    Renders the 128x64 1-bit images displayed on the Scribble screens above the faders.
+
+    The layout is fairly simple, the label (if present) sits in the top left corner. If there's
+    an icon it's scaled to fill the screen (leaving room for the label), with the text running
+    along the bottom. Without an icon the text is centred and drawn as large as possible.
+*/
+
+use std::path::PathBuf;
+
+use log::warn;
+
+use crate::canvas::{Canvas, HEIGHT, SCRIBBLE_SIZE, WIDTH};
+use crate::dither::draw_dithered;
+use crate::font::{text_height, text_width, GLYPH_SPACING, GLYPH_WIDTH};
+use crate::icon::Icon;
+
+mod bmp;
+pub mod canvas;
+mod dither;
+mod font;
+pub mod icon;
+mod png;
+
+/// The gap left around the edges of the screen
+const MARGIN: usize = 2;
+
+/// The largest text scale, anything bigger starts to look rather silly
+const MAX_TEXT_SCALE: usize = 4;
+
+/// Loads the icon (if provided) and renders a scribble, this will render without the icon if
+/// it can't be loaded.
+pub fn get_scribble(
+    icon: Option<PathBuf>,
+    text: Option<String>,
+    label: Option<String>,
+    inverted: bool,
+) -> [u8; SCRIBBLE_SIZE] {
+    let icon = icon.and_then(|path| match icon::load_icon(&path) {
+        Ok(icon) => Some(icon),
+        Err(error) => {
+            warn!("Unable to load Scribble Icon: {:?}", error);
+            None
+        }
+    });
+
+    render(icon.as_ref(), text.as_deref(), label.as_deref(), inverted).to_bytes()
+}
+
+pub fn render(
+    icon: Option<&Icon>,
+    text: Option<&str>,
+    label: Option<&str>,
+    inverted: bool,
+) -> Canvas {
+    let mut canvas = Canvas::default();
+    let text = text.filter(|text| !text.is_empty());
+    let label = label.filter(|label| !label.is_empty());
+
+    let mut label_width = 0;
+    if let Some(label) = label {
+        let (label, _) = fit_text(label, WIDTH / 4, 1);
+        canvas.draw_text(MARGIN - 1, MARGIN - 1, &label, 1);
+        label_width = text_width(&label, 1) + MARGIN;
+    }
+
+    match icon {
+        Some(icon) => {
+            // The text sits on the bottom line, with the icon filling the space above it
+            let mut icon_height = HEIGHT;
+            if let Some(text) = text {
+                let (text, scale) = fit_text(text, WIDTH - MARGIN * 2, 1);
+                let y = HEIGHT - text_height(scale) - 1;
+                draw_centred(&mut canvas, &text, y, scale);
+                icon_height = y - MARGIN;
+            }
+
+            // Keep the icon centred, while staying clear of the label
+            let icon_width = WIDTH - label_width * 2;
+            let icon = icon.fit(icon_width, icon_height);
+            let x = (WIDTH - icon.width) / 2;
+            let y = (icon_height - icon.height) / 2;
+            draw_dithered(&mut canvas, &icon, x, y);
+        }
+        None => {
+            if let Some(text) = text {
+                let (text, scale) = fit_text(text, WIDTH - MARGIN * 2, MAX_TEXT_SCALE);
+                let y = (HEIGHT - text_height(scale)) / 2;
+                draw_centred(&mut canvas, &text, y, scale);
+            }
+        }
+    }
+
+    if inverted {
+        canvas.invert();
+    }
+    canvas
+}
+
+fn draw_centred(canvas: &mut Canvas, text: &str, y: usize, scale: usize) {
+    let x = (WIDTH - text_width(text, scale)) / 2;
+    canvas.draw_text(x, y, text, scale);
+}
+
+/// Finds the largest scale at which the text fits into the width, if it doesn't fit even at
+/// the smallest scale, it's truncated.
+fn fit_text(text: &str, max_width: usize, max_scale: usize) -> (String, usize) {
+    for scale in (1..=max_scale).rev() {
+        if text_width(text, scale) <= max_width {
+            return (text.to_string(), scale);
+        }
+    }
+
+    let characters = (max_width + GLYPH_SPACING) / (GLYPH_WIDTH + GLYPH_SPACING);
+    (text.chars().take(characters).collect(), 1)
+}
@@ -0,0 +1,225 @@
+use std::io::Read;
+
+use anyhow::{bail, Context, Result};
+use flate2::read::ZlibDecoder;
+
+use crate::icon::Icon;
+
+/*
+    A minimal PNG decoder, this supports every colour type and bit depth, but not interlacing.
+    Transparency is taken from the alpha channel or, for paletted images, the tRNS chunk.
+*/
+
+pub(crate) const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+// We're only ever going to display these at 128x64, so there's no need for anything huge.
+const MAX_DIMENSION: usize = 4096;
+
+struct Header {
+    width: usize,
+    height: usize,
+    bit_depth: u8,
+    colour_type: u8,
+}
+
+impl Header {
+    fn channels(&self) -> Result<usize> {
+        Ok(match (self.colour_type, self.bit_depth) {
+            (0, 1 | 2 | 4 | 8 | 16) => 1,
+            (3, 1 | 2 | 4 | 8) => 1,
+            (2, 8 | 16) => 3,
+            (4, 8 | 16) => 2,
+            (6, 8 | 16) => 4,
+            (colour, depth) => bail!("Invalid Colour Type {} with Bit Depth {}", colour, depth),
+        })
+    }
+}
+
+pub(crate) fn decode(data: &[u8]) -> Result<Icon> {
+    let mut header = None;
+    let mut palette: &[u8] = &[];
+    let mut transparency: &[u8] = &[];
+    let mut compressed = vec![];
+
+    let mut position = SIGNATURE.len();
+    loop {
+        let length = read_u32(data, position)? as usize;
+        let kind = data
+            .get(position + 4..position + 8)
+            .context("Truncated Chunk")?;
+        let body = data
+            .get(position + 8..position + 8 + length)
+            .context("Truncated Chunk")?;
+
+        match kind {
+            b"IHDR" => header = Some(read_header(body)?),
+            b"PLTE" => palette = body,
+            b"tRNS" => transparency = body,
+            b"IDAT" => compressed.extend_from_slice(body),
+            b"IEND" => break,
+            _ => {}
+        }
+
+        // Skip the body and the CRC
+        position += 12 + length;
+    }
+
+    let header = header.context("Missing IHDR Chunk")?;
+    let channels = header.channels()?;
+    if header.colour_type == 3 && palette.is_empty() {
+        bail!("Missing Palette for Indexed Image");
+    }
+
+    let bits_per_pixel = channels * header.bit_depth as usize;
+    let stride = (header.width * bits_per_pixel).div_ceil(8);
+
+    // Each row is prefixed with its filter type, don't inflate any more than that however much
+    // the data holds (a short read is caught when unfiltering).
+    let expected = (stride + 1) * header.height;
+    let mut raw = Vec::with_capacity(expected);
+    ZlibDecoder::new(compressed.as_slice())
+        .take(expected as u64)
+        .read_to_end(&mut raw)
+        .context("Unable to decompress Image Data")?;
+    let rows = unfilter(&raw, stride, header.height, (bits_per_pixel / 8).max(1))?;
+
+    let mut pixels = Vec::with_capacity(header.width * header.height);
+    for row in rows.chunks(stride) {
+        for x in 0..header.width {
+            let sample =
+                |channel: usize| read_sample(row, x * channels + channel, header.bit_depth);
+
+            let pixel = match header.colour_type {
+                0 => {
+                    let value = scale_sample(sample(0), header.bit_depth);
+                    Icon::luma(value, value, value, 255)
+                }
+                2 => Icon::luma(sample(0), sample(1), sample(2), 255),
+                3 => {
+                    let index = sample(0) as usize;
+                    let colour = palette.get(index * 3..index * 3 + 3).unwrap_or(&[0, 0, 0]);
+                    let alpha = transparency.get(index).copied().unwrap_or(255);
+                    Icon::luma(colour[0], colour[1], colour[2], alpha)
+                }
+                4 => Icon::luma(sample(0), sample(0), sample(0), sample(1)),
+                _ => Icon::luma(sample(0), sample(1), sample(2), sample(3)),
+            };
+            pixels.push(pixel);
+        }
+    }
+
+    Ok(Icon {
+        width: header.width,
+        height: header.height,
+        pixels,
+    })
+}
+
+fn read_header(body: &[u8]) -> Result<Header> {
+    if body.len() != 13 {
+        bail!("Invalid IHDR Chunk");
+    }
+
+    let header = Header {
+        width: read_u32(body, 0)? as usize,
+        height: read_u32(body, 4)? as usize,
+        bit_depth: body[8],
+        colour_type: body[9],
+    };
+
+    if header.width == 0 || header.height == 0 {
+        bail!("Invalid Image Size ({}x{})", header.width, header.height);
+    }
+    if header.width > MAX_DIMENSION || header.height > MAX_DIMENSION {
+        bail!("Image is too large ({}x{})", header.width, header.height);
+    }
+    if body[12] != 0 {
+        bail!("Interlaced Images are not supported");
+    }
+    Ok(header)
+}
+
+/// Reverses the per-row filtering, returning the raw image rows
+fn unfilter(raw: &[u8], stride: usize, height: usize, bpp: usize) -> Result<Vec<u8>> {
+    if raw.len() < (stride + 1) * height {
+        bail!("Image Data is truncated");
+    }
+
+    let mut output = vec![0u8; stride * height];
+    for y in 0..height {
+        let filter = raw[y * (stride + 1)];
+        let line = &raw[y * (stride + 1) + 1..(y + 1) * (stride + 1)];
+
+        let (previous, current) = output.split_at_mut(y * stride);
+        let previous = if y == 0 {
+            None
+        } else {
+            Some(&previous[(y - 1) * stride..])
+        };
+        let current = &mut current[..stride];
+
+        for x in 0..stride {
+            let left = if x >= bpp { current[x - bpp] } else { 0 };
+            let up = previous.map_or(0, |row| row[x]);
+            let up_left = match previous {
+                Some(row) if x >= bpp => row[x - bpp],
+                _ => 0,
+            };
+
+            let predicted = match filter {
+                0 => 0,
+                1 => left,
+                2 => up,
+                3 => ((left as u16 + up as u16) / 2) as u8,
+                4 => paeth(left, up, up_left),
+                _ => bail!("Invalid Filter Type {}", filter),
+            };
+            current[x] = line[x].wrapping_add(predicted);
+        }
+    }
+    Ok(output)
+}
+
+fn paeth(left: u8, up: u8, up_left: u8) -> u8 {
+    let estimate = left as i16 + up as i16 - up_left as i16;
+    let distance_left = (estimate - left as i16).abs();
+    let distance_up = (estimate - up as i16).abs();
+    let distance_up_left = (estimate - up_left as i16).abs();
+
+    if distance_left <= distance_up && distance_left <= distance_up_left {
+        left
+    } else if distance_up <= distance_up_left {
+        up
+    } else {
+        up_left
+    }
+}
+
+/// Reads a single sample from a row, 16 bit samples are reduced to their high byte
+fn read_sample(row: &[u8], index: usize, bit_depth: u8) -> u8 {
+    match bit_depth {
+        8 => row[index],
+        16 => row[index * 2],
+        depth => {
+            let depth = depth as usize;
+            let bit = index * depth;
+            let shift = 8 - depth - (bit % 8);
+            (row[bit / 8] >> shift) & ((1 << depth) - 1)
+        }
+    }
+}
+
+/// Stretches a greyscale sample of less than 8 bits to the full range
+fn scale_sample(value: u8, bit_depth: u8) -> u8 {
+    match bit_depth {
+        1 | 2 | 4 => (value as u32 * 255 / ((1 << bit_depth) - 1)) as u8,
+        _ => value,
+    }
+}
+
+fn read_u32(data: &[u8], position: usize) -> Result<u32> {
+    let bytes = data
+        .get(position..position + 4)
+        .context("Unexpected End of File")?;
+    Ok(u32::from_be_bytes(bytes.try_into()?))
+}
@@ -0,0 +1,240 @@
+/*
+   Tests for the PNG and BMP decoders. The images are built by hand here, so we can produce the
+   odd (and broken) files that image editors won't.
+*/
+
+use std::io::Write;
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+
+use goxlr_scribbles::icon::decode_icon;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+// The decoder doesn't check the CRC, so it's left empty
+fn chunk(kind: &[u8], body: &[u8]) -> Vec<u8> {
+    let mut chunk = (body.len() as u32).to_be_bytes().to_vec();
+    chunk.extend_from_slice(kind);
+    chunk.extend_from_slice(body);
+    chunk.extend_from_slice(&[0; 4]);
+    chunk
+}
+
+fn header(width: u32, height: u32, bit_depth: u8, colour_type: u8) -> Vec<u8> {
+    let mut body = width.to_be_bytes().to_vec();
+    body.extend_from_slice(&height.to_be_bytes());
+    body.extend_from_slice(&[bit_depth, colour_type, 0, 0, 0]);
+    chunk(b"IHDR", &body)
+}
+
+fn compress(raw: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+    encoder.write_all(raw).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn png(header: Vec<u8>, chunks: &[Vec<u8>], raw: &[u8]) -> Vec<u8> {
+    let mut data = PNG_SIGNATURE.to_vec();
+    data.extend(header);
+    for extra in chunks {
+        data.extend_from_slice(extra);
+    }
+    data.extend(chunk(b"IDAT", &compress(raw)));
+    data.extend(chunk(b"IEND", &[]));
+    data
+}
+
+fn paeth(left: u8, up: u8, up_left: u8) -> u8 {
+    let estimate = left as i16 + up as i16 - up_left as i16;
+    let distances = [left, up, up_left].map(|value| (estimate - value as i16).abs());
+    if distances[0] <= distances[1] && distances[0] <= distances[2] {
+        left
+    } else if distances[1] <= distances[2] {
+        up
+    } else {
+        up_left
+    }
+}
+
+/// Filters each row of an image with the given filter type, the reverse of what the decoder does
+fn filter(rows: &[Vec<u8>], filter: u8, bpp: usize) -> Vec<u8> {
+    let mut raw = vec![];
+    for (y, row) in rows.iter().enumerate() {
+        raw.push(filter);
+        for x in 0..row.len() {
+            let left = if x >= bpp { row[x - bpp] } else { 0 };
+            let up = if y > 0 { rows[y - 1][x] } else { 0 };
+            let up_left = if y > 0 && x >= bpp {
+                rows[y - 1][x - bpp]
+            } else {
+                0
+            };
+
+            let predicted = match filter {
+                0 => 0,
+                1 => left,
+                2 => up,
+                3 => ((left as u16 + up as u16) / 2) as u8,
+                _ => paeth(left, up, up_left),
+            };
+            raw.push(row[x].wrapping_sub(predicted));
+        }
+    }
+    raw
+}
+
+#[test]
+fn png_filters() {
+    // Grey + Alpha, so each pixel is two bytes and 'left' isn't simply the previous byte
+    let rows = vec![
+        vec![10, 255, 200, 255, 30, 255],
+        vec![250, 255, 5, 255, 128, 255],
+        vec![64, 255, 65, 255, 66, 255],
+    ];
+    let expected = vec![10, 200, 30, 250, 5, 128, 64, 65, 66];
+
+    for filter_type in 0..=4 {
+        let data = png(header(3, 3, 8, 4), &[], &filter(&rows, filter_type, 2));
+        let icon = decode_icon(&data).unwrap();
+        assert_eq!((icon.width, icon.height), (3, 3));
+        assert_eq!(icon.pixels, expected, "Filter Type {}", filter_type);
+    }
+
+    // Anything beyond Paeth is invalid
+    let mut raw = filter(&rows, 0, 2);
+    raw[0] = 5;
+    assert!(decode_icon(&png(header(3, 3, 8, 4), &[], &raw)).is_err());
+}
+
+#[test]
+fn png_low_bit_depths() {
+    // 1 bit greyscale is stretched to the full range, rows are padded to a whole byte
+    let data = png(header(3, 1, 1, 0), &[], &[0, 0b1010_0000]);
+    assert_eq!(decode_icon(&data).unwrap().pixels, vec![255, 0, 255]);
+
+    // 2 bit paletted, with the second entry made fully transparent
+    let palette = chunk(b"PLTE", &[0, 0, 0, 255, 255, 255, 255, 255, 255]);
+    let transparency = chunk(b"tRNS", &[255, 0]);
+    let data = png(
+        header(3, 1, 2, 3),
+        &[palette, transparency],
+        &[0, 0b0001_1000],
+    );
+    assert_eq!(decode_icon(&data).unwrap().pixels, vec![0, 0, 255]);
+
+    // Paletted images need a palette
+    assert!(decode_icon(&png(header(3, 1, 2, 3), &[], &[0, 0])).is_err());
+}
+
+#[test]
+fn png_invalid_dimensions() {
+    // A zero width results in an empty stride, which should be refused rather than panic
+    for (width, height) in [(0, 1), (1, 0), (0, 0)] {
+        let data = png(header(width, height, 8, 0), &[], &[0]);
+        assert!(decode_icon(&data).is_err(), "{}x{}", width, height);
+    }
+
+    let data = png(header(4097, 1, 8, 0), &[], &[0; 4098]);
+    assert!(decode_icon(&data).is_err());
+}
+
+#[test]
+fn png_truncated() {
+    // The image data is a row short
+    let data = png(header(2, 2, 8, 0), &[], &[0, 1, 2]);
+    assert!(decode_icon(&data).is_err());
+
+    // The IDAT chunk claims to be longer than the file
+    let mut data = PNG_SIGNATURE.to_vec();
+    data.extend(header(1, 1, 8, 0));
+    data.extend_from_slice(&100_u32.to_be_bytes());
+    data.extend_from_slice(b"IDAT");
+    data.extend(compress(&[0, 0]));
+    assert!(decode_icon(&data).is_err());
+
+    // Chunks keep going without an IEND
+    let mut data = png(header(1, 1, 8, 0), &[], &[0, 0]);
+    data.truncate(data.len() - 12);
+    assert!(decode_icon(&data).is_err());
+
+    // Invalid compressed data
+    let mut data = PNG_SIGNATURE.to_vec();
+    data.extend(header(1, 1, 8, 0));
+    data.extend(chunk(b"IDAT", &[1, 2, 3, 4]));
+    data.extend(chunk(b"IEND", &[]));
+    assert!(decode_icon(&data).is_err());
+}
+
+#[test]
+fn png_oversized_data() {
+    // A 1x1 image whose data inflates to 16MB, only the single row should be read out of it
+    let mut raw = vec![0, 200];
+    raw.resize(16 * 1024 * 1024, 0);
+    let data = png(header(1, 1, 8, 0), &[], &raw);
+    assert!(data.len() < 64 * 1024);
+
+    let icon = decode_icon(&data).unwrap();
+    assert_eq!((icon.width, icon.height), (1, 1));
+    assert_eq!(icon.pixels, vec![200]);
+}
+
+/// Builds an uncompressed 24 bit BMP from greyscale rows, a negative height stores the rows
+/// top to bottom rather than bottom to top.
+fn bmp(width: i32, height: i32, rows: &[Vec<u8>]) -> Vec<u8> {
+    let mut pixels = vec![];
+    for row in rows {
+        let mut line: Vec<u8> = row.iter().flat_map(|grey| [*grey; 3]).collect();
+        line.resize(line.len().div_ceil(4) * 4, 0);
+        pixels.extend(line);
+    }
+
+    let mut data = b"BM".to_vec();
+    data.extend_from_slice(&(54 + pixels.len() as u32).to_le_bytes());
+    data.extend_from_slice(&[0; 4]);
+    data.extend_from_slice(&54_u32.to_le_bytes());
+
+    data.extend_from_slice(&40_u32.to_le_bytes());
+    data.extend_from_slice(&width.to_le_bytes());
+    data.extend_from_slice(&height.to_le_bytes());
+    data.extend_from_slice(&1_u16.to_le_bytes());
+    data.extend_from_slice(&24_u16.to_le_bytes());
+    data.extend_from_slice(&[0; 24]);
+
+    data.extend(pixels);
+    data
+}
+
+#[test]
+fn bmp_row_order() {
+    let top = vec![10, 20, 30];
+    let bottom = vec![40, 50, 60];
+    let expected = vec![10, 20, 30, 40, 50, 60];
+
+    // Normally rows are stored from the bottom up..
+    let data = bmp(3, 2, &[bottom.clone(), top.clone()]);
+    let icon = decode_icon(&data).unwrap();
+    assert_eq!((icon.width, icon.height), (3, 2));
+    assert_eq!(icon.pixels, expected);
+
+    // ..but a negative height flips that around
+    let data = bmp(3, -2, &[top, bottom]);
+    let icon = decode_icon(&data).unwrap();
+    assert_eq!((icon.width, icon.height), (3, 2));
+    assert_eq!(icon.pixels, expected);
+}
+
+#[test]
+fn bmp_invalid() {
+    for (width, height) in [(0, 1), (1, 0), (-1, 1), (4097, 1), (1, -4097)] {
+        let data = bmp(width, height, &[vec![0]]);
+        assert!(decode_icon(&data).is_err(), "{}x{}", width, height);
+    }
+
+    // The last row is missing
+    let data = bmp(2, 2, &[vec![0, 0]]);
+    assert!(decode_icon(&data).is_err());
+
+    // So is most of the header
+    assert!(decode_icon(b"BM\0\0\0\0").is_err());
+}
@@ -0,0 +1,139 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use goxlr_scribbles::canvas::{Canvas, HEIGHT, WIDTH};
+use goxlr_scribbles::icon::{decode_icon, load_icon, Icon};
+use goxlr_scribbles::{get_scribble, render};
+
+/*
+    Rendered scribbles are compared against golden images in fixtures/scribbles, these are
+    stored as text (one character per pixel) so any differences are easy to see. If a change to
+    the rendering is intentional, run the tests with UPDATE_GOLDEN=1 to regenerate them.
+*/
+
+fn fixture(path: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(path)
+}
+
+fn icon(name: &str) -> Result<Icon> {
+    load_icon(&fixture(&format!("icons/{}", name)))
+}
+
+/// Converts the bytes sent to the GoXLR back into text, 8 bytes per column, top to bottom
+fn to_text(bytes: &[u8]) -> String {
+    let mut text = String::new();
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let lit = bytes[x * (HEIGHT / 8) + y / 8] & (1 << (y % 8)) != 0;
+            text.push(if lit { '#' } else { '.' });
+        }
+        text.push('\n');
+    }
+    text
+}
+
+fn check_golden(name: &str, canvas: &Canvas) -> Result<()> {
+    let path = fixture(&format!("scribbles/{}.txt", name));
+    let rendered = to_text(&canvas.to_bytes());
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(&path, &rendered)?;
+    }
+
+    let golden = std::fs::read_to_string(&path)?;
+    assert_eq!(rendered, golden, "Scribble '{}' doesn't match", name);
+    Ok(())
+}
+
+#[test]
+fn byte_layout() {
+    let mut canvas = Canvas::default();
+    canvas.set(0, 0, true);
+    canvas.set(0, 9, true);
+    canvas.set(1, 63, true);
+
+    let bytes = canvas.to_bytes();
+    assert_eq!(bytes[0], 0b0000_0001);
+    assert_eq!(bytes[1], 0b0000_0010);
+    assert_eq!(bytes[15], 0b1000_0000);
+    assert_eq!(bytes.iter().filter(|&&byte| byte != 0).count(), 3);
+}
+
+#[test]
+fn text_is_sized_to_fit() -> Result<()> {
+    check_golden("text_short", &render(None, Some("Mic"), None, false))?;
+    check_golden("text_long", &render(None, Some("Microphone"), None, false))?;
+
+    // Text too long for the smallest size is truncated, unknown characters show as '?'
+    let text = "The quick brown fox jumps over the lazy dog";
+    check_golden("text_truncated", &render(None, Some(text), None, false))?;
+    check_golden("text_unknown", &render(None, Some("Café"), None, false))
+}
+
+#[test]
+fn labels_and_inversion() -> Result<()> {
+    check_golden("label", &render(None, Some("Music"), Some("1"), false))?;
+    check_golden("inverted", &render(None, Some("Music"), Some("1"), true))
+}
+
+#[test]
+fn icons() -> Result<()> {
+    let mic = icon("mic.png")?;
+    check_golden("icon", &render(Some(&mic), None, None, false))?;
+    check_golden("icon_text", &render(Some(&mic), Some("Mic"), Some("A"), false))?;
+
+    // A gradient should be dithered, rather than just cut off at a threshold
+    let gradient = icon("gradient.png")?;
+    check_golden("icon_gradient", &render(Some(&gradient), None, None, false))?;
+
+    let palette = icon("palette.png")?;
+    check_golden("icon_palette", &render(Some(&palette), Some("Pal"), None, true))?;
+
+    let mono = icon("mono.bmp")?;
+    check_golden("icon_mono", &render(Some(&mono), Some("Mono"), None, false))
+}
+
+#[test]
+fn png_and_bmp_decode_the_same() -> Result<()> {
+    let png = icon("mic.png")?;
+    let bmp = icon("mic.bmp")?;
+    assert_eq!((png.width, png.height), (48, 48));
+    assert_eq!(png, bmp);
+
+    // The 2 bit palette has a transparent entry, which should come out as black
+    let palette = icon("palette.png")?;
+    assert_eq!((palette.width, palette.height), (16, 16));
+    assert_eq!(palette.pixels[0], 0);
+    assert_eq!(palette.pixels[12], 0);
+    assert_eq!(palette.pixels[4], 76);
+    Ok(())
+}
+
+#[test]
+fn invalid_icons() -> Result<()> {
+    assert!(decode_icon(b"GIF89a").is_err());
+    assert!(decode_icon(&[]).is_err());
+
+    let png = std::fs::read(fixture("icons/mic.png"))?;
+    assert!(decode_icon(&png[..png.len() / 2]).is_err());
+
+    // Flip the interlace flag in the header, which we don't support
+    let mut interlaced = png.clone();
+    interlaced[28] = 1;
+    assert!(decode_icon(&interlaced).is_err());
+
+    let bmp = std::fs::read(fixture("icons/mic.bmp"))?;
+    assert!(decode_icon(&bmp[..bmp.len() - 10]).is_err());
+    Ok(())
+}
+
+#[test]
+fn missing_icons_are_skipped() {
+    let missing = Some(fixture("icons/missing.png"));
+    let scribble = get_scribble(missing, Some(String::from("Mic")), None, false);
+    assert_eq!(scribble, render(None, Some("Mic"), None, false).to_bytes());
+}
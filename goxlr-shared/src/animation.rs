@@ -0,0 +1,34 @@
+#[cfg(feature = "clap")]
+use clap::ValueEnum;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+/// The lighting animations supported by the GoXLR, these are only available on newer firmware
+/// versions (see GoXLRFeature::Animation).
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "clap", derive(ValueEnum))]
+pub enum AnimationMode {
+    RetroRainbow,
+    RainbowDark,
+    RainbowBright,
+    Simple,
+    Ripple,
+
+    /// No animation, the configured colours are displayed as-is
+    #[default]
+    None,
+}
+
+/// The direction the animation 'flows' down the faders
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "clap", derive(ValueEnum))]
+pub enum WaterfallDirection {
+    Down,
+    Up,
+
+    #[default]
+    Off,
+}
@@ -1,11 +1,15 @@
+use crate::channels::CanFrom;
 use crate::colours::TwoColourTargets;
 use crate::faders::Fader;
 use crate::interaction::InteractiveButtons;
+#[cfg(feature = "clap")]
+use clap::ValueEnum;
 use enum_map::Enum;
 use serde::{Deserialize, Serialize};
 use strum::EnumIter;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Enum, EnumIter)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Buttons {
     // Fader Mute Buttons
     FaderA,
@@ -116,8 +120,104 @@ impl From<TwoColourTargets> for Buttons {
     }
 }
 
+/// Buttons whose colours aren't attached to a channel or mic setting, and are configured
+/// directly in the profile's lighting
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Enum, EnumIter, Serialize, Deserialize)]
+#[cfg_attr(feature = "clap", derive(ValueEnum))]
+pub enum LightingButtons {
+    // FX Buttons
+    EffectSelect1,
+    EffectSelect2,
+    EffectSelect3,
+    EffectSelect4,
+    EffectSelect5,
+    EffectSelect6,
+
+    EffectFx,
+    EffectMegaphone,
+    EffectRobot,
+    EffectHardTune,
+
+    // Sampler Buttons
+    SamplerSelectA,
+    SamplerSelectB,
+    SamplerSelectC,
+
+    SamplerTopLeft,
+    SamplerTopRight,
+    SamplerBottomLeft,
+    SamplerBottomRight,
+    SamplerClear,
+}
+
+impl From<LightingButtons> for Buttons {
+    fn from(value: LightingButtons) -> Self {
+        match value {
+            LightingButtons::EffectSelect1 => Buttons::EffectSelect1,
+            LightingButtons::EffectSelect2 => Buttons::EffectSelect2,
+            LightingButtons::EffectSelect3 => Buttons::EffectSelect3,
+            LightingButtons::EffectSelect4 => Buttons::EffectSelect4,
+            LightingButtons::EffectSelect5 => Buttons::EffectSelect5,
+            LightingButtons::EffectSelect6 => Buttons::EffectSelect6,
+            LightingButtons::EffectFx => Buttons::EffectFx,
+            LightingButtons::EffectMegaphone => Buttons::EffectMegaphone,
+            LightingButtons::EffectRobot => Buttons::EffectRobot,
+            LightingButtons::EffectHardTune => Buttons::EffectHardTune,
+            LightingButtons::SamplerSelectA => Buttons::SamplerSelectA,
+            LightingButtons::SamplerSelectB => Buttons::SamplerSelectB,
+            LightingButtons::SamplerSelectC => Buttons::SamplerSelectC,
+            LightingButtons::SamplerTopLeft => Buttons::SamplerTopLeft,
+            LightingButtons::SamplerTopRight => Buttons::SamplerTopRight,
+            LightingButtons::SamplerBottomLeft => Buttons::SamplerBottomLeft,
+            LightingButtons::SamplerBottomRight => Buttons::SamplerBottomRight,
+            LightingButtons::SamplerClear => Buttons::SamplerClear,
+        }
+    }
+}
+
+impl CanFrom<Buttons> for LightingButtons {
+    fn can_from(value: Buttons) -> bool {
+        !matches!(
+            value,
+            Buttons::FaderA
+                | Buttons::FaderB
+                | Buttons::FaderC
+                | Buttons::FaderD
+                | Buttons::Swear
+                | Buttons::CoughButton
+        )
+    }
+}
+
+impl From<Buttons> for LightingButtons {
+    fn from(value: Buttons) -> Self {
+        match value {
+            Buttons::EffectSelect1 => LightingButtons::EffectSelect1,
+            Buttons::EffectSelect2 => LightingButtons::EffectSelect2,
+            Buttons::EffectSelect3 => LightingButtons::EffectSelect3,
+            Buttons::EffectSelect4 => LightingButtons::EffectSelect4,
+            Buttons::EffectSelect5 => LightingButtons::EffectSelect5,
+            Buttons::EffectSelect6 => LightingButtons::EffectSelect6,
+            Buttons::EffectFx => LightingButtons::EffectFx,
+            Buttons::EffectMegaphone => LightingButtons::EffectMegaphone,
+            Buttons::EffectRobot => LightingButtons::EffectRobot,
+            Buttons::EffectHardTune => LightingButtons::EffectHardTune,
+            Buttons::SamplerSelectA => LightingButtons::SamplerSelectA,
+            Buttons::SamplerSelectB => LightingButtons::SamplerSelectB,
+            Buttons::SamplerSelectC => LightingButtons::SamplerSelectC,
+            Buttons::SamplerTopLeft => LightingButtons::SamplerTopLeft,
+            Buttons::SamplerTopRight => LightingButtons::SamplerTopRight,
+            Buttons::SamplerBottomLeft => LightingButtons::SamplerBottomLeft,
+            Buttons::SamplerBottomRight => LightingButtons::SamplerBottomRight,
+            Buttons::SamplerClear => LightingButtons::SamplerClear,
+            _ => panic!("Attempted to Lookup Lighting on a Mute or Mic button!"),
+        }
+    }
+}
+
 /// Defines potential inactive button behaviours
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "clap", derive(ValueEnum))]
 pub enum InactiveButtonBehaviour {
     /// This Dimms the Active Colour.
     DimActive,
@@ -54,3 +54,18 @@ impl From<FaderChannels> for InputChannels {
         }
     }
 }
+
+impl From<InputChannels> for FaderChannels {
+    fn from(value: InputChannels) -> Self {
+        match value {
+            InputChannels::Microphone => FaderChannels::Microphone,
+            InputChannels::Chat => FaderChannels::Chat,
+            InputChannels::Music => FaderChannels::Music,
+            InputChannels::Game => FaderChannels::Game,
+            InputChannels::Console => FaderChannels::Console,
+            InputChannels::LineIn => FaderChannels::LineIn,
+            InputChannels::System => FaderChannels::System,
+            InputChannels::Sample => FaderChannels::Sample,
+        }
+    }
+}
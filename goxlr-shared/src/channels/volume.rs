@@ -53,6 +53,30 @@ impl From<FaderChannels> for VolumeChannels {
     }
 }
 
+impl CanFrom<VolumeChannels> for FaderChannels {
+    fn can_from(value: VolumeChannels) -> bool {
+        value != VolumeChannels::MicrophoneMonitor
+    }
+}
+
+impl From<VolumeChannels> for FaderChannels {
+    fn from(value: VolumeChannels) -> Self {
+        match value {
+            VolumeChannels::Microphone => FaderChannels::Microphone,
+            VolumeChannels::Chat => FaderChannels::Chat,
+            VolumeChannels::Music => FaderChannels::Music,
+            VolumeChannels::Game => FaderChannels::Game,
+            VolumeChannels::Console => FaderChannels::Console,
+            VolumeChannels::LineIn => FaderChannels::LineIn,
+            VolumeChannels::System => FaderChannels::System,
+            VolumeChannels::Sample => FaderChannels::Sample,
+            VolumeChannels::Headphones => FaderChannels::Headphones,
+            VolumeChannels::LineOut => FaderChannels::LineOut,
+            other => panic!("Cannot Cast from {:?} to FaderChannel", other),
+        }
+    }
+}
+
 impl CanFrom<SubMixChannels> for VolumeChannels {
     fn can_from(_: SubMixChannels) -> bool {
         true
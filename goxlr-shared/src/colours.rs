@@ -3,6 +3,7 @@
  * building the colour array. Instead this struct can be built, stored, and altered and will
  * produce the correct output.
  */
+use std::str::FromStr;
 use strum::EnumIter;
 
 use crate::buttons::Buttons;
@@ -24,7 +25,7 @@ const SAMPLE_BUTTON_COUNT: usize = 5;
 const FX_BUTTON_COUNT: usize = 4;
 const MIC_BUTTON_COUNT: usize = 2;
 
-#[derive(Default, Debug, Copy, Clone)]
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
 pub struct ColourScheme {
     pub is_legacy: bool,
 
@@ -124,13 +125,36 @@ impl Colour {
     }
 }
 
+/// Parses a colour from a hex string, in the format 'RRGGBB' (with an optional leading '#')
+impl FromStr for Colour {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let hex = value.strip_prefix('#').unwrap_or(value);
+        if hex.len() != 6 || !hex.is_ascii() {
+            return Err(format!("Invalid Colour '{}', expected RRGGBB", value));
+        }
+
+        let component = |index: usize| {
+            u32::from_str_radix(&hex[index..index + 2], 16)
+                .map_err(|_| format!("Invalid Colour '{}', expected RRGGBB", value))
+        };
+
+        Ok(Colour {
+            red: component(0)?,
+            green: component(2)?,
+            blue: component(4)?,
+        })
+    }
+}
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Default, Debug, Copy, Clone)]
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
 pub struct OneColour {
     pub colour1: Colour,
 }
 
-#[derive(Default, Debug, Copy, Clone)]
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TwoColour {
     pub colour1: Colour,
@@ -144,7 +168,7 @@ impl TwoColour {
     }
 }
 
-#[derive(Default, Debug, Copy, Clone)]
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ThreeColour {
     pub left: Colour,
@@ -154,7 +178,7 @@ pub struct ThreeColour {
 
 /// FaderColour lives separately, as it has different behaviours depending on the firmware
 /// version. While we won't see them here, they'll be handled in the USB crate.
-#[derive(Default, Debug, Copy, Clone)]
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FaderColour {
     pub colour1: Colour,
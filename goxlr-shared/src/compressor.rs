@@ -4,7 +4,9 @@ use clap::ValueEnum;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Copy, Clone)]
+use strum::EnumIter;
+
+#[derive(Debug, Copy, Clone, EnumIter)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "clap", derive(ValueEnum))]
 pub enum CompressorAttackTime {
@@ -31,7 +33,7 @@ pub enum CompressorAttackTime {
     Attack40ms,
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, EnumIter)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "clap", derive(ValueEnum))]
 pub enum CompressorReleaseTime {
@@ -58,7 +60,7 @@ pub enum CompressorReleaseTime {
     Release3000ms,
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, EnumIter)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "clap", derive(ValueEnum))]
 pub enum CompressorRatio {
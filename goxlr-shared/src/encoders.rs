@@ -1,10 +1,15 @@
 use crate::interaction::InteractiveEncoders;
+#[cfg(feature = "clap")]
+use clap::ValueEnum;
+use enum_map::Enum;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use strum::EnumIter;
 
 /// A simple list of the 4 encoders
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Enum, EnumIter)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "clap", derive(ValueEnum))]
 pub enum Encoders {
     Pitch,
     Gender,
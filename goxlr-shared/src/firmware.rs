@@ -0,0 +1,52 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The stages of a firmware update, in the order they're performed
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FirmwareUpdateStage {
+    Start,
+    Erase,
+    Upload,
+    Validate,
+    Verify,
+    Finalise,
+    Reboot,
+    Complete,
+    Failed,
+}
+
+/// The current state of a firmware update, progress is the percentage through the current stage
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FirmwareUpdateStatus {
+    pub stage: FirmwareUpdateStage,
+    pub progress: u8,
+    pub error: Option<String>,
+}
+
+impl FirmwareUpdateStatus {
+    pub fn new(stage: FirmwareUpdateStage, progress: u8) -> Self {
+        Self {
+            stage,
+            progress,
+            error: None,
+        }
+    }
+
+    pub fn failed(error: String) -> Self {
+        Self {
+            stage: FirmwareUpdateStage::Failed,
+            progress: 0,
+            error: Some(error),
+        }
+    }
+
+    /// Returns true if the update has finished, either successfully or not
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self.stage,
+            FirmwareUpdateStage::Complete | FirmwareUpdateStage::Failed
+        )
+    }
+}
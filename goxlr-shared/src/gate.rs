@@ -4,7 +4,9 @@ use clap::ValueEnum;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Copy, Clone)]
+use strum::EnumIter;
+
+#[derive(Debug, Copy, Clone, EnumIter)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "clap", derive(ValueEnum))]
 pub enum GateTimes {
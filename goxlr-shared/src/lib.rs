@@ -1,3 +1,4 @@
+pub mod animation;
 pub mod buttons;
 pub mod channels;
 pub mod colours;
@@ -6,6 +7,7 @@ pub mod device;
 pub mod encoders;
 pub mod eq_frequencies;
 pub mod faders;
+pub mod firmware;
 pub mod gate;
 pub mod interaction;
 pub mod microphone;
@@ -14,13 +14,18 @@ pub struct RoutingTable {
 impl RoutingTable {
     pub fn set_routing(&mut self, input: InputChannels, output: RoutingOutput, value: RouteValue) {
         // This format isn't supported, so do nothing.
-        if output == RoutingOutput::ChatMic && input == InputChannels::Chat {
+        if !Self::is_supported(input, output) {
             return;
         }
 
         self.table[input][output] = value;
     }
 
+    /// The GoXLR can't route the Chat channel back to the Chat Mic
+    pub fn is_supported(input: InputChannels, output: RoutingOutput) -> bool {
+        !(output == RoutingOutput::ChatMic && input == InputChannels::Chat)
+    }
+
     pub fn get_routing(&self, input: InputChannels, output: RoutingOutput) -> RouteValue {
         self.table[input][output]
     }
@@ -1,3 +1,9 @@
+#[cfg(feature = "clap")]
+use clap::ValueEnum;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
 use crate::faders::Fader;
 
 pub enum Scribble {
@@ -17,3 +23,30 @@ impl From<Fader> for Scribble {
         }
     }
 }
+
+/// What's displayed as the text on a channel's Scribble, anything other than Text is kept up
+/// to date by the daemon as the channel changes.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "clap", derive(ValueEnum))]
+pub enum ScribbleContent {
+    /// The text configured for the screen
+    #[default]
+    Text,
+
+    /// The channel's volume, as a percentage
+    VolumePercent,
+
+    /// The channel's volume in decibels, this is an approximation as the GoXLR's fader curve
+    /// isn't documented (see the daemon's scribbles component)
+    VolumeDecibels,
+
+    /// A badge when the channel is muted, falling back to the configured text when it's not
+    MuteState,
+
+    /// Text pushed to the daemon at runtime, falling back to the configured text if there's none
+    Custom,
+
+    /// The current local time, as HH:MM
+    Clock,
+}
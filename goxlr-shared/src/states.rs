@@ -2,7 +2,7 @@ use enum_map::EnumMap;
 
 use crate::buttons::{Buttons, InactiveButtonBehaviour};
 
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
 pub struct ButtonDisplayStates {
     states: EnumMap<Buttons, State>,
 }
@@ -19,7 +19,7 @@ impl ButtonDisplayStates {
     }
 }
 
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
 pub enum State {
     Colour1,
     Colour2,
@@ -3,11 +3,14 @@
 use enum_map::Enum;
 use strum::EnumIter;
 
+#[cfg(feature = "clap")]
+use clap::ValueEnum;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Enum, EnumIter)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "clap", derive(ValueEnum))]
 pub enum Mix {
     #[default]
     A,
@@ -0,0 +1,496 @@
+/*
+   A harness for running the daemon end to end. Each TestDaemon runs the full daemon (device
+   manager, IPC and HTTP servers) in-process, with its own settings, profile directory and socket,
+   and attaches a Virtual GoXLR for it to find.
+
+   As the virtual devices are global to the process, and every daemon will pick up every attached
+   device, only one TestDaemon can run at a time. This is enforced by a lock held for the lifetime
+   of the daemon, so tests inside a single file will simply queue behind each other.
+*/
+
+use std::collections::VecDeque;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use futures::{SinkExt, StreamExt};
+use interprocess::local_socket::tokio::LocalSocketStream;
+use json_patch::Patch;
+use tempfile::TempDir;
+use tokio::net::TcpStream;
+use tokio::sync::{Mutex, MutexGuard};
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use goxlr_daemon::cli::Cli;
+use goxlr_daemon::settings::SettingsHandle;
+use goxlr_daemon::{run_daemon, Stop};
+use goxlr_ipc::client::Client;
+use goxlr_ipc::clients::ipc::ipc_client::IPCClient;
+use goxlr_ipc::clients::ipc::ipc_socket::Socket;
+use goxlr_ipc::clients::web::web_client::WebClient;
+use goxlr_ipc::commands::channels::{ChannelCommands, ChannelVolume, MuteCommand};
+use goxlr_ipc::commands::{
+    DaemonRequest, DaemonResponse, DaemonStatus, GoXLRCommand, IPCRequest, IPCResponse,
+    WebsocketRequest, WebsocketResponse,
+};
+use goxlr_ipc::status::{DeviceStatus, InteractionEvent, MicLevelMeter};
+use goxlr_profile::Profile;
+use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::channels::volume::VolumeChannels;
+use goxlr_shared::device::DeviceType;
+use goxlr_shared::firmware::FirmwareUpdateStatus;
+use goxlr_shared::mute::MuteState;
+use goxlr_usb::capture::{self, Capture};
+use goxlr_usb::virtual_device::{self, CommandRecord, VirtualController};
+
+// How long to wait for something to happen before giving up
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// How long the device needs to be idle before we consider a set of commands 'complete'
+const SETTLE_TIME: Duration = Duration::from_millis(150);
+
+static LOCK: Mutex<()> = Mutex::const_new(());
+static SERIAL: AtomicUsize = AtomicUsize::new(0);
+
+/// Configuration for a TestDaemon, by default a Full Virtual GoXLR with the default profile
+#[derive(Default)]
+pub struct TestOptions {
+    pub device_type: Option<DeviceType>,
+
+    /// Used as the Default profile in place of the built-in defaults
+    pub profile: Option<Profile>,
+
+    /// Enables USB capture to this directory
+    pub capture_directory: Option<PathBuf>,
+
+    /// Replay this capture instead of attaching a Virtual GoXLR
+    pub replay: Option<PathBuf>,
+}
+
+pub struct TestDaemon {
+    serial: String,
+    device: Option<VirtualController>,
+    replay: Option<PathBuf>,
+
+    /// The commands sent to the device while it was being initialised and its profile loaded
+    pub startup: Vec<CommandRecord>,
+
+    socket_path: String,
+    http_port: u16,
+    shutdown: Stop,
+    task: Option<JoinHandle<Result<()>>>,
+
+    // These need to live as long as the daemon
+    _directory: TempDir,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl TestDaemon {
+    /// Starts a daemon with a Full GoXLR using the default profile
+    pub async fn start() -> Result<Self> {
+        Self::start_with(TestOptions::default()).await
+    }
+
+    /// Starts a daemon with a Full GoXLR, using the provided profile as the Default
+    pub async fn start_with_profile(profile: Profile) -> Result<Self> {
+        let options = TestOptions {
+            profile: Some(profile),
+            ..Default::default()
+        };
+        Self::start_with(options).await
+    }
+
+    pub async fn start_with(options: TestOptions) -> Result<Self> {
+        let lock = LOCK.lock().await;
+        let directory = tempfile::tempdir()?;
+
+        let profile_directory = directory.path().join("profiles");
+        if let Some(profile) = options.profile {
+            let path = profile_directory.join("profiles");
+            std::fs::create_dir_all(&path)?;
+            std::fs::write(path.join("Default.json"), serde_json::to_vec(&profile)?)?;
+        }
+
+        let socket_path = directory.path().join("goxlr.socket");
+        let socket_path = socket_path.to_string_lossy().to_string();
+        let http_port = free_port()?;
+
+        let cli = Cli {
+            config: Some(directory.path().join("settings.json")),
+            http_bind_address: Some(String::from("127.0.0.1")),
+            http_port: Some(http_port),
+            socket_path: Some(socket_path.clone()),
+            profile_directory: Some(profile_directory),
+            capture_directory: options.capture_directory,
+            ..Default::default()
+        };
+        let settings = SettingsHandle::load(cli)?;
+
+        let shutdown = Stop::new();
+        let task = tokio::spawn(run_daemon(settings, shutdown.clone()));
+
+        let (serial, device) = match &options.replay {
+            Some(path) => {
+                capture::attach_replay(path)?;
+                (capture_serial(&Capture::load(path)?)?, None)
+            }
+            None => {
+                let serial = format!("TEST{}X{}", std::process::id(), next_serial());
+                let device_type = options.device_type.unwrap_or(DeviceType::Full);
+                let device = virtual_device::attach(&serial, device_type);
+                (serial, Some(device))
+            }
+        };
+
+        let mut daemon = Self {
+            serial,
+            device,
+            replay: options.replay,
+            startup: vec![],
+            socket_path,
+            http_port,
+            shutdown,
+            task: Some(task),
+            _directory: directory,
+            _lock: lock,
+        };
+
+        // Wait for the device to appear, and for the profile to finish loading..
+        daemon.startup = daemon.wait_for_device().await?;
+        Ok(daemon)
+    }
+
+    pub fn serial(&self) -> &str {
+        &self.serial
+    }
+
+    /// The Virtual GoXLR attached to this daemon, panics when replaying a capture
+    pub fn device(&self) -> &VirtualController {
+        self.device
+            .as_ref()
+            .expect("Daemon is not running a Virtual Device")
+    }
+
+    pub async fn ipc_client(&self) -> Result<IPCClient> {
+        let start = Instant::now();
+        loop {
+            match IPCClient::connect_to(&self.socket_path).await {
+                Ok(client) => return Ok(client),
+                Err(error) if start.elapsed() > TIMEOUT => return Err(error),
+                Err(_) => sleep(Duration::from_millis(20)).await,
+            }
+        }
+    }
+
+    /// A bare connection to the IPC Socket, for sending requests in a form the client doesn't
+    pub async fn ipc_socket(&self) -> Result<Socket<IPCResponse, IPCRequest>> {
+        let stream = LocalSocketStream::connect(self.socket_path.as_str()).await?;
+        Ok(Socket::new(stream))
+    }
+
+    pub fn http_port(&self) -> u16 {
+        self.http_port
+    }
+
+    /// The daemon's settings file, the HTTP address and paths are overridden on the command line
+    pub fn settings_path(&self) -> PathBuf {
+        self._directory.path().join("settings.json")
+    }
+
+    pub fn web_client(&self) -> Result<WebClient> {
+        WebClient::connect(format!("http://127.0.0.1:{}/api/command", self.http_port))
+    }
+
+    pub async fn websocket(&self) -> Result<TestWebsocket> {
+        let url = format!("ws://127.0.0.1:{}/api/websocket", self.http_port);
+        let (stream, _) = tokio_tungstenite::connect_async(url).await?;
+        Ok(TestWebsocket {
+            stream,
+            patches: VecDeque::new(),
+            mic_levels: VecDeque::new(),
+            interactions: VecDeque::new(),
+            next_id: 0,
+        })
+    }
+
+    /// Fetches the status of our device from the daemon
+    pub async fn status(&self) -> Result<DeviceStatus> {
+        let mut client = self.ipc_client().await?;
+        client.poll_status().await?;
+        match client.status().devices.get(self.serial()) {
+            Some(status) => Ok(status.clone()),
+            None => bail!("Device {} not present in status", self.serial()),
+        }
+    }
+
+    /// Waits until the device is present in the status, and has stopped receiving commands
+    pub async fn wait_for_device(&self) -> Result<Vec<CommandRecord>> {
+        let start = Instant::now();
+        while self.status().await.is_err() {
+            if start.elapsed() > TIMEOUT {
+                bail!("Timeout waiting for device {}", self.serial());
+            }
+            sleep(Duration::from_millis(50)).await;
+        }
+        Ok(self.settle().await)
+    }
+
+    /// Waits until the device has been idle for a short while, and returns all the commands sent
+    /// to it since the last call.
+    pub async fn settle(&self) -> Vec<CommandRecord> {
+        let Some(device) = &self.device else {
+            return vec![];
+        };
+
+        let start = Instant::now();
+        let mut commands = vec![];
+        let mut last_command = Instant::now();
+
+        while last_command.elapsed() < SETTLE_TIME && start.elapsed() < TIMEOUT {
+            sleep(Duration::from_millis(20)).await;
+
+            let received = device.take_commands();
+            if !received.is_empty() {
+                commands.extend(received);
+                last_command = Instant::now();
+            }
+        }
+        commands
+    }
+
+    /// Collects commands until one with the given id has been received and the device has gone
+    /// quiet, used when waiting on things which take a while (such as error recovery).
+    pub async fn wait_for_command(&self, command: u32) -> Result<Vec<CommandRecord>> {
+        let start = Instant::now();
+        let mut commands = vec![];
+        while !commands
+            .iter()
+            .any(|c: &CommandRecord| c.command == command)
+        {
+            if start.elapsed() > TIMEOUT {
+                bail!("Timeout waiting for command {:#x}", command);
+            }
+            sleep(Duration::from_millis(20)).await;
+            commands.extend(self.device().take_commands());
+        }
+        commands.extend(self.settle().await);
+        Ok(commands)
+    }
+
+    /// Waits for a firmware update on our device to either complete or fail. Once an update
+    /// command has been accepted, any result from a previous update is no longer reported.
+    pub async fn wait_for_firmware_update(&self) -> Result<FirmwareUpdateStatus> {
+        let start = Instant::now();
+        let mut client = self.ipc_client().await?;
+        loop {
+            client.poll_status().await?;
+            if let Some(status) = client.status().firmware_updates.get(self.serial()) {
+                if status.is_finished() {
+                    return Ok(status.clone());
+                }
+            }
+            if start.elapsed() > TIMEOUT {
+                bail!("Timeout waiting for the firmware update");
+            }
+            sleep(Duration::from_millis(50)).await;
+        }
+    }
+
+    /// Unplugs the device and shuts down the daemon
+    pub async fn stop(mut self) -> Result<()> {
+        self.detach();
+        self.shutdown.trigger();
+
+        let Some(task) = self.task.take() else {
+            return Ok(());
+        };
+        tokio::time::timeout(TIMEOUT, task)
+            .await
+            .context("Timeout waiting for the daemon to stop")??
+    }
+}
+
+impl TestDaemon {
+    fn detach(&self) {
+        match &self.replay {
+            Some(path) => capture::detach_replay(path),
+            None => virtual_device::detach(self.serial()),
+        }
+    }
+}
+
+impl Drop for TestDaemon {
+    fn drop(&mut self) {
+        // If a test fails before calling stop, make sure the daemon and device don't leak into
+        // the next test.
+        if self.task.is_some() {
+            self.detach();
+            self.shutdown.trigger();
+        }
+    }
+}
+
+pub struct TestWebsocket {
+    stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    patches: VecDeque<Patch>,
+    mic_levels: VecDeque<MicLevelMeter>,
+    interactions: VecDeque<InteractionEvent>,
+    next_id: u64,
+}
+
+impl TestWebsocket {
+    /// Sends a request, and waits for its response. Any events received while waiting are kept
+    /// for `next_patch`, `next_mic_level` and `next_interaction`.
+    pub async fn request(&mut self, data: DaemonRequest) -> Result<DaemonResponse> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let request = serde_json::to_string(&WebsocketRequest { id, data })?;
+        self.stream.send(Message::Text(request)).await?;
+
+        loop {
+            let response = self.next().await?;
+            if response.id == id {
+                return Ok(response.data);
+            }
+            match response.data {
+                DaemonResponse::Patch(patch) => self.patches.push_back(patch),
+                DaemonResponse::MicLevel(meter) => self.mic_levels.push_back(meter),
+                DaemonResponse::Interaction(event) => self.interactions.push_back(event),
+                _ => {}
+            }
+        }
+    }
+
+    /// Waits for the next Patch pushed by the daemon
+    pub async fn next_patch(&mut self) -> Result<Patch> {
+        if let Some(patch) = self.patches.pop_front() {
+            return Ok(patch);
+        }
+        loop {
+            let response = self.next().await?;
+            if let DaemonResponse::Patch(patch) = response.data {
+                return Ok(patch);
+            }
+        }
+    }
+
+    /// Waits for the next Mic Level pushed by the daemon, any patches received are discarded
+    pub async fn next_mic_level(&mut self) -> Result<MicLevelMeter> {
+        if let Some(meter) = self.mic_levels.pop_front() {
+            return Ok(meter);
+        }
+        loop {
+            let response = self.next().await?;
+            if let DaemonResponse::MicLevel(meter) = response.data {
+                return Ok(meter);
+            }
+        }
+    }
+
+    /// Waits for the next Interaction pushed by the daemon, any other events are discarded
+    pub async fn next_interaction(&mut self) -> Result<InteractionEvent> {
+        if let Some(event) = self.interactions.pop_front() {
+            return Ok(event);
+        }
+        loop {
+            let response = self.next().await?;
+            if let DaemonResponse::Interaction(event) = response.data {
+                return Ok(event);
+            }
+        }
+    }
+
+    async fn next(&mut self) -> Result<WebsocketResponse> {
+        loop {
+            let message = tokio::time::timeout(TIMEOUT, self.stream.next())
+                .await
+                .context("Timeout waiting for websocket message")?
+                .context("Websocket Closed")??;
+
+            if let Message::Text(text) = message {
+                return Ok(serde_json::from_str(&text)?);
+            }
+        }
+    }
+}
+
+/// Fetches the full status via a client, and returns our device's profile
+pub async fn get_profile<C: Client>(client: &mut C, serial: &str) -> Result<Profile> {
+    client.poll_status().await?;
+    let status: &DaemonStatus = client.status();
+    match status.devices.get(serial) {
+        Some(device) => Ok(device.config.device.clone()),
+        None => bail!("Device {} not present in status", serial),
+    }
+}
+
+/// Reduces a list of commands to their opcodes, with consecutive duplicates removed. This is
+/// useful for checking the order in which different parts of the device are configured.
+pub fn opcode_phases(commands: &[CommandRecord]) -> Vec<u32> {
+    let mut phases: Vec<u32> = commands.iter().map(|c| c.command >> 12).collect();
+    phases.dedup();
+    phases
+}
+
+/// Returns all the commands sent with the given opcode
+pub fn with_opcode(commands: &[CommandRecord], opcode: u32) -> Vec<CommandRecord> {
+    commands
+        .iter()
+        .filter(|c| c.command >> 12 == opcode)
+        .cloned()
+        .collect()
+}
+
+pub fn command(opcode: u32, sub: u32, body: &[u8]) -> CommandRecord {
+    CommandRecord {
+        command: opcode << 12 | sub,
+        body: body.to_vec(),
+    }
+}
+
+/// Sets the volume of a channel
+pub fn volume(channel: VolumeChannels, volume: u8) -> GoXLRCommand {
+    GoXLRCommand::Channels(ChannelCommands::Volume(ChannelVolume { channel, volume }))
+}
+
+/// Sets the mute state of a channel
+pub fn mute(channel: FaderChannels, state: MuteState) -> GoXLRCommand {
+    GoXLRCommand::Channels(ChannelCommands::Mute(MuteCommand { channel, state }))
+}
+
+/// Builds a SetRouting body with the given output positions enabled
+pub fn route(positions: &[usize]) -> Vec<u8> {
+    let mut body = vec![0; 22];
+    for position in positions {
+        body[*position] = 0x20;
+    }
+    body
+}
+
+/// Finds the serial number a captured device reported
+fn capture_serial(capture: &Capture) -> Result<String> {
+    let serial_request = 0x80f << 12 | 1;
+    let record = capture.records.iter().find(|r| r.command == serial_request);
+    let Some(Ok(response)) = record.map(|r| &r.response) else {
+        bail!("Capture does not contain a Serial Number");
+    };
+
+    let serial = &response[..response.len().min(24)];
+    let length = serial.iter().position(|&c| c == 0).unwrap_or(serial.len());
+    Ok(String::from_utf8_lossy(&serial[..length]).to_string())
+}
+
+fn next_serial() -> usize {
+    SERIAL.fetch_add(1, Ordering::Relaxed)
+}
+
+fn free_port() -> Result<u16> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    Ok(listener.local_addr()?.port())
+}
@@ -0,0 +1,117 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use goxlr_shared::device::DeviceType;
+use goxlr_tests::{TestDaemon, TestOptions};
+use goxlr_usb::capture::Capture;
+use goxlr_usb::virtual_device::CommandRecord;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn capture_and_replay() -> Result<()> {
+    let directory = tempfile::tempdir()?;
+
+    let options = TestOptions {
+        capture_directory: Some(directory.path().to_path_buf()),
+        ..Default::default()
+    };
+    let daemon = TestDaemon::start_with(options).await?;
+    let startup = daemon.startup.clone();
+    let serial = daemon.serial().to_string();
+    let status = daemon.status().await?;
+    daemon.stop().await?;
+
+    let files: Vec<_> = std::fs::read_dir(directory.path())?.collect::<Result<_, _>>()?;
+    assert_eq!(files.len(), 1);
+    let path = files[0].path();
+
+    let capture = Capture::load(&path)?;
+    assert_eq!(capture.device_type, DeviceType::Full);
+
+    // Everything the device received should be in the capture, in the same order..
+    assert!(requests(&capture).starts_with(&startup));
+
+    // ..with the command index incrementing on each request
+    for (position, record) in capture.records.iter().enumerate() {
+        assert_eq!(record.index as usize, position + 1);
+    }
+
+    // Replaying the capture should produce the same device, with the same configuration
+    let options = TestOptions {
+        replay: Some(path),
+        ..Default::default()
+    };
+    let replay = TestDaemon::start_with(options).await?;
+    assert_eq!(replay.serial(), serial);
+
+    let replayed = replay.status().await?;
+    assert_eq!(
+        serde_json::to_value(&replayed.hardware)?,
+        serde_json::to_value(&status.hardware)?
+    );
+    assert_eq!(
+        serde_json::to_value(&replayed.config)?,
+        serde_json::to_value(&status.config)?
+    );
+
+    replay.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn replay_golden_capture() -> Result<()> {
+    // A Full GoXLR starting up with the default profile, recorded from the virtual device
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/captures/full.capture");
+    let golden = Capture::load(&path)?;
+
+    // Capture the replay as well, so we can see what the daemon sent to it
+    let directory = tempfile::tempdir()?;
+    let options = TestOptions {
+        replay: Some(path),
+        capture_directory: Some(directory.path().to_path_buf()),
+        ..Default::default()
+    };
+    let replay = TestDaemon::start_with(options).await?;
+    assert_eq!(replay.serial(), "GOLDEN0001X");
+    assert_eq!(replay.status().await?.hardware.serial, "GOLDEN0001X");
+    replay.stop().await?;
+
+    let files: Vec<_> = std::fs::read_dir(directory.path())?.collect::<Result<_, _>>()?;
+    assert_eq!(files.len(), 1);
+    let replayed = Capture::load(&files[0].path())?;
+
+    // Initialising the device and loading the profile should send exactly what was recorded, if
+    // this changes deliberately, the capture needs recording again.
+    assert_eq!(requests(&replayed), requests(&golden));
+    Ok(())
+}
+
+// The requests in a capture, without the button polls (which are timing dependent)
+fn requests(capture: &Capture) -> Vec<CommandRecord> {
+    capture
+        .records
+        .iter()
+        .filter(|record| record.command >> 12 != 0x800)
+        .map(|record| CommandRecord {
+            command: record.command,
+            body: record.body.clone(),
+        })
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn capture_failure_is_ignored() -> Result<()> {
+    // The capture directory can't be created, as there's a file in the way
+    let directory = tempfile::tempdir()?;
+    let path = directory.path().join("captures");
+    std::fs::write(&path, "")?;
+
+    let options = TestOptions {
+        capture_directory: Some(path.clone()),
+        ..Default::default()
+    };
+    let daemon = TestDaemon::start_with(options).await?;
+    assert!(daemon.status().await.is_ok());
+    assert!(path.is_file());
+
+    daemon.stop().await
+}
@@ -0,0 +1,225 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use tokio::time::timeout;
+
+use goxlr_ipc::client::Client;
+use goxlr_ipc::clients::ipc::ipc_client::IPCClient;
+use goxlr_ipc::commands::{
+    DaemonRequest, DaemonResponse, DeviceCommand, IPCRequest, IPCResponse, Subscription,
+    WebsocketRequest, WebsocketResponse,
+};
+use goxlr_shared::channels::volume::VolumeChannels;
+use goxlr_tests::{command, get_profile, volume, TestDaemon};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn ipc_ping_and_errors() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    client.send(DaemonRequest::Ping).await?;
+
+    // Commands for unknown devices should be reported as errors, and nothing sent
+    let result = client
+        .command("UNKNOWN", volume(VolumeChannels::Game, 10))
+        .await;
+    assert!(result.is_err());
+    assert!(daemon.settle().await.is_empty());
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn web_client() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.web_client()?;
+
+    client
+        .command(daemon.serial(), volume(VolumeChannels::Music, 10))
+        .await?;
+    let commands = daemon.settle().await;
+    assert_eq!(commands[0], command(0x806, 0x07, &[10]));
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    assert_eq!(profile.channels.volumes[VolumeChannels::Music], 10);
+
+    // Both clients should agree on the status
+    let mut ipc = daemon.ipc_client().await?;
+    ipc.poll_status().await?;
+    let web_status = serde_json::to_value(client.status())?;
+    let ipc_status = serde_json::to_value(ipc.status())?;
+    assert_eq!(web_status, ipc_status);
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn websocket_patches() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut websocket = daemon.websocket().await?;
+
+    let DaemonResponse::Status(status) = websocket.request(DaemonRequest::GetStatus).await? else {
+        panic!("Expected a Status response");
+    };
+    let mut status = serde_json::to_value(status)?;
+
+    // Commands sent over the websocket should be executed..
+    let request = DaemonRequest::DeviceCommand(DeviceCommand {
+        serial: daemon.serial().to_string(),
+        command: volume(VolumeChannels::Game, 42),
+    });
+    let response = websocket.request(request).await?;
+    assert!(matches!(response, DaemonResponse::DeviceCommand(_)));
+    assert_eq!(daemon.settle().await[0], command(0x806, 0x04, &[42]));
+
+    // ..and the change pushed as a patch, which should bring our status up to date
+    let mut client = daemon.ipc_client().await?;
+    client.poll_status().await?;
+    let expected = serde_json::to_value(client.status())?;
+
+    while status != expected {
+        let patch = websocket.next_patch().await?;
+        json_patch::patch(&mut status, &patch)?;
+    }
+    let path = format!(
+        "/devices/{}/config/device/channels/volumes/Game",
+        daemon.serial()
+    );
+    assert_eq!(status.pointer(&path), Some(&serde_json::json!(42)));
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn ipc_patch_subscription() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // Subscribing should provide the status, which later patches are applied to
+    client.subscribe(Subscription::Patches).await?;
+    assert!(client.status().devices.contains_key(daemon.serial()));
+
+    let mut other = daemon.ipc_client().await?;
+    other
+        .command(daemon.serial(), volume(VolumeChannels::Game, 42))
+        .await?;
+
+    let game_volume = |client: &IPCClient| {
+        let device = &client.status().devices[daemon.serial()];
+        device.config.device.channels.volumes[VolumeChannels::Game]
+    };
+    while game_volume(&client) != 42 {
+        timeout(Duration::from_secs(10), client.next_patch()).await??;
+    }
+
+    // Commands should still work between patches, and once the patches stop the status should
+    // match a freshly fetched one.
+    client
+        .command(daemon.serial(), volume(VolumeChannels::Chat, 10))
+        .await?;
+    daemon.settle().await;
+    other.poll_status().await?;
+    let expected = serde_json::to_value(other.status())?;
+    while serde_json::to_value(client.status())? != expected {
+        timeout(Duration::from_secs(10), client.next_patch()).await??;
+    }
+
+    // Once unsubscribed, nothing more should arrive
+    client.unsubscribe(Subscription::Patches).await?;
+    other
+        .command(daemon.serial(), volume(VolumeChannels::Game, 20))
+        .await?;
+    daemon.settle().await;
+    assert!(timeout(Duration::from_millis(200), client.next_patch())
+        .await
+        .is_err());
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn websocket_patch_subscription() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut websocket = daemon.websocket().await?;
+
+    let response = websocket
+        .request(DaemonRequest::Subscribe(Subscription::Patches))
+        .await?;
+    let DaemonResponse::Status(status) = response else {
+        panic!("Expected a Status response");
+    };
+    let mut status = serde_json::to_value(status)?;
+
+    let mut client = daemon.ipc_client().await?;
+    client
+        .command(daemon.serial(), volume(VolumeChannels::Game, 42))
+        .await?;
+    daemon.settle().await;
+    client.poll_status().await?;
+
+    let expected = serde_json::to_value(client.status())?;
+    while status != expected {
+        let patch = websocket.next_patch().await?;
+        json_patch::patch(&mut status, &patch)?;
+    }
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn ipc_mixed_subscriptions() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut socket = daemon.ipc_socket().await?;
+
+    let tagged = |id, subscription| {
+        IPCRequest::Tagged(WebsocketRequest {
+            id,
+            data: DaemonRequest::Subscribe(subscription),
+        })
+    };
+
+    // The first Subscribe decides how events are sent..
+    socket.send(tagged(1, Subscription::MicLevel)).await?;
+    let response = socket.read().await.context("Socket Closed")??;
+    assert!(matches!(
+        response,
+        IPCResponse::Tagged(WebsocketResponse {
+            id: 1,
+            data: DaemonResponse::Ok
+        })
+    ));
+
+    // ..so a plain one would change the form of the existing events, and should be refused
+    let plain = IPCRequest::Plain(DaemonRequest::Subscribe(Subscription::Interactions));
+    socket.send(plain).await?;
+    let response = socket.read().await.context("Socket Closed")??;
+    assert!(matches!(
+        response,
+        IPCResponse::Plain(DaemonResponse::Err(_))
+    ));
+
+    // Further tagged subscriptions are fine, and the events still arrive tagged
+    socket.send(tagged(2, Subscription::Interactions)).await?;
+    loop {
+        match socket.read().await.context("Socket Closed")?? {
+            IPCResponse::Tagged(WebsocketResponse { id: 2, data }) => {
+                assert!(matches!(data, DaemonResponse::Ok));
+                break;
+            }
+            IPCResponse::Tagged(WebsocketResponse { id: u64::MAX, .. }) => continue,
+            response => panic!("Unexpected response: {:?}", response),
+        }
+    }
+
+    let response = timeout(Duration::from_secs(10), socket.read()).await?;
+    let response = response.context("Socket Closed")??;
+    assert!(matches!(
+        response,
+        IPCResponse::Tagged(WebsocketResponse {
+            id: u64::MAX,
+            data: DaemonResponse::MicLevel(_)
+        })
+    ));
+
+    daemon.stop().await
+}
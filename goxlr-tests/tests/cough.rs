@@ -0,0 +1,103 @@
+use anyhow::Result;
+
+use goxlr_ipc::client::Client;
+use goxlr_ipc::commands::cough::CoughCommand;
+use goxlr_ipc::commands::GoXLRCommand;
+use goxlr_profile::CoughBehaviour;
+use goxlr_shared::channels::mute::MuteActionChannels;
+use goxlr_shared::interaction::InteractiveButtons;
+use goxlr_shared::mute::MuteState;
+use goxlr_tests::{get_profile, opcode_phases, with_opcode, TestDaemon};
+
+fn cough(command: CoughCommand) -> GoXLRCommand {
+    GoXLRCommand::Cough(command)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cough_state() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // The Cough button mutes System to the Stream Mix on press, and the Headphones on hold
+    let states = [MuteState::Pressed, MuteState::Held, MuteState::Unmuted];
+    for state in states {
+        let request = cough(CoughCommand::State(state));
+        client.command(daemon.serial(), request).await?;
+        let commands = daemon.settle().await;
+        assert_eq!(opcode_phases(&commands), vec![0x804, 0x808]);
+
+        let profile = get_profile(&mut client, daemon.serial()).await?;
+        assert_eq!(profile.cough.mute_state, state);
+    }
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cough_channel() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // Changing the channel while unmuted doesn't need to touch the device
+    let music = cough(CoughCommand::Channel(MuteActionChannels::Music));
+    client.command(daemon.serial(), music).await?;
+    assert!(daemon.settle().await.is_empty());
+
+    let request = cough(CoughCommand::State(MuteState::Pressed));
+    client.command(daemon.serial(), request).await?;
+    let commands = daemon.settle().await;
+    assert_eq!(with_opcode(&commands, 0x804).len(), 2);
+
+    // Moving an active cough restores the Music routing, then mutes the Chat routing
+    let chat = cough(CoughCommand::Channel(MuteActionChannels::Chat));
+    client.command(daemon.serial(), chat).await?;
+    let commands = daemon.settle().await;
+    let routes: Vec<u32> = with_opcode(&commands, 0x804)
+        .iter()
+        .map(|record| record.command & 0xFFF)
+        .collect();
+    assert_eq!(routes.len(), 4);
+    assert_ne!(routes[..2], routes[2..]);
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    assert_eq!(profile.cough.channel_assignment, MuteActionChannels::Chat);
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cough_behaviour() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // Switching behaviour should release a latched cough
+    let request = cough(CoughCommand::State(MuteState::Pressed));
+    client.command(daemon.serial(), request).await?;
+    daemon.settle().await;
+
+    let hold = cough(CoughCommand::Behaviour(CoughBehaviour::Hold));
+    client.command(daemon.serial(), hold).await?;
+    assert_eq!(opcode_phases(&daemon.settle().await), vec![0x804, 0x808]);
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    assert_eq!(profile.cough.cough_behaviour, CoughBehaviour::Hold);
+    assert_eq!(profile.cough.mute_state, MuteState::Unmuted);
+
+    // There's no 'Held' state when using the Hold behaviour
+    let held = cough(CoughCommand::State(MuteState::Held));
+    assert!(client.command(daemon.serial(), held).await.is_err());
+
+    // With Hold, the channel is only muted while the button is down
+    let button = InteractiveButtons::CoughButton;
+    daemon.device().press(button);
+    daemon.settle().await;
+    let status = daemon.status().await?;
+    assert_eq!(status.config.device.cough.mute_state, MuteState::Pressed);
+
+    daemon.device().release(button);
+    daemon.settle().await;
+    let status = daemon.status().await?;
+    assert_eq!(status.config.device.cough.mute_state, MuteState::Unmuted);
+
+    daemon.stop().await
+}
@@ -0,0 +1,217 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::time::timeout;
+
+use goxlr_ipc::client::Client;
+use goxlr_ipc::commands::firmware::FirmwareCommand;
+use goxlr_ipc::commands::{GoXLRCommand, Subscription};
+use goxlr_shared::channels::volume::VolumeChannels;
+use goxlr_shared::firmware::FirmwareUpdateStage;
+use goxlr_tests::{command, opcode_phases, volume, with_opcode, TestDaemon};
+use goxlr_usb::virtual_device::CommandRecord;
+
+// The size of each firmware chunk sent to the device
+const CHUNK_SIZE: usize = 1012;
+
+// Firmware Command (0x810) and Action (0x004) ids
+const START: u32 = 0x810 << 12;
+const ERASE: u32 = 0x004 << 12 | 2;
+const SEND: u32 = 0x004 << 12 | 4;
+const VALIDATE: u32 = 0x004 << 12 | 6;
+const VERIFY: u32 = 0x810 << 12 | 1;
+const FINALISE: u32 = 0x810 << 12 | 3;
+const REBOOT: u32 = 0x810 << 12 | 4;
+
+fn image(length: usize) -> Vec<u8> {
+    (0..length).map(|i| (i * 7 + i / 251) as u8).collect()
+}
+
+fn update(path: &Path) -> GoXLRCommand {
+    GoXLRCommand::Firmware(FirmwareCommand::Update(path.to_path_buf()))
+}
+
+fn count(commands: &[CommandRecord], id: u32) -> usize {
+    commands.iter().filter(|c| c.command == id).count()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn firmware_update() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    let directory = tempfile::tempdir()?;
+    let path = directory.path().join("firmware.bin");
+    let firmware = image(150_000);
+    std::fs::write(&path, &firmware)?;
+
+    client.command(daemon.serial(), update(&path)).await?;
+    let status = daemon.wait_for_firmware_update().await?;
+    assert_eq!(status.stage, FirmwareUpdateStage::Complete);
+    assert_eq!(daemon.device().firmware_image(), Some(firmware.clone()));
+
+    // Once rebooted, the device should be picked back up and fully re-initialised
+    let commands = daemon.wait_for_command(0x804 << 12 | 0x11).await?;
+    let index = |id| commands.iter().position(|c| c.command == id).unwrap();
+    assert_eq!(commands[0].command, START);
+    assert!(index(ERASE) < index(SEND));
+    assert!(index(SEND) < index(VALIDATE));
+    assert!(index(VALIDATE) < index(VERIFY));
+    assert!(index(VERIFY) < index(FINALISE));
+    assert!(index(FINALISE) < index(REBOOT));
+
+    // The whole image should be sent exactly once, in order
+    let sends = commands.iter().filter(|c| c.command == SEND);
+    let sent: Vec<u8> = sends.flat_map(|c| c.body[8..].to_vec()).collect();
+    assert_eq!(sent, firmware);
+    assert_eq!(count(&commands, SEND), firmware.len().div_ceil(CHUNK_SIZE));
+
+    // Followed by the profile being applied again
+    let after_reboot = &commands[index(REBOOT) + 1..];
+    assert_eq!(with_opcode(after_reboot, 0x805).len(), 4);
+
+    let status = daemon.wait_for_device().await;
+    assert!(status.is_ok());
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn firmware_update_resends_corrupt_blocks() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    let directory = tempfile::tempdir()?;
+    let path = directory.path().join("firmware.bin");
+    let firmware = image(100_000);
+    std::fs::write(&path, &firmware)?;
+
+    // Corrupt the first chunk, this should fail validation of the first block, and be re-sent
+    daemon.device().corrupt_firmware_chunks(1);
+    client.command(daemon.serial(), update(&path)).await?;
+
+    let status = daemon.wait_for_firmware_update().await?;
+    assert_eq!(status.stage, FirmwareUpdateStage::Complete);
+    assert_eq!(daemon.device().firmware_image(), Some(firmware.clone()));
+
+    let commands = daemon.wait_for_command(0x804 << 12 | 0x11).await?;
+    let chunks = firmware.len().div_ceil(CHUNK_SIZE);
+    assert_eq!(count(&commands, SEND), chunks + 64);
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn firmware_update_failure() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    let directory = tempfile::tempdir()?;
+
+    // Images which don't exist should be refused outright..
+    let path = directory.path().join("missing.bin");
+    assert!(client
+        .command(daemon.serial(), update(&path))
+        .await
+        .is_err());
+
+    // ..while an empty image should fail before anything is sent to the device
+    let path = directory.path().join("empty.bin");
+    std::fs::write(&path, [])?;
+    client.command(daemon.serial(), update(&path)).await?;
+
+    let empty = daemon.wait_for_firmware_update().await?;
+    assert_eq!(empty.stage, FirmwareUpdateStage::Failed);
+    assert!(daemon.settle().await.is_empty());
+
+    // If every chunk fails validation, the update should give up, without flashing the device
+    let path = directory.path().join("firmware.bin");
+    std::fs::write(&path, image(10_000))?;
+
+    daemon.device().corrupt_firmware_chunks(usize::MAX);
+    client.command(daemon.serial(), update(&path)).await?;
+
+    // The previous failure shouldn't be reported as the outcome of this update
+    client.poll_status().await?;
+    let status = &client.status().firmware_updates[daemon.serial()];
+    assert_ne!(status.error, empty.error);
+
+    let status = daemon.wait_for_firmware_update().await?;
+    assert_eq!(status.stage, FirmwareUpdateStage::Failed);
+    assert!(status.error.is_some());
+    assert_ne!(status.error, empty.error);
+    assert_eq!(daemon.device().firmware_image(), None);
+    daemon.device().corrupt_firmware_chunks(0);
+
+    // The device should still be brought back up afterwards
+    let commands = daemon.wait_for_command(0x804 << 12 | 0x11).await?;
+    assert!(!commands.iter().any(|c| c.command == VERIFY));
+    assert_eq!(opcode_phases(&commands)[0], 0x810);
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn commands_refused_during_update() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    let directory = tempfile::tempdir()?;
+    let path = directory.path().join("firmware.bin");
+    std::fs::write(&path, image(50_000))?;
+
+    client.command(daemon.serial(), update(&path)).await?;
+
+    // Erase, Verify and Finalise all need polling, so the update won't be done yet
+    let volume = volume(VolumeChannels::Game, 10);
+    assert!(client
+        .command(daemon.serial(), volume.clone())
+        .await
+        .is_err());
+    assert!(client
+        .command(daemon.serial(), update(&path))
+        .await
+        .is_err());
+
+    let status = daemon.wait_for_firmware_update().await?;
+    assert_eq!(status.stage, FirmwareUpdateStage::Complete);
+
+    // Nothing other than the update should have reached the device
+    let commands = daemon.wait_for_command(0x804 << 12 | 0x11).await?;
+    assert!(!commands.contains(&command(0x806, 0x04, &[10])));
+
+    // Once the device is back, commands should be accepted again
+    daemon.wait_for_device().await?;
+    client.command(daemon.serial(), volume).await?;
+    assert_eq!(daemon.settle().await[0], command(0x806, 0x04, &[10]));
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn firmware_update_while_metering() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // The meter can't be sampled during an update, but it mustn't hold the update up either
+    let mut meter = daemon.ipc_client().await?;
+    meter.subscribe(Subscription::MicLevel).await?;
+    timeout(Duration::from_secs(10), meter.next_mic_level()).await??;
+
+    let directory = tempfile::tempdir()?;
+    let path = directory.path().join("firmware.bin");
+    let firmware = image(150_000);
+    std::fs::write(&path, &firmware)?;
+
+    client.command(daemon.serial(), update(&path)).await?;
+    let status = daemon.wait_for_firmware_update().await?;
+    assert_eq!(status.stage, FirmwareUpdateStage::Complete);
+    assert_eq!(daemon.device().firmware_image(), Some(firmware));
+
+    // Once the device is back, sampling should pick up again
+    daemon.wait_for_device().await?;
+    timeout(Duration::from_secs(10), meter.next_mic_level()).await??;
+
+    daemon.stop().await
+}
@@ -0,0 +1,152 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::time::timeout;
+
+use goxlr_ipc::client::Client;
+use goxlr_ipc::clients::ipc::ipc_client::IPCClient;
+use goxlr_ipc::commands::pages::PageCommand;
+use goxlr_ipc::commands::{DaemonRequest, DaemonResponse, GoXLRCommand, Subscription};
+use goxlr_ipc::status::Interaction;
+use goxlr_shared::buttons::Buttons;
+use goxlr_shared::device::DeviceType;
+use goxlr_shared::encoders::Encoders;
+use goxlr_shared::faders::Fader;
+use goxlr_shared::interaction::{InteractiveButtons, InteractiveEncoders, InteractiveFaders};
+use goxlr_tests::TestDaemon;
+use goxlr_usb::virtual_device::{self, Script};
+
+// Waits for the next interaction from our device
+async fn next(client: &mut IPCClient, serial: &str) -> Result<Interaction> {
+    let event = timeout(Duration::from_secs(10), client.next_interaction()).await??;
+    assert_eq!(event.serial, serial);
+    Ok(event.interaction)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn ipc_interactions() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let serial = daemon.serial();
+
+    let mut client = daemon.ipc_client().await?;
+    client.subscribe(Subscription::Interactions).await?;
+
+    let wait = Duration::from_millis(100);
+    let script = Script::new()
+        .press(InteractiveButtons::SamplerTopLeft)
+        .wait(wait)
+        .release(InteractiveButtons::SamplerTopLeft)
+        .wait(wait);
+    daemon.device().run(&script).await;
+
+    let button = Buttons::SamplerTopLeft;
+    assert_eq!(
+        next(&mut client, serial).await?,
+        Interaction::ButtonDown(button)
+    );
+    assert_eq!(
+        next(&mut client, serial).await?,
+        Interaction::ButtonUp(button)
+    );
+
+    // Holding a fader's mute button past the hold time should be reported between the press and
+    // the release
+    daemon
+        .device()
+        .hold(InteractiveButtons::Fader3Mute, Duration::from_millis(1500))
+        .await;
+
+    let button = Buttons::FaderC;
+    assert_eq!(
+        next(&mut client, serial).await?,
+        Interaction::ButtonDown(button)
+    );
+    assert_eq!(
+        next(&mut client, serial).await?,
+        Interaction::ButtonHeld(button)
+    );
+    assert_eq!(
+        next(&mut client, serial).await?,
+        Interaction::ButtonUp(button)
+    );
+
+    // Fader moves should include the channel currently assigned to the fader
+    client.poll_status().await?;
+    let pages = &client.status().devices[serial].config.device.pages;
+    let channel = pages.page_list[pages.current].faders[Fader::C];
+
+    daemon.device().set_fader(InteractiveFaders::C, 100);
+    let expected = Interaction::FaderMoved {
+        fader: Fader::C,
+        channel,
+        value: 100,
+    };
+    assert_eq!(next(&mut client, serial).await?, expected);
+
+    daemon.device().set_encoder(InteractiveEncoders::Echo, 7);
+    let expected = Interaction::EncoderChanged {
+        encoder: Encoders::Echo,
+        value: 7,
+    };
+    assert_eq!(next(&mut client, serial).await?, expected);
+
+    // Page changes are reported regardless of where they came from
+    let page = GoXLRCommand::Pages(PageCommand::LoadPage(1));
+    client.command(serial, page).await?;
+    assert_eq!(
+        next(&mut client, serial).await?,
+        Interaction::PageChanged(1)
+    );
+
+    // Once unsubscribed, nothing more should arrive
+    client.unsubscribe(Subscription::Interactions).await?;
+    daemon.device().set_fader(InteractiveFaders::C, 50);
+    daemon.settle().await;
+    let result = timeout(Duration::from_millis(200), client.next_interaction()).await;
+    assert!(result.is_err());
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn device_attach_and_removal() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let serial = daemon.serial();
+
+    let mut client = daemon.ipc_client().await?;
+    client.subscribe(Subscription::Interactions).await?;
+
+    virtual_device::detach(serial);
+    assert_eq!(next(&mut client, serial).await?, Interaction::DeviceRemoved);
+
+    virtual_device::attach(serial, DeviceType::Full);
+    assert_eq!(
+        next(&mut client, serial).await?,
+        Interaction::DeviceAttached
+    );
+    daemon.wait_for_device().await?;
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn websocket_interactions() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut websocket = daemon.websocket().await?;
+
+    let request = DaemonRequest::Subscribe(Subscription::Interactions);
+    let response = websocket.request(request).await?;
+    assert!(matches!(response, DaemonResponse::Ok));
+
+    daemon.device().set_encoder(InteractiveEncoders::Reverb, 3);
+
+    let event = websocket.next_interaction().await?;
+    assert_eq!(event.serial, daemon.serial());
+    let expected = Interaction::EncoderChanged {
+        encoder: Encoders::Reverb,
+        value: 3,
+    };
+    assert_eq!(event.interaction, expected);
+
+    daemon.stop().await
+}
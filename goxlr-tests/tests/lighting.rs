@@ -0,0 +1,385 @@
+use anyhow::Result;
+
+use goxlr_ipc::client::Client;
+use goxlr_ipc::commands::lighting::LightingCommand;
+use goxlr_ipc::commands::GoXLRCommand;
+use goxlr_profile::Profile;
+use goxlr_profile::{Animation, ButtonColourSet, EncoderColourSet, FaderColourSet, LightingTheme};
+use goxlr_shared::animation::{AnimationMode, WaterfallDirection};
+use goxlr_shared::buttons::{InactiveButtonBehaviour, LightingButtons};
+use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::colours::Colour;
+use goxlr_shared::encoders::Encoders;
+use goxlr_tests::{command, get_profile, opcode_phases, with_opcode, TestDaemon};
+use goxlr_usb::virtual_device::CommandRecord;
+
+const RED: Colour = Colour {
+    red: 255,
+    green: 0,
+    blue: 0,
+};
+const NAVY: Colour = Colour {
+    red: 0,
+    green: 0,
+    blue: 128,
+};
+const WHITE: Colour = Colour {
+    red: 255,
+    green: 255,
+    blue: 255,
+};
+
+fn lighting(command: LightingCommand) -> GoXLRCommand {
+    GoXLRCommand::Lighting(command)
+}
+
+fn button_colours(inactive_behaviour: InactiveButtonBehaviour) -> ButtonColourSet {
+    ButtonColourSet {
+        active_colour: RED,
+        inactive_colour: NAVY,
+        inactive_behaviour,
+    }
+}
+
+/// Pulls a colour from a colour map, by its position in the map
+fn colour_at(map: &CommandRecord, index: usize) -> Colour {
+    let bytes = &map.body[index * 4..index * 4 + 4];
+    Colour {
+        red: bytes[2] as u32,
+        green: bytes[1] as u32,
+        blue: bytes[0] as u32,
+    }
+}
+
+/// The number of colours in the map, the mic buttons sit at the end of it
+fn colour_count(map: &CommandRecord) -> usize {
+    map.body.len() / 4
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn button_colours_and_behaviour() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // Changing the inactive behaviour needs both the colour map, and the button states
+    let colours = button_colours(InactiveButtonBehaviour::InactiveColour);
+    let command = LightingCommand::ButtonColours(LightingButtons::SamplerTopLeft, colours);
+    client
+        .command(daemon.serial(), lighting(command.clone()))
+        .await?;
+    let commands = daemon.settle().await;
+    assert_eq!(opcode_phases(&commands), vec![0x803, 0x808]);
+
+    // The Sampler Pads are followed by the 4 FX buttons and 2 Mic buttons, Top Left is the second
+    let index = colour_count(&commands[0]) - 12 - 10 + 2;
+    assert_eq!(colour_at(&commands[0], index), RED);
+    assert_eq!(colour_at(&commands[0], index + 1), NAVY);
+
+    // Sending the same thing again shouldn't send anything
+    client.command(daemon.serial(), lighting(command)).await?;
+    assert!(daemon.settle().await.is_empty());
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    let stored = profile.lighting.buttons[LightingButtons::SamplerTopLeft];
+    assert_eq!(stored, colours);
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn mic_button_colours() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // The default behaviour is DimActive, so only the colour map should change
+    let colours = button_colours(InactiveButtonBehaviour::DimActive);
+    let swear = LightingCommand::SwearColours(colours);
+    client.command(daemon.serial(), lighting(swear)).await?;
+    let commands = daemon.settle().await;
+    assert_eq!(opcode_phases(&commands), vec![0x803]);
+
+    let count = colour_count(&commands[0]);
+    assert_eq!(colour_at(&commands[0], count - 4), RED);
+    assert_eq!(colour_at(&commands[0], count - 3), NAVY);
+
+    let cough = LightingCommand::CoughColours(colours);
+    client.command(daemon.serial(), lighting(cough)).await?;
+    let commands = daemon.settle().await;
+    assert_eq!(colour_at(&commands[0], count - 2), RED);
+    assert_eq!(colour_at(&commands[0], count - 1), NAVY);
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    assert_eq!(profile.swear.colours, colours);
+    assert_eq!(profile.cough.colours, colours);
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn encoder_colours() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    let colours = EncoderColourSet {
+        left_colour: RED,
+        right_colour: NAVY,
+        knob_colour: WHITE,
+    };
+    let command = LightingCommand::EncoderColours(Encoders::Reverb, colours);
+    client.command(daemon.serial(), lighting(command)).await?;
+    let commands = daemon.settle().await;
+    assert_eq!(opcode_phases(&commands), vec![0x803]);
+
+    // Encoders are followed by a spacer, 3 sample banks, 5 pads, 4 FX and 2 Mic buttons
+    let encoders = colour_count(&commands[0]) - 28 - 1 - 12;
+    let reverb = encoders + 2 * 3;
+    assert_eq!(colour_at(&commands[0], reverb), RED);
+    assert_eq!(colour_at(&commands[0], reverb + 1), NAVY);
+    assert_eq!(colour_at(&commands[0], reverb + 2), WHITE);
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn channel_colours() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // Chat is on the second fader, its scribble and mute button should be updated..
+    let scribble = LightingCommand::ScribbleColour(FaderChannels::Chat, RED);
+    client.command(daemon.serial(), lighting(scribble)).await?;
+    let commands = daemon.settle().await;
+    assert_eq!(opcode_phases(&commands), vec![0x803]);
+    assert_eq!(colour_at(&commands[0], 2), RED);
+
+    let colours = button_colours(InactiveButtonBehaviour::DimActive);
+    let mute = LightingCommand::MuteColours(FaderChannels::Chat, colours);
+    client.command(daemon.serial(), lighting(mute)).await?;
+    let commands = daemon.settle().await;
+    assert_eq!(opcode_phases(&commands), vec![0x803]);
+    assert_eq!(colour_at(&commands[0], 12 + 2), RED);
+    assert_eq!(colour_at(&commands[0], 12 + 3), NAVY);
+
+    // ..along with the fader itself, which sits after the 4 mute buttons
+    let fader = FaderColourSet {
+        top_colour: WHITE,
+        bottom_colour: NAVY,
+    };
+    let command = LightingCommand::FaderColours(FaderChannels::Chat, fader);
+    client.command(daemon.serial(), lighting(command)).await?;
+    let commands = daemon.settle().await;
+    assert_eq!(opcode_phases(&commands), vec![0x803]);
+
+    // Depending on the firmware, each fader has either 2 or 14 colours
+    let stride = if colour_count(&commands[0]) > 100 {
+        14
+    } else {
+        2
+    };
+    assert_eq!(colour_at(&commands[0], 20 + stride), WHITE);
+    assert_eq!(colour_at(&commands[0], 20 + stride + 1), NAVY);
+
+    // Game isn't on the current page, so it should only be stored in the profile
+    let command = LightingCommand::FaderColours(FaderChannels::Game, fader);
+    client.command(daemon.serial(), lighting(command)).await?;
+    let command = LightingCommand::MuteColours(FaderChannels::Game, colours);
+    client.command(daemon.serial(), lighting(command)).await?;
+    assert!(daemon.settle().await.is_empty());
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    let chat = &profile.channels.configs[FaderChannels::Chat].display;
+    assert_eq!(chat.screen_display.colour, RED);
+    assert_eq!(chat.mute_colours, colours);
+    let game = &profile.channels.configs[FaderChannels::Game].display;
+    assert_eq!(game.fader_colours, fader);
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn solid_theme() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    let name = String::from("Red");
+    let theme = LightingTheme::Solid(RED);
+    let save = LightingCommand::SaveTheme(name.clone(), theme);
+    client.command(daemon.serial(), lighting(save)).await?;
+    assert!(daemon.settle().await.is_empty());
+
+    // Everything should be recoloured with a single colour map
+    let apply = LightingCommand::ApplyTheme(name.clone());
+    client
+        .command(daemon.serial(), lighting(apply.clone()))
+        .await?;
+    let commands = daemon.settle().await;
+    assert_eq!(opcode_phases(&commands), vec![0x803]);
+
+    let map = &commands[0];
+    let count = colour_count(map);
+    for scribble in [0, 2, 4, 6] {
+        assert_eq!(colour_at(map, scribble), RED);
+    }
+    for mute in [12, 14, 16, 18] {
+        assert_eq!(colour_at(map, mute), RED);
+    }
+    assert_eq!(colour_at(map, 20), RED);
+    assert_eq!(colour_at(map, count - 4), RED);
+    assert_eq!(colour_at(map, count - 2), RED);
+    assert_eq!(colour_at(map, count - 41), RED);
+
+    // Channels which aren't visible should also be updated, so they match when assigned
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    assert_eq!(profile.lighting.themes.get(&name), Some(&theme));
+    let game = &profile.channels.configs[FaderChannels::Game].display;
+    assert_eq!(game.fader_colours.top_colour, RED);
+    assert_eq!(game.mute_colours.active_colour, RED);
+
+    // Both colours of every button and encoder should be set, not just the active ones
+    assert_eq!(game.mute_colours.inactive_colour, RED);
+    assert_eq!(profile.cough.colours.inactive_colour, RED);
+    assert_eq!(profile.swear.colours.inactive_colour, RED);
+    for colours in profile.lighting.buttons.values() {
+        assert_eq!(colours.inactive_colour, RED);
+    }
+    for colours in profile.lighting.encoders.values() {
+        assert_eq!(colours.right_colour, RED);
+    }
+
+    // Applying it again shouldn't change anything
+    client.command(daemon.serial(), lighting(apply)).await?;
+    assert!(daemon.settle().await.is_empty());
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn gradient_theme() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    let name = String::from("Gradient");
+    let theme = LightingTheme::FaderGradient {
+        top: WHITE,
+        bottom: NAVY,
+    };
+    let save = LightingCommand::SaveTheme(name.clone(), theme);
+    client.command(daemon.serial(), lighting(save)).await?;
+
+    let apply = LightingCommand::ApplyTheme(name.clone());
+    client.command(daemon.serial(), lighting(apply)).await?;
+    let commands = daemon.settle().await;
+    assert_eq!(opcode_phases(&commands), vec![0x803]);
+
+    let stride = if colour_count(&commands[0]) > 100 {
+        14
+    } else {
+        2
+    };
+    for fader in 0..4 {
+        let index = 20 + fader * stride;
+        assert_eq!(colour_at(&commands[0], index), WHITE);
+        assert_eq!(colour_at(&commands[0], index + 1), NAVY);
+    }
+
+    // Once removed, it can't be applied
+    let remove = LightingCommand::RemoveTheme(name.clone());
+    client.command(daemon.serial(), lighting(remove)).await?;
+    let apply = LightingCommand::ApplyTheme(name.clone());
+    assert!(client
+        .command(daemon.serial(), lighting(apply))
+        .await
+        .is_err());
+    assert!(daemon.settle().await.is_empty());
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    assert!(profile.lighting.themes.is_empty());
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn copy_channel_colours() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    let scribble = LightingCommand::ScribbleColour(FaderChannels::Chat, RED);
+    client.command(daemon.serial(), lighting(scribble)).await?;
+    let colours = button_colours(InactiveButtonBehaviour::DimActive);
+    let mute = LightingCommand::MuteColours(FaderChannels::Chat, colours);
+    client.command(daemon.serial(), lighting(mute)).await?;
+    daemon.settle().await;
+
+    // Copying Chat's colours should update every other visible channel in one go
+    let copy = LightingCommand::CopyChannelColours(FaderChannels::Chat);
+    client.command(daemon.serial(), lighting(copy)).await?;
+    let commands = daemon.settle().await;
+    assert_eq!(opcode_phases(&commands), vec![0x803]);
+    for fader in 0..4 {
+        assert_eq!(colour_at(&commands[0], fader * 2), RED);
+        assert_eq!(colour_at(&commands[0], 12 + fader * 2), RED);
+        assert_eq!(colour_at(&commands[0], 12 + fader * 2 + 1), NAVY);
+    }
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    let game = &profile.channels.configs[FaderChannels::Game].display;
+    assert_eq!(game.screen_display.colour, RED);
+    assert_eq!(game.mute_colours, colours);
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn animation_is_loaded_from_profile() -> Result<()> {
+    // By default there's no animation, so animations are disabled
+    let daemon = TestDaemon::start().await?;
+    let animation = with_opcode(&daemon.startup, 0x816);
+    assert_eq!(
+        animation,
+        vec![command(0x816, 0, &[0, 5, 0, 0, 2, 0, 0, 0])]
+    );
+    daemon.stop().await?;
+
+    let mut profile = Profile::default();
+    profile.lighting.animation = Animation {
+        mode: AnimationMode::Ripple,
+        mod1: 40,
+        mod2: 10,
+        waterfall: WaterfallDirection::Up,
+    };
+
+    let daemon = TestDaemon::start_with_profile(profile).await?;
+    let animation = with_opcode(&daemon.startup, 0x816);
+    assert_eq!(
+        animation,
+        vec![command(0x816, 0, &[1, 4, 40, 10, 1, 0, 0, 0])]
+    );
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn set_animation() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    let animation = Animation {
+        mode: AnimationMode::RainbowBright,
+        mod1: 100,
+        mod2: 0,
+        waterfall: WaterfallDirection::Down,
+    };
+    let set = LightingCommand::Animation(animation);
+    client.command(daemon.serial(), lighting(set)).await?;
+
+    let commands = daemon.settle().await;
+    assert_eq!(
+        commands,
+        vec![command(0x816, 0, &[1, 2, 100, 0, 0, 0, 0, 0])]
+    );
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    assert_eq!(profile.lighting.animation, animation);
+
+    daemon.stop().await
+}
@@ -0,0 +1,121 @@
+use anyhow::Result;
+
+use goxlr_ipc::client::Client;
+use goxlr_ipc::commands::{DaemonRequest, DaemonResponse, Subscription};
+use goxlr_ipc::status::MicLevelMeter;
+use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::mute::MuteState;
+use goxlr_tests::{mute, with_opcode, TestDaemon};
+
+// The device reports levels between -72.2dB and 0dB
+fn assert_valid(meter: &MicLevelMeter, serial: &str) {
+    assert_eq!(meter.serial, serial);
+    for level in [meter.level, meter.peak, meter.rms] {
+        assert!(
+            (-72.2..=0.).contains(&level),
+            "Level out of range: {}",
+            level
+        );
+    }
+    assert!(meter.peak >= meter.level);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn ipc_mic_meter() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // Nobody is listening, so the level shouldn't be polled
+    assert!(with_opcode(&daemon.settle().await, 0x80c).is_empty());
+
+    let subscription = Subscription::MicLevel;
+    client.send(DaemonRequest::Subscribe(subscription)).await?;
+    for _ in 0..5 {
+        let meter = client.next_mic_level().await?;
+        assert_valid(&meter, daemon.serial());
+    }
+
+    // Commands should still work while readings are arriving
+    client.poll_status().await?;
+    assert!(client.status().devices.contains_key(daemon.serial()));
+    assert_valid(&client.next_mic_level().await?, daemon.serial());
+
+    // Once unsubscribed, the sampling should stop
+    client
+        .send(DaemonRequest::Unsubscribe(subscription))
+        .await?;
+    let commands = daemon.device().take_commands();
+    assert!(!with_opcode(&commands, 0x80c).is_empty());
+
+    daemon.settle().await;
+    assert!(with_opcode(&daemon.settle().await, 0x80c).is_empty());
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn websocket_mic_meter() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut websocket = daemon.websocket().await?;
+
+    let subscription = Subscription::MicLevel;
+    let response = websocket
+        .request(DaemonRequest::Subscribe(subscription))
+        .await?;
+    assert!(matches!(response, DaemonResponse::Ok));
+
+    for _ in 0..5 {
+        let meter = websocket.next_mic_level().await?;
+        assert_valid(&meter, daemon.serial());
+    }
+
+    // Closing the websocket should drop the subscription
+    drop(websocket);
+    daemon.settle().await;
+    assert!(with_opcode(&daemon.settle().await, 0x80c).is_empty());
+
+    // There's nowhere to push readings to over HTTP
+    let mut client = daemon.web_client()?;
+    let result = client.send(DaemonRequest::Subscribe(subscription)).await;
+    assert!(result.is_err());
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn peak_hold_and_rms() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+    let mut meter_client = daemon.ipc_client().await?;
+
+    let subscription = Subscription::MicLevel;
+    meter_client
+        .send(DaemonRequest::Subscribe(subscription))
+        .await?;
+
+    // Wait for someone to start talking..
+    let mut meter = meter_client.next_mic_level().await?;
+    while meter.level < -30. {
+        meter = meter_client.next_mic_level().await?;
+    }
+
+    // ..then mute them, leaving only the noise floor
+    let mute = mute(FaderChannels::Microphone, MuteState::Pressed);
+    client.command(daemon.serial(), mute).await?;
+
+    while meter.level > -59. {
+        meter = meter_client.next_mic_level().await?;
+    }
+
+    // The peak should be held, and the RMS should still be falling
+    assert!(meter.peak >= -30.);
+    assert!(meter.rms > meter.level);
+
+    // Eventually both settle at the floor
+    while meter.peak > -59. || meter.rms > -59. {
+        meter = meter_client.next_mic_level().await?;
+        assert_valid(&meter, daemon.serial());
+    }
+
+    daemon.stop().await
+}
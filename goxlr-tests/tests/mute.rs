@@ -0,0 +1,295 @@
+use std::time::Duration;
+
+use anyhow::Result;
+
+use goxlr_ipc::client::Client;
+use goxlr_ipc::commands::channels::{ChannelCommands, MuteTargets};
+use goxlr_ipc::commands::cough::CoughCommand;
+use goxlr_ipc::commands::GoXLRCommand;
+use goxlr_profile::{MuteAction, Profile};
+use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::channels::mute::MuteActionChannels;
+use goxlr_shared::channels::output::OutputChannels;
+use goxlr_shared::interaction::InteractiveButtons;
+use goxlr_shared::mute::MuteState;
+use goxlr_tests::{command, get_profile, mute, opcode_phases, route, with_opcode, TestDaemon};
+use goxlr_usb::virtual_device::CommandRecord;
+
+fn route_command(input: u32, positions: &[usize]) -> CommandRecord {
+    command(0x804, input, &route(positions))
+}
+
+fn targets(
+    channel: MuteActionChannels,
+    action: MuteAction,
+    targets: Vec<OutputChannels>,
+) -> GoXLRCommand {
+    let targets = MuteTargets {
+        channel,
+        action,
+        targets,
+    };
+    GoXLRCommand::Channels(ChannelCommands::MuteTargets(targets))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn mute_to_all() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // Chat has no targets, so gets muted at the channel, and its fader button and colours updated
+    client
+        .command(
+            daemon.serial(),
+            mute(FaderChannels::Chat, MuteState::Pressed),
+        )
+        .await?;
+    let commands = daemon.settle().await;
+    assert_eq!(commands[0], command(0x809, 0x05, &[1]));
+    assert_eq!(opcode_phases(&commands), vec![0x809, 0x808, 0x803]);
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    let state = profile.channels.configs[FaderChannels::Chat].mute_state;
+    assert_eq!(state, MuteState::Pressed);
+
+    client
+        .command(
+            daemon.serial(),
+            mute(FaderChannels::Chat, MuteState::Unmuted),
+        )
+        .await?;
+    let commands = daemon.settle().await;
+    assert_eq!(commands[0], command(0x809, 0x05, &[0]));
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn mute_to_targets() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // Line In is configured to mute to the Stream Mix on Press, it's not on a fader so only the
+    // routing should change, leaving the Headphones route in place.
+    client
+        .command(
+            daemon.serial(),
+            mute(FaderChannels::LineIn, MuteState::Pressed),
+        )
+        .await?;
+    let commands = daemon.settle().await;
+    assert_eq!(
+        commands,
+        vec![
+            command(0x804, 0x04, &route(&[1])),
+            command(0x804, 0x05, &route(&[3])),
+        ]
+    );
+
+    client
+        .command(
+            daemon.serial(),
+            mute(FaderChannels::LineIn, MuteState::Unmuted),
+        )
+        .await?;
+    let commands = daemon.settle().await;
+    assert_eq!(
+        commands,
+        vec![
+            command(0x804, 0x04, &route(&[1, 5])),
+            command(0x804, 0x05, &route(&[3, 7])),
+        ]
+    );
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn mute_to_targets_with_cough() -> Result<()> {
+    // Attach the Cough button to Line In, which mutes to the Headphones when Held
+    let mut profile = Profile::default();
+    profile.cough.channel_assignment = MuteActionChannels::LineIn;
+    let line_in = &mut profile.channels.mute_actions[MuteActionChannels::LineIn];
+    line_in.actions[MuteAction::Hold] = vec![OutputChannels::Headphones];
+
+    let daemon = TestDaemon::start_with_profile(profile).await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // The Cough button mutes Line In to the Stream Mix..
+    daemon
+        .device()
+        .hold(InteractiveButtons::CoughButton, Duration::from_millis(100))
+        .await;
+    let commands = with_opcode(&daemon.settle().await, 0x804);
+    assert_eq!(
+        commands,
+        vec![route_command(0x04, &[1]), route_command(0x05, &[3])]
+    );
+
+    // ..so Holding the channel should mute it to both
+    client
+        .command(
+            daemon.serial(),
+            mute(FaderChannels::LineIn, MuteState::Held),
+        )
+        .await?;
+    let commands = daemon.settle().await;
+    assert_eq!(
+        commands,
+        vec![route_command(0x04, &[]), route_command(0x05, &[])]
+    );
+
+    // Unmuting the channel leaves the Cough's mute in place
+    client
+        .command(
+            daemon.serial(),
+            mute(FaderChannels::LineIn, MuteState::Unmuted),
+        )
+        .await?;
+    let commands = daemon.settle().await;
+    assert_eq!(
+        commands,
+        vec![route_command(0x04, &[1]), route_command(0x05, &[3])]
+    );
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn mute_button_press() -> Result<()> {
+    let mut profile = Profile::default();
+    let music = &mut profile.channels.mute_actions[MuteActionChannels::Music];
+    music.actions[MuteAction::Press] = vec![OutputChannels::Headphones];
+
+    let daemon = TestDaemon::start_with_profile(profile).await?;
+
+    // Music is on the third fader, a short press should remove it from the Headphones
+    let button = InteractiveButtons::Fader3Mute;
+    daemon
+        .device()
+        .hold(button, Duration::from_millis(100))
+        .await;
+    let commands = daemon.settle().await;
+    assert_eq!(commands[0], command(0x804, 0x0e, &route(&[5])));
+    assert_eq!(commands[1], command(0x804, 0x0f, &route(&[7])));
+
+    let status = daemon.status().await?;
+    let state = status.config.device.channels.configs[FaderChannels::Music].mute_state;
+    assert_eq!(state, MuteState::Pressed);
+
+    // Pressing again should restore it
+    daemon
+        .device()
+        .hold(button, Duration::from_millis(100))
+        .await;
+    let commands = daemon.settle().await;
+    assert_eq!(commands[0], command(0x804, 0x0e, &route(&[1, 5])));
+    assert_eq!(commands[1], command(0x804, 0x0f, &route(&[3, 7])));
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn changing_targets() -> Result<()> {
+    // Line In starts muted to the Stream Mix
+    let mut profile = Profile::default();
+    profile.channels.configs[FaderChannels::LineIn].mute_state = MuteState::Pressed;
+
+    let daemon = TestDaemon::start_with_profile(profile).await?;
+    let mut client = daemon.ipc_client().await?;
+    let line_in = MuteActionChannels::LineIn;
+
+    // The Hold action isn't active, so changing its targets should only update the profile
+    let headphones = vec![OutputChannels::Headphones, OutputChannels::Headphones];
+    let request = targets(line_in, MuteAction::Hold, headphones);
+    client.command(daemon.serial(), request).await?;
+    assert!(daemon.settle().await.is_empty());
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    let actions = &profile.channels.mute_actions[line_in].actions;
+    assert_eq!(actions[MuteAction::Hold], vec![OutputChannels::Headphones]);
+
+    // Swap the Press target to the Headphones
+    let headphones = vec![OutputChannels::Headphones];
+    let request = targets(line_in, MuteAction::Press, headphones.clone());
+    client.command(daemon.serial(), request).await?;
+    assert_eq!(
+        daemon.settle().await,
+        vec![route_command(0x04, &[5]), route_command(0x05, &[7]),]
+    );
+
+    // Setting the same targets again shouldn't send anything
+    let request = targets(line_in, MuteAction::Press, headphones);
+    client.command(daemon.serial(), request).await?;
+    assert!(daemon.settle().await.is_empty());
+
+    // Clearing the targets mutes to all, which restores the routing and mutes the channel
+    let request = targets(line_in, MuteAction::Press, vec![]);
+    client.command(daemon.serial(), request).await?;
+    let commands = daemon.settle().await;
+    assert_eq!(commands[0], command(0x809, 0x01, &[1]));
+    assert_eq!(opcode_phases(&commands), vec![0x809, 0x804]);
+    assert_eq!(
+        &commands[1..],
+        &[route_command(0x04, &[1, 5]), route_command(0x05, &[3, 7])]
+    );
+
+    // And back to a target, which unmutes the channel again
+    let stream = vec![OutputChannels::StreamMix];
+    let request = targets(line_in, MuteAction::Press, stream);
+    client.command(daemon.serial(), request).await?;
+    let commands = daemon.settle().await;
+    assert_eq!(commands[0], command(0x809, 0x01, &[0]));
+    assert_eq!(
+        &commands[1..],
+        &[route_command(0x04, &[1]), route_command(0x05, &[3])]
+    );
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn changing_cough_targets() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // The Cough button is assigned to System, and mutes it to the Stream Mix on press
+    let cough = InteractiveButtons::CoughButton;
+    daemon
+        .device()
+        .hold(cough, Duration::from_millis(100))
+        .await;
+    let commands = daemon.settle().await;
+    assert!(with_opcode(&commands, 0x809).is_empty());
+    assert!(!with_opcode(&commands, 0x804).is_empty());
+
+    // Switching the cough to 'Mute to All' should mute the channel, and dim System's fader
+    let all = CoughCommand::MuteTargets(MuteAction::Press, vec![]);
+    client
+        .command(daemon.serial(), GoXLRCommand::Cough(all))
+        .await?;
+    let commands = daemon.settle().await;
+    let mutes: Vec<Vec<u8>> = with_opcode(&commands, 0x809)
+        .into_iter()
+        .map(|record| record.body)
+        .collect();
+    assert_eq!(mutes, vec![vec![1]]);
+    assert!(!with_opcode(&commands, 0x803).is_empty());
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    assert!(profile.cough.mute_actions[MuteAction::Press].is_empty());
+
+    // Pressing the cough button again should unmute it
+    daemon
+        .device()
+        .hold(cough, Duration::from_millis(100))
+        .await;
+    let commands = daemon.settle().await;
+    let mutes: Vec<Vec<u8>> = with_opcode(&commands, 0x809)
+        .into_iter()
+        .map(|record| record.body)
+        .collect();
+    assert_eq!(mutes, vec![vec![0]]);
+
+    daemon.stop().await
+}
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use anyhow::Result;
+
+use goxlr_ipc::client::Client;
+use goxlr_ipc::commands::pages::{PageCommand, SetFader};
+use goxlr_ipc::commands::GoXLRCommand;
+use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::faders::Fader;
+use goxlr_shared::interaction::InteractiveButtons;
+use goxlr_tests::{command, get_profile, with_opcode, TestDaemon};
+use goxlr_usb::virtual_device::Script;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn load_page() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // Page 2 is System, Game, Line In, Line Out
+    let page = GoXLRCommand::Pages(PageCommand::LoadPage(1));
+    client.command(daemon.serial(), page).await?;
+
+    let commands = daemon.settle().await;
+    assert_eq!(
+        with_opcode(&commands, 0x805),
+        vec![
+            command(0x805, 0, &[0x03, 0, 0, 0]),
+            command(0x805, 1, &[0x04, 0, 0, 0]),
+            command(0x805, 2, &[0x01, 0, 0, 0]),
+            command(0x805, 3, &[0x0a, 0, 0, 0]),
+        ]
+    );
+
+    // Once the faders are assigned, the colours and button states should be refreshed
+    let last: Vec<u32> = commands.iter().rev().take(2).map(|c| c.command).collect();
+    assert_eq!(last, vec![0x808 << 12, 0x803 << 12]);
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    assert_eq!(profile.pages.current, 1);
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn set_page_fader() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // Assigning Music to A on the current page should swap it with the Mic
+    let request = GoXLRCommand::Pages(PageCommand::SetFader(SetFader {
+        page_number: 0,
+        fader: Fader::A,
+        channel: FaderChannels::Music,
+    }));
+    client.command(daemon.serial(), request).await?;
+
+    let commands = daemon.settle().await;
+    assert_eq!(
+        with_opcode(&commands, 0x805),
+        vec![
+            command(0x805, 0, &[0x07, 0, 0, 0]),
+            command(0x805, 2, &[0x00, 0, 0, 0]),
+        ]
+    );
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    assert_eq!(
+        profile.pages.page_list[0].faders[Fader::A],
+        FaderChannels::Music
+    );
+    assert_eq!(
+        profile.pages.page_list[0].faders[Fader::C],
+        FaderChannels::Microphone
+    );
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn change_page_with_buttons() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let wait = Duration::from_millis(100);
+
+    // Holding A and pressing B moves to the previous page, which wraps around to the last page
+    // (Sample, Chat, Console, Headphones). Chat is already on B, so only A, C and D are changed.
+    let script = Script::new()
+        .press(InteractiveButtons::Fader1Mute)
+        .wait(wait)
+        .press(InteractiveButtons::Fader2Mute)
+        .wait(wait)
+        .release(InteractiveButtons::Fader2Mute)
+        .release(InteractiveButtons::Fader1Mute)
+        .wait(wait);
+    daemon.device().run(&script).await;
+
+    let commands = daemon.settle().await;
+    assert_eq!(
+        with_opcode(&commands, 0x805),
+        vec![
+            command(0x805, 0, &[0x06, 0, 0, 0]),
+            command(0x805, 2, &[0x02, 0, 0, 0]),
+            command(0x805, 3, &[0x08, 0, 0, 0]),
+        ]
+    );
+
+    // Neither button should have performed its mute action
+    assert!(with_opcode(&commands, 0x809).is_empty());
+    assert_eq!(daemon.status().await?.config.device.pages.current, 2);
+
+    // Holding D and pressing C moves forward, back to the first page
+    let script = Script::new()
+        .press(InteractiveButtons::Fader4Mute)
+        .wait(wait)
+        .press(InteractiveButtons::Fader3Mute)
+        .wait(wait)
+        .release(InteractiveButtons::Fader3Mute)
+        .release(InteractiveButtons::Fader4Mute)
+        .wait(wait);
+    daemon.device().run(&script).await;
+
+    daemon.settle().await;
+    assert_eq!(daemon.status().await?.config.device.pages.current, 0);
+
+    daemon.stop().await
+}
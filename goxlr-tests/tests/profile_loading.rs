@@ -0,0 +1,139 @@
+use anyhow::Result;
+
+use goxlr_ipc::client::Client;
+use goxlr_ipc::commands::{DaemonCommand, ProfileType};
+use goxlr_profile::Profile;
+use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::channels::volume::VolumeChannels;
+use goxlr_shared::faders::Fader;
+use goxlr_shared::mute::MuteState;
+use goxlr_tests::{command, opcode_phases, route, with_opcode, TestDaemon};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn default_profile_load_order() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+
+    // Hardware Info, then each fader is assigned and styled, followed by the mute states (the
+    // mic mute is an effect key), volumes, sub mix assignments, button states, colours, the
+    // animation, routing and finally the mic.
+    let mut faders = vec![];
+    for _ in 0..4 {
+        faders.extend([0x805, 0x814, 0x802]);
+    }
+    let mut expected = vec![0x80f];
+    expected.extend(faders);
+    expected.extend([
+        0x801, 0x809, 0x806, 0x818, 0x817, 0x808, 0x803, 0x816, 0x804, 0x80b, 0x801,
+    ]);
+    assert_eq!(opcode_phases(&daemon.startup), expected);
+
+    // Page 1 should be Mic, Chat, Music, System
+    let faders = with_opcode(&daemon.startup, 0x805);
+    assert_eq!(
+        faders,
+        vec![
+            command(0x805, 0, &[0x00, 0, 0, 0]),
+            command(0x805, 1, &[0x05, 0, 0, 0]),
+            command(0x805, 2, &[0x07, 0, 0, 0]),
+            command(0x805, 3, &[0x03, 0, 0, 0]),
+        ]
+    );
+
+    // Everything is unmuted..
+    let mutes = with_opcode(&daemon.startup, 0x809);
+    assert_eq!(mutes.len(), 10);
+    assert!(mutes.iter().all(|mute| mute.body == [0]));
+
+    // Volumes should be sent with their sub mix volumes directly after
+    let volumes = with_opcode(&daemon.startup, 0x806);
+    assert_eq!(volumes[0], command(0x806, 0x00, &[255]));
+    assert_eq!(volumes[1], command(0x806, 0x10, &[255]));
+    assert_eq!(volumes[2], command(0x806, 0x05, &[128]));
+    assert_eq!(volumes[3], command(0x806, 0x15, &[128]));
+
+    // The Mic goes everywhere, Sample goes to the Chat Mic, and everything else just goes to the
+    // Headphones and Stream Mix. Each input is sent Left then Right.
+    let routing = with_opcode(&daemon.startup, 0x804);
+    assert_eq!(routing.len(), 16);
+    assert_eq!(routing[0], command(0x804, 0x02, &route(&[1, 5, 9, 13, 17])));
+    assert_eq!(
+        routing[1],
+        command(0x804, 0x03, &route(&[3, 7, 11, 15, 19]))
+    );
+
+    let sample = routing.iter().filter(|c| c.command & 0xFFF >= 0x10);
+    let sample: Vec<_> = sample.cloned().collect();
+    assert_eq!(sample[0], command(0x804, 0x10, &route(&[1, 5, 9])));
+    assert_eq!(sample[1], command(0x804, 0x11, &route(&[3, 7, 11])));
+
+    let status = daemon.status().await?;
+    assert_eq!(status.config.device.pages.current, 0);
+    assert_eq!(
+        status.config.device.channels.volumes[VolumeChannels::Chat],
+        128
+    );
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn stored_profile_is_applied() -> Result<()> {
+    let mut profile = Profile::default();
+    profile.pages.page_list[0].faders[Fader::A] = FaderChannels::Game;
+    profile.channels.volumes[VolumeChannels::Game] = 50;
+    profile.channels.configs[FaderChannels::Chat].mute_state = MuteState::Pressed;
+
+    let daemon = TestDaemon::start_with_profile(profile).await?;
+
+    // Game is currently assigned to the first fader
+    let faders = with_opcode(&daemon.startup, 0x805);
+    assert_eq!(faders[0], command(0x805, 0, &[0x04, 0, 0, 0]));
+
+    // Chat has no mute actions, so should be muted to all..
+    let mutes = with_opcode(&daemon.startup, 0x809);
+    assert!(mutes.contains(&command(0x809, 0x05, &[1])));
+    assert!(mutes.contains(&command(0x809, 0x04, &[0])));
+
+    let volumes = with_opcode(&daemon.startup, 0x806);
+    assert!(volumes.contains(&command(0x806, 0x04, &[50])));
+
+    let status = daemon.status().await?;
+    let channels = &status.config.device.channels;
+    assert_eq!(channels.volumes[VolumeChannels::Game], 50);
+    assert_eq!(
+        channels.configs[FaderChannels::Chat].mute_state,
+        MuteState::Pressed
+    );
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn active_profiles_are_protected() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+    let default = String::from("Default");
+
+    // The device hasn't saved anything yet, but is still running the Default profile..
+    let delete = DaemonCommand::DeleteProfile(ProfileType::Profile, default.clone());
+    let error = client.daemon(delete).await.unwrap_err().to_string();
+    assert!(error.contains("in use"), "{}", error);
+    let rename = DaemonCommand::RenameProfile(ProfileType::MicProfile, default, "Old".into());
+    let error = client.daemon(rename).await.unwrap_err().to_string();
+    assert!(error.contains("in use"), "{}", error);
+
+    // ..while profiles not in use can be changed freely
+    let name = String::from("Spare");
+    let create = DaemonCommand::CreateProfile(ProfileType::Profile, name.clone());
+    client.daemon(create).await?;
+    let rename = DaemonCommand::RenameProfile(ProfileType::Profile, name, "Unused".into());
+    client.daemon(rename).await?;
+    let delete = DaemonCommand::DeleteProfile(ProfileType::Profile, "Unused".into());
+    client.daemon(delete).await?;
+
+    client.poll_status().await?;
+    let profiles = &client.status().profiles;
+    assert!(!profiles.profiles.iter().any(|name| name == "Unused"));
+
+    daemon.stop().await
+}
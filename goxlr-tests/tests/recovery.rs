@@ -0,0 +1,59 @@
+use anyhow::Result;
+
+use goxlr_ipc::client::Client;
+use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::mute::MuteState;
+use goxlr_tests::{command, mute, with_opcode, TestDaemon};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn device_recovers_after_failure() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    let mute = mute(FaderChannels::Chat, MuteState::Pressed);
+    client.command(daemon.serial(), mute).await?;
+    daemon.settle().await;
+
+    // Fail both the next poll, and its retry after recovery, this should cause the device to be
+    // stopped and re-initialised by the device manager.
+    daemon.device().fail_requests(2);
+
+    // Wait for the Routing for Sample (Right) to be sent, which is the end of the profile load
+    let commands = daemon.wait_for_command(0x804 << 12 | 0x11).await?;
+
+    // The device should have been fully re-initialised, with the mute state preserved
+    let faders = with_opcode(&commands, 0x805);
+    assert_eq!(faders.len(), 4);
+    assert!(commands.contains(&command(0x809, 0x05, &[1])));
+
+    let status = daemon.wait_for_device().await;
+    assert!(status.is_ok());
+
+    let status = daemon.status().await?;
+    let state = status.config.device.channels.configs[FaderChannels::Chat].mute_state;
+    assert_eq!(state, MuteState::Pressed);
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn device_removal_and_reattach() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+
+    goxlr_usb::virtual_device::detach(daemon.serial());
+    let mut removed = false;
+    for _ in 0..100 {
+        if daemon.status().await.is_err() {
+            removed = true;
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    assert!(removed, "Device was not removed from the status");
+
+    goxlr_usb::virtual_device::attach(daemon.serial(), goxlr_shared::device::DeviceType::Full);
+    let commands = daemon.wait_for_device().await?;
+    assert_eq!(with_opcode(&commands, 0x805).len(), 4);
+
+    daemon.stop().await
+}
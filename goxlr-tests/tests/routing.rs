@@ -0,0 +1,331 @@
+use anyhow::Result;
+
+use goxlr_ipc::client::Client;
+use goxlr_ipc::commands::routing::{RoutingCommand, SetRoute, SetRouteLevel};
+use goxlr_ipc::commands::GoXLRCommand;
+use goxlr_profile::{Profile, Route, ROUTE_LEVEL_MAX};
+use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::channels::input::InputChannels;
+use goxlr_shared::channels::output::OutputChannels;
+use goxlr_shared::mute::MuteState;
+use goxlr_tests::{command, get_profile, mute, route, TestDaemon};
+
+fn routing(command: RoutingCommand) -> GoXLRCommand {
+    GoXLRCommand::Routing(command)
+}
+
+fn set_route(input: InputChannels, output: OutputChannels, enabled: bool) -> GoXLRCommand {
+    routing(RoutingCommand::SetRoute(SetRoute {
+        input,
+        output,
+        enabled,
+    }))
+}
+
+fn set_level(input: InputChannels, output: OutputChannels, level: u8) -> GoXLRCommand {
+    routing(RoutingCommand::SetLevel(SetRouteLevel {
+        input,
+        output,
+        level,
+    }))
+}
+
+/// Builds a SetRouting body with the given output positions enabled, at the given levels
+fn route_levels(positions: &[(usize, u8)]) -> Vec<u8> {
+    let mut body = route(&[]);
+    for (position, level) in positions {
+        body[*position] = *level;
+    }
+    body
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn single_route() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // Only the left and right rows for Game should be sent
+    let game_to_line_out = set_route(InputChannels::Game, OutputChannels::LineOut, true);
+    client
+        .command(daemon.serial(), game_to_line_out.clone())
+        .await?;
+    assert_eq!(
+        daemon.settle().await,
+        vec![
+            command(0x804, 0x0a, &route(&[1, 5, 17])),
+            command(0x804, 0x0b, &route(&[3, 7, 19])),
+        ]
+    );
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    assert!(profile.routing[InputChannels::Game][OutputChannels::LineOut].enabled);
+
+    // Setting it again shouldn't change anything..
+    client.command(daemon.serial(), game_to_line_out).await?;
+    assert!(daemon.settle().await.is_empty());
+
+    // ..while toggling it should turn it back off
+    let toggle = RoutingCommand::ToggleRoute(InputChannels::Game, OutputChannels::LineOut);
+    client.command(daemon.serial(), routing(toggle)).await?;
+    assert_eq!(
+        daemon.settle().await,
+        vec![
+            command(0x804, 0x0a, &route(&[1, 5])),
+            command(0x804, 0x0b, &route(&[3, 7])),
+        ]
+    );
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn unsupported_route() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // Chat can't be sent back to the Chat Mic, however it's requested
+    let chat = InputChannels::Chat;
+    let chat_mic = OutputChannels::ChatMic;
+    let commands = vec![
+        set_route(chat, chat_mic, true),
+        routing(RoutingCommand::ToggleRoute(chat, chat_mic)),
+        routing(RoutingCommand::SetInput(chat, vec![chat_mic])),
+        routing(RoutingCommand::SetOutput(chat_mic, vec![chat])),
+    ];
+    for command in commands {
+        assert!(client.command(daemon.serial(), command).await.is_err());
+    }
+    assert!(daemon.settle().await.is_empty());
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    assert!(!profile.routing[chat][chat_mic].enabled);
+
+    // Disabling it is harmless, as it's never on
+    client
+        .command(daemon.serial(), set_route(chat, chat_mic, false))
+        .await?;
+    assert!(daemon.settle().await.is_empty());
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn input_and_output_routing() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // Music should only be left on the Headphones
+    let outputs = vec![OutputChannels::Headphones];
+    let command_input = RoutingCommand::SetInput(InputChannels::Music, outputs);
+    client
+        .command(daemon.serial(), routing(command_input))
+        .await?;
+    assert_eq!(
+        daemon.settle().await,
+        vec![
+            command(0x804, 0x0e, &route(&[1])),
+            command(0x804, 0x0f, &route(&[3])),
+        ]
+    );
+
+    // Moving the Line Out from the Mic to Game and Chat should update all three inputs
+    let inputs = vec![InputChannels::Game, InputChannels::Chat];
+    let command_output = RoutingCommand::SetOutput(OutputChannels::LineOut, inputs);
+    client
+        .command(daemon.serial(), routing(command_output))
+        .await?;
+    assert_eq!(
+        daemon.settle().await,
+        vec![
+            command(0x804, 0x02, &route(&[1, 5, 9, 13])),
+            command(0x804, 0x03, &route(&[3, 7, 11, 15])),
+            command(0x804, 0x0c, &route(&[1, 5, 17])),
+            command(0x804, 0x0d, &route(&[3, 7, 19])),
+            command(0x804, 0x0a, &route(&[1, 5, 17])),
+            command(0x804, 0x0b, &route(&[3, 7, 19])),
+        ]
+    );
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    let music = profile.routing[InputChannels::Music];
+    assert!(music[OutputChannels::Headphones].enabled && !music[OutputChannels::StreamMix].enabled);
+
+    let line_out = |input| profile.routing[input][OutputChannels::LineOut].enabled;
+    assert!(!line_out(InputChannels::Microphone));
+    assert!(line_out(InputChannels::Game) && line_out(InputChannels::Chat));
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn routing_while_muted_to_target() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // Line In mutes to the Stream Mix, so that route should be removed..
+    client
+        .command(
+            daemon.serial(),
+            mute(FaderChannels::LineIn, MuteState::Pressed),
+        )
+        .await?;
+    daemon.settle().await;
+
+    // ..and should stay removed while other routes for Line In are changed
+    let line_out = set_route(InputChannels::LineIn, OutputChannels::LineOut, true);
+    client.command(daemon.serial(), line_out).await?;
+    assert_eq!(
+        daemon.settle().await,
+        vec![
+            command(0x804, 0x04, &route(&[1, 17])),
+            command(0x804, 0x05, &route(&[3, 19])),
+        ]
+    );
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    let line_in = profile.routing[InputChannels::LineIn];
+    assert!(line_in[OutputChannels::StreamMix].enabled && line_in[OutputChannels::LineOut].enabled);
+
+    // Unmuting should restore the Stream Mix, alongside the new route
+    client
+        .command(
+            daemon.serial(),
+            mute(FaderChannels::LineIn, MuteState::Unmuted),
+        )
+        .await?;
+    assert_eq!(
+        daemon.settle().await,
+        vec![
+            command(0x804, 0x04, &route(&[1, 5, 17])),
+            command(0x804, 0x05, &route(&[3, 7, 19])),
+        ]
+    );
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn route_levels_are_sent() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    let (music, stream_mix) = (InputChannels::Music, OutputChannels::StreamMix);
+    client
+        .command(daemon.serial(), set_level(music, stream_mix, 16))
+        .await?;
+    assert_eq!(
+        daemon.settle().await,
+        vec![
+            command(0x804, 0x0e, &route_levels(&[(1, 0x20), (5, 16)])),
+            command(0x804, 0x0f, &route_levels(&[(3, 0x20), (7, 16)])),
+        ]
+    );
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    assert_eq!(profile.routing[music][stream_mix].level, 16);
+
+    // Levels above full volume should be rejected
+    let level = ROUTE_LEVEL_MAX + 1;
+    let too_loud = set_level(music, stream_mix, level);
+    assert!(client.command(daemon.serial(), too_loud).await.is_err());
+    assert!(daemon.settle().await.is_empty());
+
+    // Setting a level on a disabled route is stored, but nothing is sent until it's enabled
+    let line_out = OutputChannels::LineOut;
+    client
+        .command(daemon.serial(), set_level(music, line_out, 8))
+        .await?;
+    assert!(daemon.settle().await.is_empty());
+
+    client
+        .command(daemon.serial(), set_route(music, line_out, true))
+        .await?;
+    assert_eq!(
+        daemon.settle().await,
+        vec![
+            command(0x804, 0x0e, &route_levels(&[(1, 0x20), (5, 16), (17, 8)])),
+            command(0x804, 0x0f, &route_levels(&[(3, 0x20), (7, 16), (19, 8)])),
+        ]
+    );
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn route_levels_restored_after_mute() -> Result<()> {
+    let mut profile = Profile::default();
+    profile.routing[InputChannels::LineIn][OutputChannels::StreamMix].level = 12;
+    let daemon = TestDaemon::start_with_profile(profile).await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // Line In mutes to the Stream Mix, which should remove the route entirely..
+    client
+        .command(
+            daemon.serial(),
+            mute(FaderChannels::LineIn, MuteState::Pressed),
+        )
+        .await?;
+    assert_eq!(
+        daemon.settle().await,
+        vec![
+            command(0x804, 0x04, &route(&[1])),
+            command(0x804, 0x05, &route(&[3])),
+        ]
+    );
+
+    // ..and unmuting should bring it back at its configured level, rather than full volume
+    client
+        .command(
+            daemon.serial(),
+            mute(FaderChannels::LineIn, MuteState::Unmuted),
+        )
+        .await?;
+    assert_eq!(
+        daemon.settle().await,
+        vec![
+            command(0x804, 0x04, &route_levels(&[(1, 0x20), (5, 12)])),
+            command(0x804, 0x05, &route_levels(&[(3, 0x20), (7, 12)])),
+        ]
+    );
+
+    daemon.stop().await
+}
+
+#[test]
+fn legacy_routing_format() -> Result<()> {
+    // Profiles saved before route levels were added store each route as a bool
+    let mut profile = serde_json::to_value(Profile::default())?;
+    for row in profile["routing"].as_object_mut().unwrap().values_mut() {
+        for route in row.as_object_mut().unwrap().values_mut() {
+            *route = route["enabled"].clone();
+        }
+    }
+
+    let profile: Profile = serde_json::from_value(profile)?;
+    let defaults = Profile::default();
+    for (input, row) in profile.routing {
+        for (output, route) in row {
+            assert_eq!(route.enabled, defaults.routing[input][output].enabled);
+            assert_eq!(route.level, ROUTE_LEVEL_MAX);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn route_level_decibels() {
+    // Levels are a linear gain, so halving the level is roughly -6 dB
+    let decibels = |level| Route::level_to_decibels(level).map(|db| (db * 10.0).round() / 10.0);
+    assert_eq!(decibels(ROUTE_LEVEL_MAX), Some(0.0));
+    assert_eq!(decibels(16), Some(-6.0));
+    assert_eq!(decibels(8), Some(-12.0));
+    assert_eq!(decibels(1), Some(-30.1));
+    assert_eq!(decibels(0), None);
+
+    assert_eq!(Route::level_from_decibels(-6.0), 16);
+    assert_eq!(Route::level_from_decibels(-12.0), 8);
+    assert_eq!(Route::level_from_decibels(0.0), ROUTE_LEVEL_MAX);
+    assert_eq!(Route::level_from_decibels(6.0), ROUTE_LEVEL_MAX);
+    assert_eq!(Route::level_from_decibels(-100.0), 0);
+    assert_eq!(Route::level_from_decibels(f64::NEG_INFINITY), 0);
+}
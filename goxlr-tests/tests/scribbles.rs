@@ -0,0 +1,296 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::Local;
+
+use goxlr_ipc::client::Client;
+use goxlr_ipc::commands::scribbles::ScribbleCommand;
+use goxlr_ipc::commands::GoXLRCommand;
+use goxlr_profile::{MuteAction, Profile};
+use goxlr_scribbles::render;
+use goxlr_shared::channels::fader::FaderChannels;
+use goxlr_shared::channels::mute::MuteActionChannels;
+use goxlr_shared::channels::output::OutputChannels;
+use goxlr_shared::channels::volume::VolumeChannels;
+use goxlr_shared::interaction::InteractiveFaders;
+use goxlr_shared::mute::MuteState;
+use goxlr_shared::scribbles::ScribbleContent;
+use goxlr_tests::{get_profile, mute, opcode_phases, volume, with_opcode, TestDaemon};
+use goxlr_usb::virtual_device::CommandRecord;
+
+fn scribble(command: ScribbleCommand) -> GoXLRCommand {
+    GoXLRCommand::Scribbles(command)
+}
+
+/// Returns the text of every scribble sent to a fader, by matching it against a rendering
+fn scribble_texts(commands: &[CommandRecord], fader: u32, texts: &[&str]) -> Vec<String> {
+    with_opcode(commands, 0x802)
+        .iter()
+        .filter(|record| record.command & 0xFFF == fader)
+        .map(|record| {
+            let text = texts.iter().find(|text| {
+                let rendered = render(None, Some(text), None, false).to_bytes();
+                record.body == rendered
+            });
+            text.map_or_else(|| String::from("<unknown>"), |text| text.to_string())
+        })
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn only_the_assigned_fader_is_rendered() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // Every fader gets its scribble drawn during startup
+    let scribbles = with_opcode(&daemon.startup, 0x802);
+    let faders: Vec<u32> = scribbles.iter().map(|c| c.command & 0xFFF).collect();
+    assert_eq!(faders, vec![0, 1, 2, 3]);
+
+    // Music is on Fader C, so only that scribble should be redrawn
+    let commands = [
+        ScribbleCommand::Text(FaderChannels::Music, Some(String::from("Tunes"))),
+        ScribbleCommand::Label(FaderChannels::Music, Some('M')),
+        ScribbleCommand::Inverted(FaderChannels::Music, true),
+    ];
+    for command in commands {
+        client.command(daemon.serial(), scribble(command)).await?;
+        let commands = daemon.settle().await;
+        assert_eq!(opcode_phases(&commands), vec![0x802]);
+        assert_eq!(commands[0].command & 0xFFF, 2);
+    }
+
+    // Game isn't assigned, so nothing is sent, but the profile should still be updated
+    let text = ScribbleCommand::Text(FaderChannels::Game, None);
+    client.command(daemon.serial(), scribble(text)).await?;
+    assert!(daemon.settle().await.is_empty());
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    let music = &profile.channels.configs[FaderChannels::Music].display;
+    assert_eq!(music.screen_display.text.as_deref(), Some("Tunes"));
+    assert_eq!(music.screen_display.label, Some('M'));
+    assert!(music.screen_display.inverted);
+
+    let game = &profile.channels.configs[FaderChannels::Game].display;
+    assert_eq!(game.screen_display.text, None);
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn scribble_images() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // Icons which don't exist should be rejected
+    let missing = ScribbleCommand::Image(FaderChannels::Chat, Some("missing.png".into()));
+    assert!(client
+        .command(daemon.serial(), scribble(missing))
+        .await
+        .is_err());
+    assert!(daemon.settle().await.is_empty());
+
+    let icon = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/icons/square.png");
+    let image = ScribbleCommand::Image(FaderChannels::Chat, Some(icon.clone()));
+    client.command(daemon.serial(), scribble(image)).await?;
+    let commands = daemon.settle().await;
+    assert_eq!(opcode_phases(&commands), vec![0x802]);
+    assert_eq!(commands[0].command & 0xFFF, 1);
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    let chat = &profile.channels.configs[FaderChannels::Chat].display;
+    assert_eq!(chat.screen_display.image, Some(icon));
+
+    // And it can be cleared again
+    let clear = ScribbleCommand::Image(FaderChannels::Chat, None);
+    client.command(daemon.serial(), scribble(clear)).await?;
+    assert_eq!(opcode_phases(&daemon.settle().await), vec![0x802]);
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn volume_content() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+    let texts = ["0%", "50%", "100%", "-inf dB", "-6.0 dB", "0.0 dB"];
+
+    // Music is on Fader C
+    client
+        .command(daemon.serial(), volume(VolumeChannels::Music, 255))
+        .await?;
+    daemon.settle().await;
+
+    let content = ScribbleCommand::Content(FaderChannels::Music, ScribbleContent::VolumePercent);
+    client.command(daemon.serial(), scribble(content)).await?;
+    let commands = daemon.settle().await;
+    assert_eq!(scribble_texts(&commands, 2, &texts), vec!["100%"]);
+
+    client
+        .command(daemon.serial(), volume(VolumeChannels::Music, 0))
+        .await?;
+    let commands = daemon.settle().await;
+    assert_eq!(scribble_texts(&commands, 2, &texts), vec!["0%"]);
+
+    // Setting the same volume doesn't change the image, so nothing should be sent
+    client
+        .command(daemon.serial(), volume(VolumeChannels::Music, 0))
+        .await?;
+    assert!(with_opcode(&daemon.settle().await, 0x802).is_empty());
+
+    client
+        .command(daemon.serial(), volume(VolumeChannels::Music, 128))
+        .await?;
+    let commands = daemon.settle().await;
+    assert_eq!(scribble_texts(&commands, 2, &texts), vec!["50%"]);
+
+    // Decibels treat the volume as a linear gain, so half volume is roughly -6 dB
+    let content = ScribbleCommand::Content(FaderChannels::Music, ScribbleContent::VolumeDecibels);
+    client.command(daemon.serial(), scribble(content)).await?;
+    let commands = daemon.settle().await;
+    assert_eq!(scribble_texts(&commands, 2, &texts), vec!["-6.0 dB"]);
+
+    for (value, text) in [(0, "-inf dB"), (255, "0.0 dB")] {
+        client
+            .command(daemon.serial(), volume(VolumeChannels::Music, value))
+            .await?;
+        let commands = daemon.settle().await;
+        assert_eq!(scribble_texts(&commands, 2, &texts), vec![text]);
+    }
+
+    // The content should be stored in the profile
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    let screen = &profile.channels.configs[FaderChannels::Music]
+        .display
+        .screen_display;
+    assert_eq!(screen.content, ScribbleContent::VolumeDecibels);
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn moving_a_fader_is_rate_limited() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    let content = ScribbleCommand::Content(FaderChannels::Chat, ScribbleContent::VolumePercent);
+    client.command(daemon.serial(), scribble(content)).await?;
+    daemon.settle().await;
+
+    // Sweep Fader B from the bottom to the middle
+    let moves = 20;
+    for step in 0..=moves {
+        let position = (step * 128 / moves) as u8;
+        daemon.device().set_fader(InteractiveFaders::B, position);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let commands = daemon.settle().await;
+
+    // We should get fewer updates than moves, but the last one must match the final position
+    let scribbles = with_opcode(&commands, 0x802);
+    assert!(!scribbles.is_empty() && scribbles.len() < moves);
+    assert_eq!(
+        scribble_texts(&scribbles[scribbles.len() - 1..], 1, &["50%"]),
+        vec!["50%"]
+    );
+
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    assert_eq!(profile.channels.volumes[VolumeChannels::Chat], 128);
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn mute_state_content() -> Result<()> {
+    let mut profile = Profile::default();
+    let actions = &mut profile.channels.mute_actions[MuteActionChannels::Music].actions;
+    actions[MuteAction::Press] = vec![OutputChannels::StreamMix];
+    actions[MuteAction::Hold] = vec![];
+
+    let screen = &mut profile.channels.configs[FaderChannels::Music]
+        .display
+        .screen_display;
+    screen.text = Some(String::from("Music"));
+    screen.content = ScribbleContent::MuteState;
+
+    let daemon = TestDaemon::start_with_profile(profile).await?;
+    let mut client = daemon.ipc_client().await?;
+    let texts = ["Music", "MUTED", "MUTED TO STREAM"];
+
+    // While unmuted, the configured text is shown
+    assert_eq!(scribble_texts(&daemon.startup, 2, &texts), vec!["Music"]);
+
+    let states = [
+        (MuteState::Pressed, "MUTED TO STREAM"),
+        (MuteState::Held, "MUTED"),
+        (MuteState::Unmuted, "Music"),
+    ];
+    for (state, text) in states {
+        client
+            .command(daemon.serial(), mute(FaderChannels::Music, state))
+            .await?;
+        let commands = daemon.settle().await;
+        assert_eq!(scribble_texts(&commands, 2, &texts), vec![text]);
+    }
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn clock_content() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+
+    // The minute may tick over while we're waiting, so either side of it is fine
+    let before = Local::now().format("%H:%M").to_string();
+    let content = ScribbleCommand::Content(FaderChannels::Music, ScribbleContent::Clock);
+    client.command(daemon.serial(), scribble(content)).await?;
+    let commands = daemon.settle().await;
+    let after = Local::now().format("%H:%M").to_string();
+
+    let texts = scribble_texts(&commands, 2, &[&before, &after]);
+    assert!(!texts.is_empty());
+    assert!(!texts.contains(&String::from("<unknown>")), "{:?}", texts);
+
+    // Nothing more should be sent while the time stays the same
+    let commands = daemon.settle().await;
+    if Local::now().format("%H:%M").to_string() == after {
+        assert!(with_opcode(&commands, 0x802).is_empty());
+    }
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pushed_text() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+    let texts = ["Chat", "On Air"];
+
+    // Chat is on Fader B, without any pushed text the configured text is used, so switching
+    // the content doesn't change what's on the screen.
+    assert_eq!(scribble_texts(&daemon.startup, 1, &texts), vec!["Chat"]);
+    let content = ScribbleCommand::Content(FaderChannels::Chat, ScribbleContent::Custom);
+    client.command(daemon.serial(), scribble(content)).await?;
+    assert!(daemon.settle().await.is_empty());
+
+    let push = ScribbleCommand::PushText(FaderChannels::Chat, Some(String::from("On Air")));
+    client.command(daemon.serial(), scribble(push)).await?;
+    let commands = daemon.settle().await;
+    assert_eq!(scribble_texts(&commands, 1, &texts), vec!["On Air"]);
+
+    let clear = ScribbleCommand::PushText(FaderChannels::Chat, None);
+    client.command(daemon.serial(), scribble(clear)).await?;
+    let commands = daemon.settle().await;
+    assert_eq!(scribble_texts(&commands, 1, &texts), vec!["Chat"]);
+
+    // Pushed text is transient, so it's not stored in the profile
+    let profile = get_profile(&mut client, daemon.serial()).await?;
+    let screen = &profile.channels.configs[FaderChannels::Chat]
+        .display
+        .screen_display;
+    assert_eq!(screen.text.as_deref(), Some("Chat"));
+
+    daemon.stop().await
+}
@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use anyhow::Result;
+
+use goxlr_shared::interaction::{InteractiveButtons, InteractiveEncoders, InteractiveFaders};
+use goxlr_usb::virtual_device::Script;
+
+// None of the inputs implement PartialEq, so scripts are compared by their Debug output
+fn assert_same(parsed: &Script, expected: &Script) {
+    let parsed = format!("{:?}", parsed.steps());
+    let expected = format!("{:?}", expected.steps());
+    assert_eq!(parsed, expected);
+}
+
+fn parse_error(script: &str) -> String {
+    match Script::parse(script) {
+        Ok(script) => panic!("Script should have failed: {:?}", script.steps()),
+        Err(error) => format!("{:#}", error),
+    }
+}
+
+#[test]
+fn parse_script() -> Result<()> {
+    let script = "
+        hold Fader1Mute 600
+        press Fader2Mute
+        wait 100
+        release Fader2Mute
+        fader A 128
+        encoder Pitch 12
+        turn Reverb -3
+    ";
+
+    let expected = Script::new()
+        .hold(InteractiveButtons::Fader1Mute, Duration::from_millis(600))
+        .press(InteractiveButtons::Fader2Mute)
+        .wait(Duration::from_millis(100))
+        .release(InteractiveButtons::Fader2Mute)
+        .fader(InteractiveFaders::A, 128)
+        .encoder(InteractiveEncoders::Pitch, 12)
+        .turn(InteractiveEncoders::Reverb, -3);
+    assert_same(&Script::parse(script)?, &expected);
+
+    // Names aren't case sensitive
+    let expected = Script::new().press(InteractiveButtons::Fader1Mute);
+    assert_same(&Script::parse("press fader1mute")?, &expected);
+
+    Ok(())
+}
+
+#[test]
+fn parse_comments_and_blank_lines() -> Result<()> {
+    let script = "
+        # Nothing on this line, or the next, should become an input
+
+        press Fader1Mute
+            # Indented comments are fine too
+        release Fader1Mute
+    ";
+
+    let expected = Script::new()
+        .press(InteractiveButtons::Fader1Mute)
+        .release(InteractiveButtons::Fader1Mute);
+    assert_same(&Script::parse(script)?, &expected);
+
+    // A script with nothing in it is still valid
+    assert!(Script::parse("# Empty\n\n")?.steps().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn parse_unknown_names() {
+    let error = parse_error("press Fader1Mute\npress Fader9Mute");
+    assert!(error.contains("line 2"), "{}", error);
+    assert!(error.contains("Unknown Value: Fader9Mute"), "{}", error);
+
+    let error = parse_error("turn Volume 3");
+    assert!(error.contains("Unknown Value: Volume"), "{}", error);
+
+    let error = parse_error("encoder Pitch 12\nfader E 100");
+    assert!(error.contains("line 2"), "{}", error);
+    assert!(error.contains("Unknown Value: E"), "{}", error);
+
+    let error = parse_error("tap Fader1Mute");
+    assert!(error.contains("Unknown Instruction"), "{}", error);
+}
+
+#[test]
+fn parse_invalid_values() {
+    // Durations must be a whole, positive number of milliseconds
+    for duration in ["-100", "1.5", "100ms", "soon"] {
+        let error = parse_error(&format!("wait {}", duration));
+        assert!(error.contains("line 1: wait"), "{}", error);
+
+        let error = parse_error(&format!("hold Fader1Mute {}", duration));
+        assert!(error.contains("line 1: hold"), "{}", error);
+    }
+
+    // A missing duration doesn't match any instruction
+    let error = parse_error("wait");
+    assert!(error.contains("Unknown Instruction"), "{}", error);
+
+    // Values out of range for the fader and encoder
+    assert!(Script::parse("fader A 256").is_err());
+    assert!(Script::parse("encoder Pitch 128").is_err());
+    assert!(Script::parse("turn Echo -129").is_err());
+}
@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::{sleep, timeout};
+
+use goxlr_ipc::client::Client;
+use goxlr_ipc::commands::{DaemonCommand, HttpSettings, LogLevel};
+use goxlr_tests::TestDaemon;
+
+const ALLOW_ORIGIN: &str = "access-control-allow-origin";
+
+/// Makes a plain HTTP request with the given Origin, returning the status line and headers
+async fn request(daemon: &TestDaemon, origin: &str) -> Result<String> {
+    let mut stream = TcpStream::connect(("127.0.0.1", daemon.http_port())).await?;
+    let request = format!(
+        "GET /api/get-devices HTTP/1.1\r\nHost: 127.0.0.1\r\nOrigin: {}\r\nConnection: close\r\n\r\n",
+        origin
+    );
+    stream.write_all(request.as_bytes()).await?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await?;
+    let head = response.split("\r\n\r\n").next().unwrap_or_default();
+    Ok(head.to_lowercase())
+}
+
+async fn set_cors(daemon: &TestDaemon, enabled: bool) -> Result<()> {
+    let mut client = daemon.ipc_client().await?;
+    let settings = HttpSettings {
+        enabled: true,
+        bind_address: String::from("127.0.0.1"),
+        cors_enabled: enabled,
+        port: daemon.http_port(),
+    };
+    client
+        .daemon(DaemonCommand::SetHttpSettings(settings))
+        .await?;
+
+    // The change is applied once the settings watcher has seen the file change
+    timeout(Duration::from_secs(10), async {
+        loop {
+            let response = request(daemon, "http://localhost").await?;
+            if response.contains(ALLOW_ORIGIN) == enabled {
+                return Ok(());
+            }
+            sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await?
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cors_toggled_while_running() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+
+    // With CORS disabled, the middleware shouldn't be involved at all, so any origin is served
+    // without CORS headers
+    for origin in ["http://127.0.0.1", "http://localhost", "http://example.com"] {
+        let response = request(&daemon, origin).await?;
+        assert!(response.starts_with("http/1.1 200"), "{}", response);
+        assert!(!response.contains(ALLOW_ORIGIN), "{}", response);
+    }
+
+    // Once enabled, only local origins are allowed
+    set_cors(&daemon, true).await?;
+    let response = request(&daemon, "http://127.0.0.1:8080").await?;
+    assert!(response.contains("access-control-allow-origin: http://127.0.0.1:8080"));
+    let response = request(&daemon, "http://example.com").await?;
+    assert!(!response.contains(ALLOW_ORIGIN), "{}", response);
+
+    // And disabling it again removes the headers for local origins too
+    set_cors(&daemon, false).await?;
+    let response = request(&daemon, "http://127.0.0.1:8080").await?;
+    assert!(response.starts_with("http/1.1 200"), "{}", response);
+    assert!(!response.contains(ALLOW_ORIGIN), "{}", response);
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn overridden_settings_are_refused() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+    let original = std::fs::read(daemon.settings_path())?;
+
+    // The port is set on the command line, so changing it would never take effect
+    let settings = HttpSettings {
+        enabled: true,
+        bind_address: String::from("127.0.0.1"),
+        cors_enabled: false,
+        port: daemon.http_port() + 1,
+    };
+    let result = client
+        .daemon(DaemonCommand::SetHttpSettings(settings))
+        .await;
+    let error = result.unwrap_err().to_string();
+    assert!(error.contains("http.port"), "{}", error);
+    assert_eq!(std::fs::read(daemon.settings_path())?, original);
+
+    // The log level isn't, so that should be written out, without leaving anything behind
+    client
+        .daemon(DaemonCommand::SetLogLevel(LogLevel::Warn))
+        .await?;
+    let content = std::fs::read(daemon.settings_path())?;
+    let settings: serde_json::Value = serde_json::from_slice(&content)?;
+    assert_eq!(settings["log_level"], "Warn");
+
+    let directory = daemon.settings_path().parent().unwrap().to_path_buf();
+    for entry in std::fs::read_dir(directory)? {
+        let name = entry?.file_name();
+        assert!(!name.to_string_lossy().ends_with(".tmp"), "{:?}", name);
+    }
+
+    daemon.stop().await
+}
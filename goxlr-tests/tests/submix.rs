@@ -0,0 +1,215 @@
+use anyhow::Result;
+
+use goxlr_ipc::client::Client;
+use goxlr_ipc::commands::channels::{ChannelCommands, OutputMix, SubMix, SubMixCommands};
+use goxlr_ipc::commands::GoXLRCommand;
+use goxlr_shared::channels::output::OutputChannels;
+use goxlr_shared::channels::sub_mix::SubMixChannels;
+use goxlr_shared::channels::volume::VolumeChannels;
+use goxlr_shared::submix::Mix;
+use goxlr_tests::{command, get_profile, opcode_phases, volume, TestDaemon};
+
+fn output_mix(output: OutputChannels, mix: Mix) -> GoXLRCommand {
+    GoXLRCommand::Channels(ChannelCommands::OutputMix(OutputMix { output, mix }))
+}
+
+fn monitor_mix(mix: Option<Mix>) -> GoXLRCommand {
+    GoXLRCommand::Channels(ChannelCommands::MonitorMix(mix))
+}
+
+fn sub_mix_enabled(enabled: bool) -> GoXLRCommand {
+    GoXLRCommand::Channels(ChannelCommands::SubMixEnabled(enabled))
+}
+
+fn sub_mix(channel: SubMixChannels, command: SubMixCommands) -> GoXLRCommand {
+    GoXLRCommand::Channels(ChannelCommands::SubMix(SubMix { channel, command }))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn linked_sub_mix_follows_volume() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+    let serial = daemon.serial();
+
+    // Unlink the Chat mix so it can be given its own volume
+    let linked = SubMixCommands::Linked(false);
+    client
+        .command(serial, sub_mix(SubMixChannels::Chat, linked))
+        .await?;
+    let volume_command = SubMixCommands::Volume(64);
+    client
+        .command(serial, sub_mix(SubMixChannels::Chat, volume_command))
+        .await?;
+
+    let commands = daemon.settle().await;
+    assert_eq!(commands, vec![command(0x806, 0x15, &[64])]);
+
+    // While unlinked, changing the channel volume shouldn't touch the mix
+    client
+        .command(serial, volume(VolumeChannels::Chat, 100))
+        .await?;
+    let commands = daemon.settle().await;
+    assert_eq!(commands, vec![command(0x806, 0x05, &[100])]);
+
+    // Linking now should store the ratio between the mix and the channel..
+    let linked = SubMixCommands::Linked(true);
+    client
+        .command(serial, sub_mix(SubMixChannels::Chat, linked))
+        .await?;
+    daemon.settle().await;
+
+    let profile = get_profile(&mut client, serial).await?;
+    let mix = &profile.channels.sub_mix[SubMixChannels::Chat];
+    assert_eq!(mix.linked, Some(0.64));
+
+    // ..and apply it when the channel volume changes
+    client
+        .command(serial, volume(VolumeChannels::Chat, 200))
+        .await?;
+    let commands = daemon.settle().await;
+    assert_eq!(
+        commands,
+        vec![command(0x806, 0x05, &[200]), command(0x806, 0x15, &[128])]
+    );
+
+    let profile = get_profile(&mut client, serial).await?;
+    assert_eq!(profile.channels.volumes[VolumeChannels::Chat], 200);
+    assert_eq!(profile.channels.sub_mix[SubMixChannels::Chat].volume, 128);
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn output_mix_assignment() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+    let serial = daemon.serial();
+
+    // Everything starts on Mix A, with the headphones monitoring it
+    assert!(daemon.startup.contains(&command(0x818, 0, &[0])));
+    let mixes = [2, 4, 6, 8, 12, 12, 12, 12];
+    assert!(daemon.startup.contains(&command(0x817, 0, &mixes)));
+
+    client
+        .command(serial, output_mix(OutputChannels::StreamMix, Mix::B))
+        .await?;
+    let mixes = [12, 4, 6, 8, 2, 12, 12, 12];
+    assert_eq!(
+        daemon.settle().await,
+        vec![command(0x818, 0, &[0]), command(0x817, 0, &mixes)]
+    );
+
+    // The Headphones aren't in the mix list, their assignment is the monitored mix
+    client
+        .command(serial, output_mix(OutputChannels::Headphones, Mix::B))
+        .await?;
+    assert_eq!(
+        daemon.settle().await,
+        vec![command(0x818, 0, &[1]), command(0x817, 0, &mixes)]
+    );
+
+    let profile = get_profile(&mut client, serial).await?;
+    assert_eq!(
+        profile.outputs[OutputChannels::StreamMix].mix_assignment,
+        Mix::B
+    );
+    assert_eq!(
+        profile.outputs[OutputChannels::Headphones].mix_assignment,
+        Mix::B
+    );
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn monitor_mix_override() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+    let serial = daemon.serial();
+    let mixes = [2, 4, 6, 8, 12, 12, 12, 12];
+
+    // Overriding the monitor ignores the Headphones' assignment..
+    client.command(serial, monitor_mix(Some(Mix::B))).await?;
+    assert_eq!(
+        daemon.settle().await,
+        vec![command(0x817, 0, &mixes), command(0x818, 0, &[1])]
+    );
+
+    client
+        .command(serial, output_mix(OutputChannels::Headphones, Mix::A))
+        .await?;
+    assert_eq!(
+        daemon.settle().await,
+        vec![command(0x817, 0, &mixes), command(0x818, 0, &[1])]
+    );
+
+    let profile = get_profile(&mut client, serial).await?;
+    assert_eq!(profile.sub_mix.monitor_mix, Some(Mix::B));
+
+    // ..until it's cleared, at which point it follows them again
+    client.command(serial, monitor_mix(None)).await?;
+    assert_eq!(
+        daemon.settle().await,
+        vec![command(0x818, 0, &[0]), command(0x817, 0, &mixes)]
+    );
+
+    daemon.stop().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn disabling_sub_mixes() -> Result<()> {
+    let daemon = TestDaemon::start().await?;
+    let mut client = daemon.ipc_client().await?;
+    let serial = daemon.serial();
+
+    client
+        .command(serial, output_mix(OutputChannels::StreamMix, Mix::B))
+        .await?;
+    client.command(serial, monitor_mix(Some(Mix::B))).await?;
+    daemon.settle().await;
+
+    // Disabling should put everything back onto Mix A, ignoring the monitor override
+    client.command(serial, sub_mix_enabled(false)).await?;
+    let mixes = [2, 4, 6, 8, 12, 12, 12, 12];
+    assert_eq!(
+        daemon.settle().await,
+        vec![command(0x818, 0, &[0]), command(0x817, 0, &mixes)]
+    );
+
+    let status = daemon.status().await?;
+    let state = &status.state.sub_mix;
+    assert_eq!(state.configured[OutputChannels::StreamMix], Mix::B);
+    assert_eq!(state.effective[OutputChannels::StreamMix], Mix::A);
+    assert_eq!(state.monitor_mix, Mix::A);
+
+    // Mix B volumes are stored, but not sent while disabled..
+    let linked = SubMixCommands::Linked(false);
+    client
+        .command(serial, sub_mix(SubMixChannels::Chat, linked))
+        .await?;
+    let volume_command = SubMixCommands::Volume(64);
+    client
+        .command(serial, sub_mix(SubMixChannels::Chat, volume_command))
+        .await?;
+    assert!(daemon.settle().await.is_empty());
+
+    // ..and pushed along with the saved assignments when re-enabled
+    client.command(serial, sub_mix_enabled(true)).await?;
+    let commands = daemon.settle().await;
+    assert_eq!(opcode_phases(&commands), vec![0x817, 0x818, 0x806]);
+    assert!(commands.contains(&command(0x817, 0, &[12, 4, 6, 8, 2, 12, 12, 12])));
+    assert!(commands.contains(&command(0x818, 0, &[1])));
+    assert!(commands.contains(&command(0x806, 0x15, &[64])));
+
+    let status = daemon.status().await?;
+    let state = &status.state.sub_mix;
+    assert_eq!(state.effective[OutputChannels::StreamMix], Mix::B);
+    assert_eq!(state.monitor_mix, Mix::B);
+    assert!(status.config.device.sub_mix.enabled);
+
+    // The headphones are still assigned to Mix A, but are monitoring Mix B
+    assert_eq!(state.configured[OutputChannels::Headphones], Mix::A);
+    assert_eq!(state.effective[OutputChannels::Headphones], Mix::B);
+
+    daemon.stop().await
+}
@@ -19,7 +19,8 @@ use goxlr_shared::states::ButtonDisplayStates;
 use goxlr_shared::version::{FirmwareVersions, VersionNumber};
 
 use crate::common::executor::ExecutableGoXLR;
-use crate::goxlr::commands::{Command, HardwareInfoCommand};
+use crate::goxlr::commands::{Command, FirmwareAction, FirmwareCommand, HardwareInfoCommand};
+use crate::types::animation::{DeviceAnimationMode, DeviceWaterfallDirection};
 use crate::types::buttons::{CurrentButtonStates, DeviceButton};
 use crate::types::channels::{ChannelList, ChannelState, MixOutputChannel};
 use crate::types::colours::ColourStruct;
@@ -236,6 +237,21 @@ pub(crate) trait GoXLRCommands: ExecutableGoXLR {
         Ok(())
     }
 
+    async fn set_animation_mode(
+        &mut self,
+        mode: DeviceAnimationMode,
+        mod1: u8,
+        mod2: u8,
+        waterfall: DeviceWaterfallDirection,
+    ) -> Result<()> {
+        // The first byte enables animations, which we only do if there's one to display
+        let enabled = !matches!(mode, DeviceAnimationMode::None) as u8;
+        let data = [enabled, mode as u8, mod1, mod2, waterfall as u8, 0, 0, 0];
+
+        self.request_data(Command::SetAnimationMode, &data).await?;
+        Ok(())
+    }
+
     async fn set_scribble(&mut self, fader: Fader, data: [u8; 1024]) -> Result<()> {
         let command = Command::SetScribble(fader.into());
         self.request_data(command, &data).await?;
@@ -340,4 +356,85 @@ pub(crate) trait GoXLRCommands: ExecutableGoXLR {
 
         Ok(())
     }
+
+    /// Firmware Update Stuff, see handlers/firmware.rs for how these fit together
+    async fn begin_firmware_update(&mut self) -> Result<()> {
+        let command = Command::ExecuteFirmwareUpdate(FirmwareCommand::START);
+        self.request_data(command, &[]).await?;
+        Ok(())
+    }
+
+    async fn erase_firmware_partition(&mut self, size: u32) -> Result<()> {
+        let mut data = [0; 4];
+        LittleEndian::write_u32(&mut data, size);
+
+        let command = Command::ExecuteFirmwareUpdateAction(FirmwareAction::ERASE);
+        self.request_data(command, &data).await?;
+        Ok(())
+    }
+
+    async fn poll_firmware_erase(&mut self) -> Result<u8> {
+        let command = Command::ExecuteFirmwareUpdateAction(FirmwareAction::POLL);
+        let result = self.request_data(command, &[]).await?;
+        match result.first() {
+            Some(progress) => Ok(*progress),
+            None => bail!("Empty response from Erase Poll"),
+        }
+    }
+
+    async fn send_firmware_chunk(&mut self, offset: u32, chunk: &[u8]) -> Result<()> {
+        let mut data = Vec::with_capacity(chunk.len() + 8);
+        let mut cursor = Cursor::new(&mut data);
+        cursor.write_u32::<LittleEndian>(offset)?;
+        cursor.write_u32::<LittleEndian>(chunk.len() as u32)?;
+        data.extend_from_slice(chunk);
+
+        let command = Command::ExecuteFirmwareUpdateAction(FirmwareAction::SEND);
+        self.request_data(command, &data).await?;
+        Ok(())
+    }
+
+    async fn get_firmware_checksum(&mut self, offset: u32, length: u32) -> Result<u32> {
+        let mut data = [0; 8];
+        LittleEndian::write_u32(&mut data[0..4], offset);
+        LittleEndian::write_u32(&mut data[4..8], length);
+
+        let command = Command::ExecuteFirmwareUpdateAction(FirmwareAction::VALIDATE);
+        let result = self.request_data(command, &data).await?;
+        Ok(Cursor::new(result).read_u32::<LittleEndian>()?)
+    }
+
+    /// Used for both VERIFY and FINALISE, which take the image size, and are then polled
+    async fn run_firmware_command(&mut self, sub: FirmwareCommand, size: u32) -> Result<()> {
+        let mut data = [0; 4];
+        LittleEndian::write_u32(&mut data, size);
+
+        self.request_data(Command::ExecuteFirmwareUpdate(sub), &data)
+            .await?;
+        Ok(())
+    }
+
+    /// Returns the (state, done, total) of the current VERIFY or FINALISE
+    async fn poll_firmware_command(&mut self) -> Result<(u32, u32, u32)> {
+        let command = Command::ExecuteFirmwareUpdate(FirmwareCommand::POLL);
+        let result = self.request_data(command, &[]).await?;
+
+        let mut cursor = Cursor::new(result);
+        let state = cursor.read_u32::<LittleEndian>()?;
+        let done = cursor.read_u32::<LittleEndian>()?;
+        let total = cursor.read_u32::<LittleEndian>()?;
+        Ok((state, done, total))
+    }
+
+    async fn abort_firmware_update(&mut self) -> Result<()> {
+        let command = Command::ExecuteFirmwareUpdate(FirmwareCommand::ABORT);
+        self.request_data(command, &[]).await?;
+        Ok(())
+    }
+
+    async fn reboot_after_firmware_update(&mut self) -> Result<()> {
+        let command = Command::ExecuteFirmwareUpdate(FirmwareCommand::REBOOT);
+        self.request_data(command, &[]).await?;
+        Ok(())
+    }
 }
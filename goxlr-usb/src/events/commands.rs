@@ -1,20 +1,23 @@
 use anyhow::Result;
 use enum_map::EnumMap;
+use goxlr_shared::animation::{AnimationMode, WaterfallDirection};
 use goxlr_shared::channels::fader::FaderChannels;
 use goxlr_shared::channels::input::InputChannels;
 use goxlr_shared::channels::output::{OutputChannels, RoutingOutput};
 use goxlr_shared::channels::sub_mix::SubMixChannels;
 use goxlr_shared::channels::volume::VolumeChannels;
 use ritelinked::LinkedHashMap;
-use tokio::sync::oneshot;
+use tokio::sync::{mpsc, oneshot};
 
 use goxlr_shared::colours::{ColourScheme, FaderDisplayMode};
 use goxlr_shared::faders::Fader;
+use goxlr_shared::firmware::FirmwareUpdateStatus;
 use goxlr_shared::interaction::CurrentStates;
 use goxlr_shared::microphone::{MicEffectKeys, MicParamKeys, MicrophoneType};
 use goxlr_shared::mute::ChannelMuteState;
 use goxlr_shared::routing::RouteValue;
 use goxlr_shared::states::ButtonDisplayStates;
+use goxlr_shared::submix::Mix;
 
 /// This is a helper enum for commands that will simply return a Result<()> with no additional
 /// data, it helps simplify wrapping these type of commands together.
@@ -28,10 +31,12 @@ pub enum BasicResultCommand {
     SetFaderStyle(Fader, Vec<FaderDisplayMode>),
     SetButtonStates(ButtonDisplayStates),
     SetScribble(Fader, [u8; 1024]),
+    SetAnimation(AnimationMode, u8, u8, WaterfallDirection),
 
     /// SubMix Stuff
     SetSubMixVolume(SubMixChannels, u8),
     SetSubMixMix(Vec<OutputChannels>, Vec<OutputChannels>),
+    SetMonitorMix(Mix),
 
     /// Mic Stuff
     SetMicGain(MicrophoneType, u8),
@@ -44,4 +49,8 @@ pub enum CommandSender {
     GetButtonStates(oneshot::Sender<Result<CurrentStates>>),
     GetMicLevel(oneshot::Sender<Result<f64>>),
     BasicResultCommand(BasicResultCommand, oneshot::Sender<Result<()>>),
+
+    /// Flashes a firmware image to the device, progress is sent to the provided channel until
+    /// the update is either Complete or Failed. The device will be restarted afterwards.
+    UpdateFirmware(Vec<u8>, mpsc::Sender<FirmwareUpdateStatus>),
 }
@@ -0,0 +1,269 @@
+/*
+   Handles updating the firmware on the GoXLR. This is performed as a series of stages, each of
+   which needs to complete before moving onto the next:
+     Start    - Puts the GoXLR into update mode (it'll go green)
+     Erase    - Erases the update partition, polled until the GoXLR reports 0xFF
+     Upload   - Sends the image in chunks, each chunk is prefixed with its offset and length
+     Validate - Fetches a checksum for each block of the image from the GoXLR, and compares it
+                against our own, any blocks which don't match are re-sent
+     Verify   - The GoXLR checks the image, polled until complete
+     Finalise - Writes the image to active memory, polled until complete
+     Reboot   - Restarts the GoXLR onto the new firmware
+
+   Progress is sent upstream as each stage advances, ending with either Complete or Failed.
+
+   While this is running the device runner is busy, so nothing else will be sent to the GoXLR until
+   it's done. Once the update has started the GoXLR will either reboot, or be left in an unknown
+   state, so in both cases the runner should stop and allow the device to be re-initialised.
+*/
+
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Result};
+use log::{debug, info, warn};
+use tokio::sync::mpsc;
+use tokio::time::sleep;
+
+use goxlr_shared::firmware::{FirmwareUpdateStage, FirmwareUpdateStatus};
+
+use crate::goxlr::commands::FirmwareCommand;
+use crate::platform::FullGoXLRDevice;
+
+type Stage = FirmwareUpdateStage;
+
+// Sends need to fit into the GoXLR's 1024 byte request limit, including the offset and length
+const CHUNK_SIZE: usize = 1012;
+
+// Checksums are validated in blocks, so a mismatch only requires part of the image be re-sent
+const BLOCK_SIZE: usize = CHUNK_SIZE * 64;
+const BLOCK_ATTEMPTS: usize = 3;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+const POLL_TIMEOUT: Duration = Duration::from_secs(120);
+
+// The GoXLR reports erase progress from 0x00, to 0xFF when done
+const ERASE_COMPLETE: u8 = 0xff;
+
+// The states returned when polling VERIFY or FINALISE, anything else is an error
+const COMMAND_RUNNING: u32 = 0;
+const COMMAND_COMPLETE: u32 = 1;
+
+pub(crate) struct FirmwareUpdater<'a> {
+    device: &'a mut Box<dyn FullGoXLRDevice>,
+    sender: mpsc::Sender<FirmwareUpdateStatus>,
+
+    stage: Stage,
+    reported: Option<(Stage, u8)>,
+}
+
+impl<'a> FirmwareUpdater<'a> {
+    pub fn new(
+        device: &'a mut Box<dyn FullGoXLRDevice>,
+        sender: mpsc::Sender<FirmwareUpdateStatus>,
+    ) -> Self {
+        Self {
+            device,
+            sender,
+
+            stage: Stage::Start,
+            reported: None,
+        }
+    }
+
+    /// Runs the update to completion, returning whether the GoXLR was placed into update mode
+    pub async fn run(mut self, image: &[u8]) -> bool {
+        if let Err(error) = check_image(image) {
+            warn!("Firmware Image Rejected: {}", error);
+            self.send(FirmwareUpdateStatus::failed(error.to_string()))
+                .await;
+            return false;
+        }
+
+        info!("Starting Firmware Update, {} bytes", image.len());
+        match self.run_stages(image).await {
+            Ok(()) => {
+                info!("Firmware Update Complete");
+                self.send(FirmwareUpdateStatus::new(Stage::Complete, 100))
+                    .await;
+            }
+            Err(error) => {
+                warn!("Firmware Update Failed during {:?}: {}", self.stage, error);
+                self.send(FirmwareUpdateStatus::failed(error.to_string()))
+                    .await;
+            }
+        }
+        true
+    }
+
+    async fn run_stages(&mut self, image: &[u8]) -> Result<()> {
+        let size = image.len() as u32;
+
+        loop {
+            self.report(0).await;
+            self.stage = match self.stage {
+                Stage::Start => {
+                    self.device.begin_firmware_update().await?;
+                    Stage::Erase
+                }
+                Stage::Erase => {
+                    self.erase(size).await?;
+                    Stage::Upload
+                }
+                Stage::Upload => {
+                    self.upload(image).await?;
+                    Stage::Validate
+                }
+                Stage::Validate => {
+                    self.validate(image).await?;
+                    Stage::Verify
+                }
+                Stage::Verify => {
+                    if let Err(error) = self.run_command(FirmwareCommand::VERIFY, size).await {
+                        // Verification failed, tell the GoXLR to drop out of update mode
+                        let _ = self.device.abort_firmware_update().await;
+                        return Err(error);
+                    }
+                    Stage::Finalise
+                }
+                Stage::Finalise => {
+                    self.run_command(FirmwareCommand::FINALISE, size).await?;
+                    Stage::Reboot
+                }
+                Stage::Reboot => {
+                    // The GoXLR may restart before it gets a chance to respond, so this can fail
+                    if let Err(error) = self.device.reboot_after_firmware_update().await {
+                        debug!("Error Rebooting GoXLR, it's likely already gone: {}", error);
+                    }
+                    Stage::Complete
+                }
+                Stage::Complete | Stage::Failed => return Ok(()),
+            };
+        }
+    }
+
+    async fn erase(&mut self, size: u32) -> Result<()> {
+        self.device.erase_firmware_partition(size).await?;
+
+        let started = Instant::now();
+        loop {
+            let progress = self.device.poll_firmware_erase().await?;
+            if progress == ERASE_COMPLETE {
+                return Ok(());
+            }
+            self.report(percentage(progress as usize, ERASE_COMPLETE as usize))
+                .await;
+            wait(started).await?;
+        }
+    }
+
+    async fn upload(&mut self, image: &[u8]) -> Result<()> {
+        for (index, chunk) in image.chunks(CHUNK_SIZE).enumerate() {
+            let offset = index * CHUNK_SIZE;
+            self.device
+                .send_firmware_chunk(offset as u32, chunk)
+                .await?;
+            self.report(percentage(offset + chunk.len(), image.len()))
+                .await;
+        }
+        Ok(())
+    }
+
+    async fn validate(&mut self, image: &[u8]) -> Result<()> {
+        for (index, block) in image.chunks(BLOCK_SIZE).enumerate() {
+            let offset = index * BLOCK_SIZE;
+            let expected = checksum(block);
+
+            let mut attempt = 1;
+            loop {
+                let (start, length) = (offset as u32, block.len() as u32);
+                let received = self.device.get_firmware_checksum(start, length).await?;
+                if received == expected {
+                    break;
+                }
+
+                if attempt == BLOCK_ATTEMPTS {
+                    bail!("Checksum mismatch at offset {:#x}", offset);
+                }
+                attempt += 1;
+
+                warn!("Checksum mismatch at offset {:#x}, re-sending", offset);
+                for (index, chunk) in block.chunks(CHUNK_SIZE).enumerate() {
+                    let chunk_offset = offset + index * CHUNK_SIZE;
+                    self.device
+                        .send_firmware_chunk(chunk_offset as u32, chunk)
+                        .await?;
+                }
+            }
+            self.report(percentage(offset + block.len(), image.len()))
+                .await;
+        }
+        Ok(())
+    }
+
+    async fn run_command(&mut self, command: FirmwareCommand, size: u32) -> Result<()> {
+        self.device.run_firmware_command(command, size).await?;
+
+        let started = Instant::now();
+        loop {
+            let (state, done, total) = self.device.poll_firmware_command().await?;
+            match state {
+                COMMAND_COMPLETE => return Ok(()),
+                COMMAND_RUNNING => self.report(percentage(done as usize, total as usize)).await,
+                _ => bail!("GoXLR reported error {} during {:?}", state, command),
+            }
+            wait(started).await?;
+        }
+    }
+
+    /// Sends the progress of the current stage upstream, but only if it's changed
+    async fn report(&mut self, progress: u8) {
+        if self.reported == Some((self.stage, progress)) {
+            return;
+        }
+        self.reported = Some((self.stage, progress));
+        self.send(FirmwareUpdateStatus::new(self.stage, progress))
+            .await;
+    }
+
+    async fn send(&self, status: FirmwareUpdateStatus) {
+        // If nobody is listening, there's not much we can do, the update should still continue.
+        // Progress is dropped rather than waited on if the receiver falls behind, as a stalled
+        // update is far worse than a skipped percentage, but the final result is always sent.
+        if status.is_finished() {
+            let _ = self.sender.send(status).await;
+        } else if self.sender.try_send(status).is_err() {
+            debug!("Firmware progress not delivered, receiver is busy");
+        }
+    }
+}
+
+/// The checksum the GoXLR uses to validate the image, a simple sum of all bytes
+pub(crate) fn checksum(data: &[u8]) -> u32 {
+    data.iter()
+        .fold(0_u32, |sum, byte| sum.wrapping_add(*byte as u32))
+}
+
+fn check_image(image: &[u8]) -> Result<()> {
+    if image.is_empty() {
+        bail!("Firmware image is empty");
+    }
+    if u32::try_from(image.len()).is_err() {
+        bail!("Firmware image is too large");
+    }
+    Ok(())
+}
+
+fn percentage(done: usize, total: usize) -> u8 {
+    if total == 0 {
+        return 0;
+    }
+    (done.min(total) * 100 / total) as u8
+}
+
+async fn wait(started: Instant) -> Result<()> {
+    if started.elapsed() > POLL_TIMEOUT {
+        bail!("Timed out waiting for the GoXLR");
+    }
+    sleep(POLL_INTERVAL).await;
+    Ok(())
+}
@@ -1 +1,2 @@
+pub(crate) mod firmware;
 pub mod state_tracker;
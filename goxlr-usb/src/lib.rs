@@ -1,4 +1,7 @@
 use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+use goxlr_shared::device::DeviceType;
 
 // Re-export the goxlr-shared crate..
 pub use goxlr_shared as shared;
@@ -15,6 +18,13 @@ pub mod requests;
 pub mod runners;
 mod util;
 
+// Allows the physical inputs of a Virtual GoXLR to be controlled
+#[cfg(any(test, feature = "virtual-device"))]
+pub use platform::virtual_device::control as virtual_device;
+
+// Capturing USB traffic to a file, and replaying it back as a device
+pub use platform::capture;
+
 /// GoXLR USB Vendor ID
 pub const VID_GOXLR: u16 = 0x1220;
 
@@ -29,6 +39,8 @@ pub const PID_GOXLR_MINI: u16 = 0x8fe4;
 pub struct USBLocation {
     lib_usb: Option<LibUSB>,
     windows_usb: Option<WindowsUSB>,
+    virtual_device: Option<VirtualUSB>,
+    replay: Option<ReplayUSB>,
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -42,6 +54,17 @@ struct WindowsUSB {
     pub(crate) identifier: String,
 }
 
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+struct VirtualUSB {
+    pub(crate) serial: String,
+    pub(crate) device_type: DeviceType,
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+struct ReplayUSB {
+    pub(crate) path: PathBuf,
+}
+
 pub struct DeviceHandle {
     handle: u32,
 }
@@ -54,6 +77,12 @@ impl Display for USBLocation {
         if let Some(winusb) = &self.windows_usb {
             return write!(f, "[{}]", winusb.identifier);
         }
+        if let Some(virtual_device) = &self.virtual_device {
+            return write!(f, "[VIRTUAL:{}]", virtual_device.serial);
+        }
+        if let Some(replay) = &self.replay {
+            return write!(f, "[REPLAY:{}]", replay.path.display());
+        }
         write!(f, "[ERROR] Unknown Device identification")
     }
 }
@@ -0,0 +1,179 @@
+use std::fmt::Write as FmtWrite;
+use std::fs::File;
+use std::io::{LineWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+
+use goxlr_shared::device::DeviceType;
+
+/// A single request sent to the GoXLR, and what came back
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureRecord {
+    /// Time since the start of the capture that the request was sent
+    pub elapsed: Duration,
+
+    /// How long the device took to respond
+    pub duration: Duration,
+
+    pub command: u32,
+    pub index: u16,
+    pub body: Vec<u8>,
+
+    /// The response, or the error message if the request failed
+    pub response: Result<Vec<u8>, String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Capture {
+    pub device_type: DeviceType,
+    pub records: Vec<CaptureRecord>,
+}
+
+impl Capture {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Unable to read capture {:?}", path))?;
+        Self::parse(&content).with_context(|| format!("Unable to parse capture {:?}", path))
+    }
+
+    pub fn parse(capture: &str) -> Result<Self> {
+        let mut device_type = None;
+        let mut records = vec![];
+
+        for (index, line) in capture.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(comment) = line.strip_prefix('#') {
+                if let Some(value) = comment.trim().strip_prefix("device ") {
+                    device_type = Some(parse_device_type(value.trim())?);
+                }
+                continue;
+            }
+
+            let record = parse_record(line)
+                .with_context(|| format!("Error on line {}: {}", index + 1, line))?;
+            records.push(record);
+        }
+
+        let Some(device_type) = device_type else {
+            bail!("Capture does not specify a device type");
+        };
+
+        Ok(Self {
+            device_type,
+            records,
+        })
+    }
+}
+
+/// Writes records to disk as they happen, so that as much as possible is kept if the daemon
+/// goes away unexpectedly.
+pub(crate) struct CaptureWriter {
+    file: LineWriter<File>,
+    start: Instant,
+}
+
+impl CaptureWriter {
+    pub fn create(path: &Path, device_type: DeviceType, location: &str) -> Result<Self> {
+        let file = File::create(path).with_context(|| format!("Unable to create {:?}", path))?;
+        let mut file = LineWriter::new(file);
+
+        writeln!(file, "# GoXLR USB Capture")?;
+        writeln!(file, "# device {:?}", device_type)?;
+        writeln!(file, "# location {}", location)?;
+
+        Ok(Self {
+            file,
+            start: Instant::now(),
+        })
+    }
+
+    pub fn start_time(&self) -> Instant {
+        self.start
+    }
+
+    pub fn write(&mut self, record: &CaptureRecord) -> Result<()> {
+        writeln!(self.file, "{}", format_record(record))?;
+        Ok(())
+    }
+}
+
+fn format_record(record: &CaptureRecord) -> String {
+    let response = match &record.response {
+        Ok(response) => to_hex(response),
+        Err(error) => format!("!{}", error.replace('\n', " ")),
+    };
+
+    format!(
+        "{} {} {:08x} {} {} {}",
+        record.elapsed.as_micros(),
+        record.duration.as_micros(),
+        record.command,
+        record.index,
+        to_hex(&record.body),
+        response
+    )
+}
+
+fn parse_record(line: &str) -> Result<CaptureRecord> {
+    let parts: Vec<&str> = line.splitn(6, ' ').collect();
+    let [elapsed, duration, command, index, body, response] = parts[..] else {
+        bail!("Expected 6 fields, found {}", parts.len());
+    };
+
+    let response = match response.strip_prefix('!') {
+        Some(error) => Err(error.to_string()),
+        None => Ok(from_hex(response)?),
+    };
+
+    Ok(CaptureRecord {
+        elapsed: Duration::from_micros(elapsed.parse()?),
+        duration: Duration::from_micros(duration.parse()?),
+        command: u32::from_str_radix(command, 16)?,
+        index: index.parse()?,
+        body: from_hex(body)?,
+        response,
+    })
+}
+
+fn parse_device_type(value: &str) -> Result<DeviceType> {
+    match value.to_lowercase().as_str() {
+        "full" => Ok(DeviceType::Full),
+        "mini" => Ok(DeviceType::Mini),
+        _ => bail!("Unknown Device Type: {}", value),
+    }
+}
+
+// Empty data is written as '-' to keep the number of fields consistent
+fn to_hex(data: &[u8]) -> String {
+    if data.is_empty() {
+        return String::from("-");
+    }
+
+    let mut result = String::with_capacity(data.len() * 2);
+    for byte in data {
+        let _ = write!(result, "{:02x}", byte);
+    }
+    result
+}
+
+fn from_hex(value: &str) -> Result<Vec<u8>> {
+    if value == "-" {
+        return Ok(vec![]);
+    }
+    let pairs = value.as_bytes().chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        bail!("Invalid Hex Length: {}", value.len());
+    }
+
+    let mut result = Vec::with_capacity(value.len() / 2);
+    for pair in pairs {
+        result.push(u8::from_str_radix(std::str::from_utf8(pair)?, 16)?);
+    }
+    Ok(result)
+}
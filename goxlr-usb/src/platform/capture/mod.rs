@@ -0,0 +1,70 @@
+/*
+   Capturing and replaying USB traffic. When a capture directory is provided, devices are wrapped
+   in a recorder which logs every request sent to the GoXLR (and its response) to a file. That file
+   can then be attached as a Replay device, which serves the recorded responses back to the
+   daemon, allowing a user's setup to be reproduced without their hardware.
+
+   Captures are plain text, with one request per line:
+     <elapsed us> <duration us> <command id> <command index> <body> <response>
+
+   The command id is in hex, the body and response are hex encoded bytes (or '-' when empty), and
+   a failed request has its response replaced by '!' followed by the error. Lines starting with
+   '#' are comments, aside from '# device <Full|Mini>' which defines the device type.
+
+   Replay devices can also be defined by the GOXLR_REPLAY_FILES environment variable, as a comma
+   separated list of capture paths.
+*/
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::Result;
+
+use crate::{ReplayUSB, USBLocation};
+
+pub use crate::platform::capture::format::{Capture, CaptureRecord};
+
+mod format;
+pub(crate) mod recorder;
+pub(crate) mod replay;
+
+static REPLAY_VARIABLE: &str = "GOXLR_REPLAY_FILES";
+
+// Captures attached via `attach_replay`
+static ATTACHED: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// Attaches a capture as a device, which will be picked up on the next PnP scan
+pub fn attach_replay(path: &Path) -> Result<()> {
+    // Make sure the capture is valid now, rather than when the device is created
+    Capture::load(path)?;
+
+    let mut attached = ATTACHED.lock().unwrap();
+    if !attached.iter().any(|attached| attached == path) {
+        attached.push(path.to_path_buf());
+    }
+    Ok(())
+}
+
+/// Removes a device previously attached with `attach_replay`
+pub fn detach_replay(path: &Path) {
+    ATTACHED.lock().unwrap().retain(|attached| attached != path);
+}
+
+pub(crate) fn get_devices() -> Vec<USBLocation> {
+    let mut paths = ATTACHED.lock().unwrap().clone();
+    if let Ok(value) = env::var(REPLAY_VARIABLE) {
+        let entries = value.split(',').map(str::trim).filter(|e| !e.is_empty());
+        paths.extend(entries.map(PathBuf::from));
+    }
+
+    paths
+        .into_iter()
+        .map(|path| USBLocation {
+            lib_usb: None,
+            windows_usb: None,
+            virtual_device: None,
+            replay: Some(ReplayUSB { path }),
+        })
+        .collect()
+}
@@ -0,0 +1,138 @@
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use log::{info, warn};
+
+use goxlr_shared::device::DeviceType;
+
+use crate::common::command_handler::GoXLRCommands;
+use crate::common::executor::ExecutableGoXLR;
+use crate::goxlr::commands::Command;
+use crate::platform::capture::format::{CaptureRecord, CaptureWriter};
+use crate::platform::common::device::{GoXLRConfiguration, GoXLRDevice};
+use crate::platform::FullGoXLRDevice;
+use crate::USBLocation;
+
+/// Wraps another device, passing everything through while writing each request to a capture
+pub(crate) struct CaptureGoXLR {
+    device: Box<dyn FullGoXLRDevice>,
+    writer: Option<CaptureWriter>,
+    index: u16,
+}
+
+impl CaptureGoXLR {
+    /// Wraps the device in a recorder. Capturing is only a debugging aid, so if the capture can't
+    /// be created the device is handed back as-is, rather than stopping it from starting.
+    pub fn wrap(
+        device: Box<dyn FullGoXLRDevice>,
+        directory: &Path,
+        location: &USBLocation,
+    ) -> Box<dyn FullGoXLRDevice> {
+        let device_type = device.get_device_type();
+        match create_writer(directory, location, device_type) {
+            Ok(writer) => Box::new(Self {
+                device,
+                writer: Some(writer),
+                index: 0,
+            }),
+            Err(error) => {
+                warn!("{} Unable to capture USB Requests: {:#}", location, error);
+                device
+            }
+        }
+    }
+}
+
+fn create_writer(
+    directory: &Path,
+    location: &USBLocation,
+    device_type: DeviceType,
+) -> Result<CaptureWriter> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+    // Locations contain characters which aren't great in file names, so strip them out
+    let name: String = location
+        .to_string()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let name = format!("goxlr-{}-{}.capture", timestamp, name.trim_matches('-'));
+    let path = directory.join(name);
+
+    std::fs::create_dir_all(directory)?;
+    let writer = CaptureWriter::create(&path, device_type, &location.to_string())?;
+
+    info!("{} Capturing USB Requests to {:?}", location, path);
+    Ok(writer)
+}
+
+#[async_trait]
+impl GoXLRDevice for CaptureGoXLR {
+    async fn from_config(_: GoXLRConfiguration) -> Result<Box<dyn FullGoXLRDevice>>
+    where
+        Self: Sized,
+    {
+        bail!("Capture devices can only be created by wrapping an existing device");
+    }
+
+    async fn run(&mut self) -> Result<()> {
+        self.device.run().await
+    }
+
+    async fn stop(&mut self) {
+        self.device.stop().await
+    }
+
+    fn get_device_type(&self) -> DeviceType {
+        self.device.get_device_type()
+    }
+}
+
+#[async_trait]
+impl ExecutableGoXLR for CaptureGoXLR {
+    async fn perform_request(&mut self, command: Command, body: &[u8]) -> Result<Vec<u8>> {
+        // Track the command index in the same way as the device does
+        if command == Command::ResetCommandIndex {
+            self.index = 0;
+        } else {
+            self.index = self.index.checked_add(1).unwrap_or(1);
+        }
+
+        let sent = Instant::now();
+        let result = self.device.perform_request(command, body).await;
+
+        if let Some(writer) = &mut self.writer {
+            let record = CaptureRecord {
+                elapsed: sent.duration_since(writer.start_time()),
+                duration: sent.elapsed(),
+                command: command.command_id(),
+                index: self.index,
+                body: body.to_vec(),
+                response: match &result {
+                    Ok(response) => Ok(response.clone()),
+                    Err(error) => Err(error.to_string()),
+                },
+            };
+
+            // A failure to write the capture shouldn't affect the device, so just stop capturing
+            if let Err(error) = writer.write(&record) {
+                warn!("Unable to write capture, stopping: {}", error);
+                self.writer = None;
+            }
+        }
+        result
+    }
+
+    async fn perform_recovery(&mut self) -> Result<()> {
+        self.device.perform_recovery().await
+    }
+
+    async fn perform_stop(&mut self) {
+        self.device.perform_stop().await
+    }
+}
+
+impl GoXLRCommands for CaptureGoXLR {}
+impl FullGoXLRDevice for CaptureGoXLR {}
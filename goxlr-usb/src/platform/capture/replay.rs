@@ -0,0 +1,175 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use log::{debug, info, warn};
+use tokio::task::JoinHandle;
+use tokio::{select, task, time};
+
+use goxlr_shared::device::DeviceType;
+
+use crate::common::command_handler::GoXLRCommands;
+use crate::common::executor::ExecutableGoXLR;
+use crate::goxlr::commands::Command;
+use crate::platform::capture::format::{Capture, CaptureRecord};
+use crate::platform::common::device::{GoXLRConfiguration, GoXLRDevice};
+use crate::platform::FullGoXLRDevice;
+use crate::runners::device::InternalDeviceMessage;
+use crate::util::stop::Stop;
+
+// Button state polls are timing dependent, so are handled separately from other requests
+const POLL_OPCODE: u32 = 0x800;
+
+/*
+    The daemon is unlikely to send exactly the same sequence of requests on replay as it did
+    when capturing (button polling is timer driven, and the daemon itself may have changed), so
+    rather than strictly playing the capture in order, we look for the next matching request
+    and return its response. Anything which doesn't match exactly is logged, as that's either a
+    change in behaviour or a regression.
+*/
+pub(crate) struct ReplayGoXLR {
+    config: GoXLRConfiguration,
+    stop: Stop,
+    task: Option<JoinHandle<()>>,
+
+    device_type: DeviceType,
+    requests: Vec<CaptureRecord>,
+    polls: VecDeque<CaptureRecord>,
+    last_poll: Vec<u8>,
+    position: usize,
+}
+
+#[async_trait]
+impl GoXLRDevice for ReplayGoXLR {
+    async fn from_config(config: GoXLRConfiguration) -> Result<Box<dyn FullGoXLRDevice>>
+    where
+        Self: Sized,
+    {
+        let Some(replay) = config.device.replay.clone() else {
+            bail!("Attempted to create a Replay device from a physical device");
+        };
+
+        let capture = Capture::load(&replay.path)?;
+        let (polls, requests) = capture
+            .records
+            .into_iter()
+            .partition(|record| record.command >> 12 == POLL_OPCODE);
+
+        info!("Replaying Capture {:?}", replay.path);
+        Ok(Box::new(Self {
+            config,
+            stop: Stop::new(),
+            task: None,
+
+            device_type: capture.device_type,
+            requests,
+            polls: VecDeque::from(polls),
+            last_poll: vec![0; 24],
+            position: 0,
+        }))
+    }
+
+    async fn run(&mut self) -> Result<()> {
+        let device = self.config.device.clone();
+        let events = self.config.events.clone();
+
+        let mut stop = self.stop.clone();
+        self.task = Some(task::spawn(async move {
+            debug!("[DEVICE]{} Spawning Event Loop..", device);
+            let mut ticker = time::interval(Duration::from_millis(20));
+            loop {
+                select! {
+                    _ = ticker.tick() => {
+                        if events.capacity() > 0 {
+                            let _ = events.send(InternalDeviceMessage::Poll).await;
+                        }
+                    }
+                    _ = stop.recv() => {
+                        debug!("[DEVICE]{} Stopping Event Loop..", device);
+                        break;
+                    }
+                }
+            }
+            debug!("[DEVICE]{} Event Loop Stopped", device);
+        }));
+        Ok(())
+    }
+
+    async fn stop(&mut self) {
+        self.stop.trigger();
+
+        if self.task.is_some() {
+            let _ = self.task.take().unwrap().await;
+        }
+    }
+
+    fn get_device_type(&self) -> DeviceType {
+        self.device_type
+    }
+}
+
+#[async_trait]
+impl ExecutableGoXLR for ReplayGoXLR {
+    async fn perform_request(&mut self, command: Command, body: &[u8]) -> Result<Vec<u8>> {
+        let command_id = command.command_id();
+
+        // Polls are played back in order, once we run out the last state is simply held
+        if command_id >> 12 == POLL_OPCODE {
+            if let Some(record) = self.polls.pop_front() {
+                if let Ok(response) = &record.response {
+                    self.last_poll.clone_from(response);
+                }
+                return to_result(record.response);
+            }
+            return Ok(self.last_poll.clone());
+        }
+
+        let matches = |record: &CaptureRecord| record.command == command_id && record.body == body;
+
+        // Ideally, this is the next request in the capture..
+        let remaining = &self.requests[self.position..];
+        if let Some(offset) = remaining.iter().position(matches) {
+            if offset != 0 {
+                warn!("[REPLAY] Skipped {} recorded requests", offset);
+            }
+            let index = self.position + offset;
+            self.position = index + 1;
+            return to_result(self.requests[index].response.clone());
+        }
+
+        // Otherwise, the request was sent in a different order, or with a different body
+        warn!("[REPLAY] Request {:#x} not found in order", command_id);
+        if let Some(record) = self.requests.iter().find(|r| matches(r)) {
+            return to_result(record.response.clone());
+        }
+
+        warn!(
+            "[REPLAY] Request {:#x} body mismatch: {:?}",
+            command_id, body
+        );
+        if let Some(record) = self.requests.iter().find(|r| r.command == command_id) {
+            return to_result(record.response.clone());
+        }
+        bail!("No recorded response for request {:#x}", command_id);
+    }
+
+    async fn perform_recovery(&mut self) -> Result<()> {
+        debug!("[REPLAY] Recovery Requested, nothing to do");
+        Ok(())
+    }
+
+    async fn perform_stop(&mut self) {
+        self.stop().await
+    }
+}
+
+impl GoXLRCommands for ReplayGoXLR {}
+impl FullGoXLRDevice for ReplayGoXLR {}
+
+fn to_result(response: Result<Vec<u8>, String>) -> Result<Vec<u8>> {
+    match response {
+        Ok(response) => Ok(response),
+        Err(error) => bail!("{}", error),
+    }
+}
@@ -4,11 +4,35 @@
    and Linux
 */
 
-use crate::{USBLocation, PID_GOXLR_FULL, PID_GOXLR_MINI, VID_GOXLR, LibUSB};
+use std::sync::OnceLock;
+
+use log::warn;
+use rusb::{Context, UsbContext};
+
+use crate::{LibUSB, USBLocation, PID_GOXLR_FULL, PID_GOXLR_MINI, VID_GOXLR};
+
+// rusb's global context panics if libusb can't be initialised (for example, in a container with
+// no USB access), so we create our own and simply find no devices if it fails.
+static CONTEXT: OnceLock<Option<Context>> = OnceLock::new();
 
 pub async fn get_devices() -> Vec<USBLocation> {
     let mut list = vec![];
-    if let Ok(devices) = rusb::devices() {
+    let context = CONTEXT.get_or_init(|| match Context::new() {
+        Ok(context) => Some(context),
+        Err(error) => {
+            warn!(
+                "Unable to initialise libusb, USB devices will not be found: {}",
+                error
+            );
+            None
+        }
+    });
+
+    let Some(context) = context else {
+        return list;
+    };
+
+    if let Ok(devices) = context.devices() {
         for device in devices.iter() {
             if let Ok(descriptor) = device.device_descriptor() {
                 let bus_number = device.bus_number();
@@ -24,6 +48,8 @@ pub async fn get_devices() -> Vec<USBLocation> {
                             address,
                         }),
                         windows_usb: None,
+                        virtual_device: None,
+                        replay: None,
                     };
 
                     list.push(device);
@@ -5,7 +5,10 @@ use anyhow::Result;
 
 // This file will select which backend to use depending on platform, internally they'll all
 // behave the same way.
+pub mod capture;
 pub mod common;
+#[cfg(any(test, feature = "virtual-device"))]
+pub(crate) mod virtual_device;
 
 pub trait FullGoXLRDevice: GoXLRDevice + GoXLRCommands + Sync + Send {}
 
@@ -15,20 +18,42 @@ cfg_if::cfg_if! {
         use crate::platform::tusb::device;
 
         pub async fn find_devices() -> Vec<USBLocation> {
-            crate::platform::tusb::pnp::get_devices()
+            let mut devices = crate::platform::tusb::pnp::get_devices();
+            #[cfg(any(test, feature = "virtual-device"))]
+            devices.extend(virtual_device::get_devices());
+            devices.extend(capture::get_devices());
+            devices
         }
 
         pub async fn from_device(config: GoXLRConfiguration) -> Result<Box<dyn FullGoXLRDevice>> {
+            #[cfg(any(test, feature = "virtual-device"))]
+            if config.device.virtual_device.is_some() {
+                return virtual_device::device::VirtualGoXLR::from_config(config).await;
+            }
+            if config.device.replay.is_some() {
+                return capture::replay::ReplayGoXLR::from_config(config).await;
+            }
             device::TUSBAudioGoXLR::from_config(config).await
         }
     } else {
         mod libusb;
 
         pub async fn find_devices() -> Vec<USBLocation> {
-            libusb::pnp::get_devices().await
+            let mut devices = libusb::pnp::get_devices().await;
+            #[cfg(any(test, feature = "virtual-device"))]
+            devices.extend(virtual_device::get_devices());
+            devices.extend(capture::get_devices());
+            devices
         }
 
         pub async fn from_device(config: GoXLRConfiguration) -> Result<Box<dyn FullGoXLRDevice>> {
+            #[cfg(any(test, feature = "virtual-device"))]
+            if config.device.virtual_device.is_some() {
+                return virtual_device::device::VirtualGoXLR::from_config(config).await;
+            }
+            if config.device.replay.is_some() {
+                return capture::replay::ReplayGoXLR::from_config(config).await;
+            }
             libusb::device::LibUSBGoXLR::from_config(config).await
         }
     }
@@ -12,7 +12,9 @@ pub fn get_devices() -> Vec<USBLocation> {
             lib_usb: None,
             windows_usb: Some(WindowsUSB {
                 identifier: device
-            })
+            }),
+            virtual_device: None,
+            replay: None,
         })
     }
     list
@@ -609,6 +609,8 @@ impl TUSBAudio<'_> {
                                         address,
                                     }),
                                     windows_usb: None,
+                                    virtual_device: None,
+                                    replay: None,
                                 });
                             }
                         }
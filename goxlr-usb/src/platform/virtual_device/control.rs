@@ -0,0 +1,195 @@
+/*
+   The 'physical' side of a Virtual GoXLR. Each attached virtual device registers its state here
+   by serial, allowing buttons to be pressed, faders moved and encoders turned from outside the
+   device. These changes are then picked up by the next GetButtonStates poll in the same way a
+   real interaction would be, so everything upstream of the StateTracker is exercised as normal.
+
+   Devices can also be attached and detached here without using the environment variable, and
+   the requests they receive inspected, which is primarily useful for tests.
+*/
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use tokio::time::sleep;
+
+use goxlr_shared::device::DeviceType;
+use goxlr_shared::interaction::{InteractiveButtons, InteractiveEncoders, InteractiveFaders};
+
+use crate::platform::virtual_device::state::VirtualState;
+use crate::types::buttons::DeviceButton;
+use crate::types::encoders::DeviceEncoder;
+use crate::types::faders::DeviceFader;
+use crate::{USBLocation, VirtualUSB};
+
+pub use crate::platform::virtual_device::script::{Script, VirtualInput};
+pub use crate::platform::virtual_device::state::CommandRecord;
+
+// The state of every virtual device we've seen, this persists if the device is detached or
+// restarted (for example, during error recovery) so existing controllers remain valid.
+static DEVICES: Mutex<BTreeMap<String, Arc<Mutex<VirtualState>>>> = Mutex::new(BTreeMap::new());
+
+// Devices attached via `attach`, rather than the environment variable
+static ATTACHED: Mutex<Vec<USBLocation>> = Mutex::new(Vec::new());
+
+pub(crate) fn get_state(serial: &str) -> Arc<Mutex<VirtualState>> {
+    let mut devices = DEVICES.lock().unwrap();
+    devices.entry(serial.to_string()).or_default().clone()
+}
+
+pub(crate) fn get_attached() -> Vec<USBLocation> {
+    ATTACHED.lock().unwrap().clone()
+}
+
+/// Plugs in a new virtual device, which will be picked up on the next PnP scan
+pub fn attach(serial: &str, device_type: DeviceType) -> VirtualController {
+    let location = USBLocation {
+        lib_usb: None,
+        windows_usb: None,
+        virtual_device: Some(VirtualUSB {
+            serial: serial.to_string(),
+            device_type,
+        }),
+        replay: None,
+    };
+
+    let mut attached = ATTACHED.lock().unwrap();
+    if !attached.contains(&location) {
+        attached.push(location);
+    }
+
+    VirtualController {
+        serial: serial.to_string(),
+        state: get_state(serial),
+    }
+}
+
+/// Unplugs a device previously attached with `attach`
+pub fn detach(serial: &str) {
+    let mut attached = ATTACHED.lock().unwrap();
+    attached.retain(|location| match &location.virtual_device {
+        Some(device) => device.serial != serial,
+        None => true,
+    });
+}
+
+#[derive(Clone)]
+pub struct VirtualController {
+    serial: String,
+    state: Arc<Mutex<VirtualState>>,
+}
+
+impl VirtualController {
+    /// Gets a controller for a Virtual Device which has previously been created
+    pub fn get(serial: &str) -> Result<Self> {
+        let Some(state) = DEVICES.lock().unwrap().get(serial).cloned() else {
+            bail!("Virtual Device {} is not known", serial);
+        };
+
+        Ok(Self {
+            serial: serial.to_string(),
+            state,
+        })
+    }
+
+    pub fn serial(&self) -> &str {
+        &self.serial
+    }
+
+    pub fn press(&self, button: InteractiveButtons) {
+        let button = DeviceButton::from(button);
+        self.state.lock().unwrap().pressed_buttons |= 1 << button as u8;
+    }
+
+    pub fn release(&self, button: InteractiveButtons) {
+        let button = DeviceButton::from(button);
+        self.state.lock().unwrap().pressed_buttons &= !(1 << button as u8);
+    }
+
+    pub fn release_all(&self) {
+        self.state.lock().unwrap().pressed_buttons = 0;
+    }
+
+    pub fn is_pressed(&self, button: InteractiveButtons) -> bool {
+        let button = DeviceButton::from(button);
+        self.state.lock().unwrap().pressed_buttons & (1 << button as u8) != 0
+    }
+
+    pub fn set_fader(&self, fader: InteractiveFaders, position: u8) {
+        let fader = DeviceFader::from(fader);
+        self.state.lock().unwrap().fader_positions[fader as usize] = position;
+    }
+
+    pub fn get_fader(&self, fader: InteractiveFaders) -> u8 {
+        let fader = DeviceFader::from(fader);
+        self.state.lock().unwrap().fader_positions[fader as usize]
+    }
+
+    pub fn set_encoder(&self, encoder: InteractiveEncoders, value: i8) {
+        let encoder = DeviceEncoder::from(encoder);
+        self.state.lock().unwrap().encoder_values[encoder as usize] = value;
+    }
+
+    /// Turns an encoder by a number of 'clicks', positive values turn clockwise. As with the
+    /// hardware, the GoXLR doesn't know the encoder range, so the value is only bound by the i8.
+    pub fn turn_encoder(&self, encoder: InteractiveEncoders, clicks: i8) {
+        let encoder = DeviceEncoder::from(encoder) as usize;
+        let mut state = self.state.lock().unwrap();
+        state.encoder_values[encoder] = state.encoder_values[encoder].saturating_add(clicks);
+    }
+
+    pub fn get_encoder(&self, encoder: InteractiveEncoders) -> i8 {
+        let encoder = DeviceEncoder::from(encoder);
+        self.state.lock().unwrap().encoder_values[encoder as usize]
+    }
+
+    /// Returns the requests received by the device since the last call, excluding polls. This
+    /// is capped, so the oldest may have been dropped if they weren't taken in time.
+    pub fn take_commands(&self) -> Vec<CommandRecord> {
+        self.state.lock().unwrap().commands.drain(..).collect()
+    }
+
+    /// Causes the next `count` requests sent to the device to fail
+    pub fn fail_requests(&self, count: usize) {
+        self.state.lock().unwrap().failures = count;
+    }
+
+    /// Returns the image flashed by the last firmware update to complete on this device
+    pub fn firmware_image(&self) -> Option<Vec<u8>> {
+        self.state.lock().unwrap().firmware.flashed.clone()
+    }
+
+    /// Corrupts the next `count` firmware chunks written to the device, so they fail validation
+    pub fn corrupt_firmware_chunks(&self, count: usize) {
+        self.state.lock().unwrap().firmware.corrupt_chunks = count;
+    }
+
+    pub fn apply(&self, input: &VirtualInput) {
+        match *input {
+            VirtualInput::Press(button) => self.press(button),
+            VirtualInput::Release(button) => self.release(button),
+            VirtualInput::Fader(fader, position) => self.set_fader(fader, position),
+            VirtualInput::Encoder(encoder, value) => self.set_encoder(encoder, value),
+            VirtualInput::Turn(encoder, clicks) => self.turn_encoder(encoder, clicks),
+            VirtualInput::Wait(_) => {}
+        }
+    }
+
+    /// Runs a script against the device. Note that the device is only polled every 20ms, so any
+    /// press and release which happen within that window may not be seen by the daemon.
+    pub async fn run(&self, script: &Script) {
+        for step in script.steps() {
+            match step {
+                VirtualInput::Wait(duration) => sleep(*duration).await,
+                input => self.apply(input),
+            }
+        }
+    }
+
+    /// Convenience method for 'hold a button for a period of time, then release it'
+    pub async fn hold(&self, button: InteractiveButtons, duration: Duration) {
+        self.run(&Script::new().hold(button, duration)).await
+    }
+}
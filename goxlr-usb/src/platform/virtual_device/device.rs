@@ -0,0 +1,115 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use std::{env, fs};
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use log::{debug, info};
+use tokio::task::JoinHandle;
+use tokio::{select, task, time};
+
+use goxlr_shared::device::DeviceType;
+
+use crate::common::command_handler::GoXLRCommands;
+use crate::platform::common::device::{GoXLRConfiguration, GoXLRDevice};
+use crate::platform::virtual_device::control;
+use crate::platform::virtual_device::control::{Script, VirtualController};
+use crate::platform::virtual_device::state::VirtualState;
+use crate::platform::FullGoXLRDevice;
+use crate::runners::device::InternalDeviceMessage;
+use crate::util::stop::Stop;
+
+static SCRIPT_VARIABLE: &str = "GOXLR_VIRTUAL_SCRIPT";
+
+pub(crate) struct VirtualGoXLR {
+    config: GoXLRConfiguration,
+    stop: Stop,
+    task: Option<JoinHandle<()>>,
+
+    pub(crate) serial: String,
+    pub(crate) device_type: DeviceType,
+    pub(crate) state: Arc<Mutex<VirtualState>>,
+}
+
+#[async_trait]
+impl GoXLRDevice for VirtualGoXLR {
+    async fn from_config(config: GoXLRConfiguration) -> Result<Box<dyn FullGoXLRDevice>>
+    where
+        Self: Sized,
+    {
+        let Some(device) = config.device.virtual_device.clone() else {
+            bail!("Attempted to create a Virtual GoXLR from a physical device");
+        };
+
+        info!(
+            "Created Virtual GoXLR {} ({:?})",
+            device.serial, device.device_type
+        );
+        let state = control::get_state(&device.serial);
+
+        Ok(Box::new(VirtualGoXLR {
+            config,
+            stop: Stop::new(),
+            task: None,
+
+            serial: device.serial,
+            device_type: device.device_type,
+            state,
+        }))
+    }
+
+    async fn run(&mut self) -> Result<()> {
+        let poll_millis = 20;
+
+        // There's no initialisation needed here, simply spawn the poller in the same way as the
+        // libusb backend does.
+        let device = self.config.device.clone();
+        let events = self.config.events.clone();
+
+        let mut stop = self.stop.clone();
+        self.task = Some(task::spawn(async move {
+            debug!("[DEVICE]{} Spawning Event Loop..", device);
+            let mut ticker = time::interval(Duration::from_millis(poll_millis));
+            loop {
+                select! {
+                    _ = ticker.tick() => {
+                        if events.capacity() > 0 {
+                            let _ = events.send(InternalDeviceMessage::Poll).await;
+                        }
+                    }
+                    _ = stop.recv() => {
+                        debug!("[DEVICE]{} Stopping Event Loop..", device);
+                        break;
+                    }
+                }
+            }
+            debug!("[DEVICE]{} Event Loop Stopped", device);
+        }));
+
+        // If a startup script has been provided, play it against the device
+        if let Ok(path) = env::var(SCRIPT_VARIABLE) {
+            let script = Script::parse(&fs::read_to_string(&path)?)?;
+            let controller = VirtualController::get(&self.serial)?;
+
+            info!("Running Virtual Input Script {} on {}", path, self.serial);
+            task::spawn(async move { controller.run(&script).await });
+        }
+
+        Ok(())
+    }
+
+    async fn stop(&mut self) {
+        self.stop.trigger();
+
+        if self.task.is_some() {
+            let _ = self.task.take().unwrap().await;
+        }
+    }
+
+    fn get_device_type(&self) -> DeviceType {
+        self.device_type
+    }
+}
+
+impl GoXLRCommands for VirtualGoXLR {}
+impl FullGoXLRDevice for VirtualGoXLR {}
@@ -0,0 +1,442 @@
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use byteorder::{ByteOrder, LittleEndian};
+use log::{debug, trace};
+
+use goxlr_shared::device::DeviceType;
+
+use crate::common::executor::ExecutableGoXLR;
+use crate::goxlr::commands::Command;
+use crate::handlers::firmware::checksum;
+use crate::platform::virtual_device::device::VirtualGoXLR;
+use crate::platform::virtual_device::state::{VirtualFirmware, VirtualState};
+
+/*
+    Rather than matching on the Command itself, we decode the command id in the same way the
+    GoXLR would. This means the virtual device also acts as a sanity check for the ids and
+    payloads we're sending, any mistakes there will show up here as errors.
+*/
+
+// Firmware versions reported by the virtual devices, these are new enough to support all features
+const FULL_FIRMWARE: (u32, u32, u32, u32) = (1, 4, 2, 107);
+const MINI_FIRMWARE: (u32, u32, u32, u32) = (1, 2, 0, 46);
+const DICE_FIRMWARE: (u32, u32, u32, u32) = (1, 0, 0, 2000);
+const MANUFACTURE_DATE: &str = "20230101";
+
+#[async_trait]
+impl ExecutableGoXLR for VirtualGoXLR {
+    async fn perform_request(&mut self, command: Command, body: &[u8]) -> Result<Vec<u8>> {
+        let command_id = command.command_id();
+        trace!("[VIRTUAL] Command {:#x}: {:?}", command_id, body);
+
+        let opcode = command_id >> 12;
+        let sub = command_id & 0xFFF;
+        let mut state = self.state.lock().unwrap();
+
+        // Button state polling happens every 20ms, so we don't record them
+        if opcode != 0x800 {
+            state.record_command(command_id, body);
+        }
+
+        if state.failures > 0 {
+            state.failures -= 1;
+            bail!("Simulated Device Failure");
+        }
+
+        match opcode {
+            // Reset Command Index and the System Info requests
+            0x000 => match sub {
+                0 => Ok(vec![]),
+                1 => Ok(vec![1, 0, 0, 0]),
+                2 => Ok(self.firmware_response()),
+                _ => bail!("Unknown System Info Request: {}", sub),
+            },
+
+            // Firmware Update Actions (Erase, Send and Validate) and Commands
+            0x004 => firmware_action(&mut state.firmware, sub, body),
+            0x810 => firmware_command(&mut state.firmware, sub, body),
+
+            // GetButtonStates
+            0x800 => {
+                let mut response = vec![0; 24];
+                LittleEndian::write_u32(&mut response[0..4], state.pressed_buttons);
+                for (index, value) in state.encoder_values.iter().enumerate() {
+                    response[4 + index] = *value as u8;
+                }
+                response[8..12].copy_from_slice(&state.fader_positions);
+                Ok(response)
+            }
+
+            // SetMicrophoneEffects, pairs of (key, i32)
+            0x801 => {
+                for chunk in pairs(body)? {
+                    let key = LittleEndian::read_u32(&chunk[0..4]);
+                    let value = LittleEndian::read_i32(&chunk[4..8]);
+                    state.mic_effects.insert(key, value);
+                }
+                Ok(vec![])
+            }
+
+            // SetScribble
+            0x802 => {
+                let fader = fader_index(sub)?;
+                if body.len() != 1024 {
+                    bail!("Invalid Scribble Length: {}", body.len());
+                }
+                state.scribbles[fader] = body.to_vec();
+                Ok(vec![])
+            }
+
+            // SetColourMap
+            0x803 => {
+                state.colour_map = body.to_vec();
+                Ok(vec![])
+            }
+
+            // SetRouting
+            0x804 => {
+                if !(0x02..=0x11).contains(&sub) {
+                    bail!("Invalid Routing Input: {:#x}", sub);
+                }
+                if body.len() != 22 {
+                    bail!("Invalid Routing Length: {}", body.len());
+                }
+                state.routing.insert(sub, body.to_vec());
+                Ok(vec![])
+            }
+
+            // SetFader
+            0x805 => {
+                let fader = fader_index(sub)?;
+                let channel = LittleEndian::read_u32(single(body, 4)?);
+                if channel > 0x0A || channel == 0x09 {
+                    bail!("Invalid Fader Channel: {}", channel);
+                }
+                state.fader_channels[fader] = channel;
+                Ok(vec![])
+            }
+
+            // SetChannelVolume and SetSubChannelVolume share an opcode, sub mixes start at 0x10
+            0x806 => {
+                let volume = single(body, 1)?[0];
+                match sub {
+                    0x00..=0x0A => state.volumes[sub as usize] = volume,
+                    0x10..=0x17 => state.submix_volumes[sub as usize - 0x10] = volume,
+                    _ => bail!("Invalid Volume Channel: {:#x}", sub),
+                }
+                Ok(vec![])
+            }
+
+            // SetButtonStates
+            0x808 => {
+                single(body, 24)?;
+                state.button_display = body.to_vec();
+                Ok(vec![])
+            }
+
+            // SetChannelState
+            0x809 => {
+                if sub > 0x0A {
+                    bail!("Invalid Channel: {:#x}", sub);
+                }
+                state.mute_states[sub as usize] = single(body, 1)?[0];
+                Ok(vec![])
+            }
+
+            // SetEncoderValue
+            0x80a => {
+                let encoder = encoder_index(sub)?;
+                state.encoder_values[encoder] = single(body, 1)?[0] as i8;
+                Ok(vec![])
+            }
+
+            // SetMicrophoneParameters, pairs of (key, u32 / f32)
+            0x80b => {
+                for chunk in pairs(body)? {
+                    let key = LittleEndian::read_u32(&chunk[0..4]);
+                    let value = LittleEndian::read_u32(&chunk[4..8]);
+                    state.mic_params.insert(key, value);
+                }
+                Ok(vec![])
+            }
+
+            // GetMicrophoneLevel
+            0x80c => {
+                let mut response = vec![0; 2];
+                LittleEndian::write_u16(&mut response, microphone_level(&state));
+                Ok(response)
+            }
+
+            // GetHardwareInfo
+            0x80f => match sub {
+                0 => Ok(self.firmware_response()),
+                1 => Ok(self.serial_response()),
+                _ => bail!("Unknown Hardware Info Request: {}", sub),
+            },
+
+            // SetEncoderMode
+            0x811 => {
+                let encoder = encoder_index(sub)?;
+                state.encoder_modes[encoder] = body.to_vec();
+                Ok(vec![])
+            }
+
+            // SetFaderDisplayMode
+            0x814 => {
+                let fader = fader_index(sub)?;
+                let body = single(body, 2)?;
+                state.fader_display[fader] = [body[0], body[1]];
+                Ok(vec![])
+            }
+
+            // SetAnimationMode
+            0x816 => {
+                single(body, 8)?;
+                state.animation = body.to_vec();
+                Ok(vec![])
+            }
+
+            // SetChannelMixes
+            0x817 => {
+                single(body, 8)?;
+                state.channel_mixes = body.to_vec();
+                Ok(vec![])
+            }
+
+            // SetMonitoredMix
+            0x818 => {
+                state.monitored_mix = single(body, 1)?[0];
+                Ok(vec![])
+            }
+
+            _ => bail!("Unknown Command: {:#x}", command_id),
+        }
+    }
+
+    async fn perform_recovery(&mut self) -> Result<()> {
+        debug!("[VIRTUAL] Recovery Requested, nothing to do");
+        Ok(())
+    }
+
+    async fn perform_stop(&mut self) {
+        use crate::platform::common::device::GoXLRDevice;
+        self.stop().await
+    }
+}
+
+impl VirtualGoXLR {
+    fn firmware_response(&self) -> Vec<u8> {
+        let (major, minor, patch, build) = match self.device_type {
+            DeviceType::Full => FULL_FIRMWARE,
+            DeviceType::Mini => MINI_FIRMWARE,
+        };
+        let (dice_major, dice_minor, dice_patch, dice_build) = DICE_FIRMWARE;
+
+        let mut response = vec![0; 24];
+        LittleEndian::write_u32(&mut response[0..4], major << 12 | minor << 8 | patch);
+        LittleEndian::write_u32(&mut response[4..8], build);
+        LittleEndian::write_u32(&mut response[12..16], 1);
+        LittleEndian::write_u32(&mut response[16..20], dice_build);
+        let dice = dice_major << 20 | dice_minor << 12 | dice_patch;
+        LittleEndian::write_u32(&mut response[20..24], dice);
+        response
+    }
+
+    fn serial_response(&self) -> Vec<u8> {
+        let mut response = vec![0; 24];
+        let serial = self.serial.as_bytes();
+        let length = serial.len().min(23);
+        response[..length].copy_from_slice(&serial[..length]);
+        response.extend(MANUFACTURE_DATE.as_bytes());
+        response.push(0);
+        response
+    }
+}
+
+/// Generates a level which loosely resembles someone talking, with bursts of speech over
+/// a noise floor. If the microphone is muted, only the floor is returned.
+fn microphone_level(state: &VirtualState) -> u16 {
+    let time = state.created.elapsed().as_secs_f64();
+    let muted = state.mute_states[0] != 0;
+
+    let speech = (time * 1.3).sin().max(0.) * (0.6 + 0.4 * (time * 11.).sin().abs());
+    let decibels = if muted { -60. } else { -60. + 50. * speech };
+
+    // This is the inverse of the conversion in get_microphone_level
+    10_f64.powf((decibels + 72.2) / 20.) as u16
+}
+
+// How far the erase, and a VERIFY / FINALISE progress with each poll
+const ERASE_STEP: u8 = 0x40;
+const COMMAND_STEPS: u32 = 4;
+
+fn firmware_action(firmware: &mut VirtualFirmware, sub: u32, body: &[u8]) -> Result<Vec<u8>> {
+    if !firmware.updating {
+        bail!("Firmware Action {} sent outside of an update", sub);
+    }
+
+    match sub {
+        // Erase, the body contains the size of the partition to erase
+        2 => {
+            let size = LittleEndian::read_u32(single(body, 4)?);
+            firmware.partition = vec![0; size as usize];
+            firmware.erase_progress = 0;
+            firmware.finalised = false;
+            Ok(vec![])
+        }
+
+        // Poll Erase
+        3 => {
+            firmware.erase_progress = firmware.erase_progress.saturating_add(ERASE_STEP);
+            Ok(vec![firmware.erase_progress])
+        }
+
+        // Send, (offset, length, data)
+        4 => {
+            if firmware.erase_progress != 0xff {
+                bail!("Firmware Chunk sent before Erase completed");
+            }
+            if body.len() < 8 {
+                bail!("Invalid Firmware Chunk Length: {}", body.len());
+            }
+            let (offset, length) = offset_length(body);
+            let data = &body[8..];
+            if data.len() != length || length > 1016 {
+                bail!("Invalid Firmware Chunk Length: {}", data.len());
+            }
+
+            let Some(target) = firmware.partition.get_mut(offset..offset + length) else {
+                bail!("Firmware Chunk outside of the partition: {:#x}", offset);
+            };
+            target.copy_from_slice(data);
+
+            if firmware.corrupt_chunks > 0 && length > 0 {
+                firmware.corrupt_chunks -= 1;
+                target[0] = target[0].wrapping_add(1);
+            }
+            Ok(vec![])
+        }
+
+        // Validate, returns the checksum of (offset, length)
+        6 => {
+            let (offset, length) = offset_length(single(body, 8)?);
+            let Some(data) = firmware.partition.get(offset..offset + length) else {
+                bail!("Validation outside of the partition: {:#x}", offset);
+            };
+
+            let mut response = vec![0; 4];
+            LittleEndian::write_u32(&mut response, checksum(data));
+            Ok(response)
+        }
+        _ => bail!("Unknown Firmware Action: {}", sub),
+    }
+}
+
+fn firmware_command(firmware: &mut VirtualFirmware, sub: u32, body: &[u8]) -> Result<Vec<u8>> {
+    if sub != 0 && !firmware.updating {
+        bail!("Firmware Command {} sent outside of an update", sub);
+    }
+
+    match sub {
+        // Start
+        0 => {
+            *firmware = VirtualFirmware {
+                updating: true,
+                flashed: firmware.flashed.take(),
+                corrupt_chunks: firmware.corrupt_chunks,
+                ..Default::default()
+            };
+            Ok(vec![])
+        }
+
+        // Verify and Finalise, these need the full image size
+        1 | 3 => {
+            let size = LittleEndian::read_u32(single(body, 4)?);
+            if size as usize != firmware.partition.len() {
+                bail!("Firmware Size Mismatch: {}", size);
+            }
+            firmware.running = Some((sub, 0));
+            Ok(vec![])
+        }
+
+        // Abort
+        2 => {
+            firmware.updating = false;
+            firmware.running = None;
+            Ok(vec![])
+        }
+
+        // Reboot, only the finalised image makes it into 'flash'
+        4 => {
+            if firmware.finalised {
+                firmware.flashed = Some(firmware.partition.clone());
+            }
+            firmware.updating = false;
+            Ok(vec![])
+        }
+
+        // Poll, returns (state, done, total)
+        5 => {
+            let Some((command, done)) = firmware.running else {
+                bail!("Firmware Poll without a running command");
+            };
+
+            let total = firmware.partition.len() as u32;
+            let done = (done + total.div_ceil(COMMAND_STEPS)).min(total);
+            let state = if done < total {
+                firmware.running = Some((command, done));
+                0
+            } else {
+                firmware.running = None;
+                firmware.finalised = command == 3;
+                1
+            };
+
+            let mut response = vec![0; 12];
+            LittleEndian::write_u32(&mut response[0..4], state);
+            LittleEndian::write_u32(&mut response[4..8], done);
+            LittleEndian::write_u32(&mut response[8..12], total);
+            Ok(response)
+        }
+        _ => bail!("Unknown Firmware Command: {}", sub),
+    }
+}
+
+fn offset_length(body: &[u8]) -> (usize, usize) {
+    let offset = LittleEndian::read_u32(&body[0..4]) as usize;
+    let length = LittleEndian::read_u32(&body[4..8]) as usize;
+    (offset, length)
+}
+
+fn single(body: &[u8], length: usize) -> Result<&[u8]> {
+    if body.len() != length {
+        bail!(
+            "Invalid Body Length, Expected {}, got {}",
+            length,
+            body.len()
+        );
+    }
+    Ok(body)
+}
+
+fn pairs(body: &[u8]) -> Result<std::slice::ChunksExact<'_, u8>> {
+    let chunks = body.chunks_exact(8);
+    if !chunks.remainder().is_empty() {
+        bail!("Invalid Key / Value Length: {}", body.len());
+    }
+    Ok(chunks)
+}
+
+fn fader_index(sub: u32) -> Result<usize> {
+    if sub > 3 {
+        bail!("Invalid Fader: {}", sub);
+    }
+    Ok(sub as usize)
+}
+
+fn encoder_index(sub: u32) -> Result<usize> {
+    if sub > 3 {
+        bail!("Invalid Encoder: {}", sub);
+    }
+    Ok(sub as usize)
+}
@@ -0,0 +1,72 @@
+/*
+   A Virtual GoXLR, this behaves as closely to a real GoXLR as possible, decoding commands sent
+   to it and maintaining an in-memory model of the device so that the daemon, CLI and UI can all
+   be used without any hardware attached.
+
+   Virtual devices are defined by the GOXLR_VIRTUAL_DEVICES environment variable as a comma
+   separated list of 'type[:serial]' entries, for example:
+     GOXLR_VIRTUAL_DEVICES=full:S220202153DI7,mini
+
+   This is only compiled in with the 'virtual-device' feature, and with 'default-virtual-device'
+   a single Full device is also created when the variable isn't set.
+
+   Buttons, faders and encoders can be driven through the VirtualController (see control.rs), or
+   by pointing GOXLR_VIRTUAL_SCRIPT at an input script (see script.rs) to run on startup.
+*/
+
+use std::env;
+
+use log::warn;
+
+use goxlr_shared::device::DeviceType;
+
+use crate::{USBLocation, VirtualUSB};
+
+pub mod control;
+pub(crate) mod device;
+mod executor;
+mod script;
+mod state;
+
+static DEVICE_VARIABLE: &str = "GOXLR_VIRTUAL_DEVICES";
+
+pub fn get_devices() -> Vec<USBLocation> {
+    let definition = match env::var(DEVICE_VARIABLE) {
+        Ok(value) => value,
+        Err(_) if cfg!(feature = "default-virtual-device") => String::from("full"),
+        Err(_) => String::new(),
+    };
+
+    let mut list = control::get_attached();
+    for (index, entry) in definition.split(',').map(str::trim).enumerate() {
+        if entry.is_empty() {
+            continue;
+        }
+
+        let (device_type, serial) = match entry.split_once(':') {
+            Some((device_type, serial)) => (device_type, Some(serial.to_string())),
+            None => (entry, None),
+        };
+
+        let device_type = match device_type.to_lowercase().as_str() {
+            "full" => DeviceType::Full,
+            "mini" => DeviceType::Mini,
+            _ => {
+                warn!("Unknown Virtual Device Type: {}", device_type);
+                continue;
+            }
+        };
+
+        let serial = serial.unwrap_or_else(|| format!("VIRTUAL{:06}", index + 1));
+        list.push(USBLocation {
+            lib_usb: None,
+            windows_usb: None,
+            virtual_device: Some(VirtualUSB {
+                serial,
+                device_type,
+            }),
+            replay: None,
+        });
+    }
+    list
+}
@@ -0,0 +1,125 @@
+/*
+   A timeline of physical inputs to play against a Virtual GoXLR. Scripts can either be built in
+   code, or parsed from a simple line based format, one input per line:
+
+     # Hold the first mute button long enough to trigger a 'Hold' action
+     hold Fader1Mute 600
+     press Fader2Mute
+     wait 100
+     release Fader2Mute
+     fader A 128
+     encoder Pitch 12
+     turn Reverb -3
+
+   Durations are in milliseconds, blank lines and lines starting with '#' are ignored.
+*/
+
+use std::fmt::Debug;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use strum::IntoEnumIterator;
+
+use goxlr_shared::interaction::{InteractiveButtons, InteractiveEncoders, InteractiveFaders};
+
+#[derive(Debug, Copy, Clone)]
+pub enum VirtualInput {
+    Press(InteractiveButtons),
+    Release(InteractiveButtons),
+    Fader(InteractiveFaders, u8),
+    Encoder(InteractiveEncoders, i8),
+    Turn(InteractiveEncoders, i8),
+    Wait(Duration),
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Script {
+    steps: Vec<VirtualInput>,
+}
+
+impl Script {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn steps(&self) -> &[VirtualInput] {
+        &self.steps
+    }
+
+    pub fn then(mut self, input: VirtualInput) -> Self {
+        self.steps.push(input);
+        self
+    }
+
+    pub fn press(self, button: InteractiveButtons) -> Self {
+        self.then(VirtualInput::Press(button))
+    }
+
+    pub fn release(self, button: InteractiveButtons) -> Self {
+        self.then(VirtualInput::Release(button))
+    }
+
+    pub fn hold(self, button: InteractiveButtons, duration: Duration) -> Self {
+        self.press(button).wait(duration).release(button)
+    }
+
+    pub fn fader(self, fader: InteractiveFaders, position: u8) -> Self {
+        self.then(VirtualInput::Fader(fader, position))
+    }
+
+    pub fn encoder(self, encoder: InteractiveEncoders, value: i8) -> Self {
+        self.then(VirtualInput::Encoder(encoder, value))
+    }
+
+    pub fn turn(self, encoder: InteractiveEncoders, clicks: i8) -> Self {
+        self.then(VirtualInput::Turn(encoder, clicks))
+    }
+
+    pub fn wait(self, duration: Duration) -> Self {
+        self.then(VirtualInput::Wait(duration))
+    }
+
+    pub fn parse(script: &str) -> Result<Self> {
+        let mut result = Script::new();
+
+        for (index, line) in script.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            result = parse_line(result, &parts)
+                .with_context(|| format!("Error on line {}: {}", index + 1, line))?;
+        }
+        Ok(result)
+    }
+}
+
+fn parse_line(script: Script, parts: &[&str]) -> Result<Script> {
+    let script = match parts {
+        ["press", button] => script.press(parse_enum(button)?),
+        ["release", button] => script.release(parse_enum(button)?),
+        ["hold", button, millis] => script.hold(parse_enum(button)?, parse_millis(millis)?),
+        ["fader", fader, position] => script.fader(parse_enum(fader)?, position.parse()?),
+        ["encoder", encoder, value] => script.encoder(parse_enum(encoder)?, value.parse()?),
+        ["turn", encoder, clicks] => script.turn(parse_enum(encoder)?, clicks.parse()?),
+        ["wait", millis] => script.wait(parse_millis(millis)?),
+        _ => bail!("Unknown Instruction"),
+    };
+    Ok(script)
+}
+
+fn parse_millis(value: &str) -> Result<Duration> {
+    Ok(Duration::from_millis(value.parse()?))
+}
+
+/// None of the interaction enums implement FromStr, so simply match against their Debug names
+fn parse_enum<T: IntoEnumIterator + Debug>(value: &str) -> Result<T> {
+    for variant in T::iter() {
+        if format!("{:?}", variant).eq_ignore_ascii_case(value) {
+            return Ok(variant);
+        }
+    }
+    bail!("Unknown Value: {}", value)
+}
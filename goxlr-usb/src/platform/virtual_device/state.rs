@@ -0,0 +1,131 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Instant;
+
+// The number of requests kept for inspection, the oldest are dropped beyond this
+const MAX_COMMANDS: usize = 4096;
+
+/// The in-memory model of a virtual GoXLR. Values are stored in the same form they're sent to
+/// the device, indexed by their on-wire ids, so that this can be compared directly against a
+/// capture from a real GoXLR.
+#[derive(Debug)]
+pub(crate) struct VirtualState {
+    /// Channel Volumes, indexed by ChannelList
+    pub volumes: [u8; 11],
+
+    /// Channel Mute States, indexed by ChannelList
+    pub mute_states: [u8; 11],
+
+    /// Sub Mix Volumes, indexed by SubMixChannelList - 0x10
+    pub submix_volumes: [u8; 8],
+
+    /// Raw routing tables, keyed by the RoutingInputChannel id
+    pub routing: BTreeMap<u32, Vec<u8>>,
+
+    /// Fader Assignments (as ChannelList) and display modes ([gradient, meter])
+    pub fader_channels: [u32; 4],
+    pub fader_display: [[u8; 2]; 4],
+
+    /// The raw Colour Map, Button Display States, and Scribble bitmaps
+    pub colour_map: Vec<u8>,
+    pub button_display: Vec<u8>,
+    pub scribbles: [Vec<u8>; 4],
+
+    /// Encoder values and modes, as set by the host
+    pub encoder_values: [i8; 4],
+    pub encoder_modes: [Vec<u8>; 4],
+
+    /// Microphone Parameters (stored as raw u32 as some are ints, and some are floats) and Effects
+    pub mic_params: BTreeMap<u32, u32>,
+    pub mic_effects: BTreeMap<u32, i32>,
+
+    /// Animation, Mix Assignments and Monitored Mix
+    pub animation: Vec<u8>,
+    pub channel_mixes: Vec<u8>,
+    pub monitored_mix: u8,
+
+    /// The 'physical' state of the device, which buttons are held, and where the faders are
+    pub pressed_buttons: u32,
+    pub fader_positions: [u8; 4],
+
+    /// Used to generate a microphone level which changes over time
+    pub created: Instant,
+
+    /// The progress of any firmware update being performed
+    pub firmware: VirtualFirmware,
+
+    /// The most recent requests received by the device (other than polls), and the number of
+    /// upcoming requests which should fail, used to check what the daemon sends and how it
+    /// recovers. Only the most recent are kept, so a device nobody is reading from doesn't
+    /// grow forever.
+    pub commands: VecDeque<CommandRecord>,
+    pub failures: usize,
+}
+
+/// A simulated firmware update. Erase and the VERIFY / FINALISE commands each take a few polls
+/// to complete, and the update partition can be checked against what's been sent.
+#[derive(Debug, Default)]
+pub(crate) struct VirtualFirmware {
+    /// Set by START, and cleared by ABORT or REBOOT
+    pub updating: bool,
+
+    /// The update partition, and the erase progress (0xFF once it's been erased)
+    pub partition: Vec<u8>,
+    pub erase_progress: u8,
+
+    /// The (sub command, done) of a running VERIFY or FINALISE
+    pub running: Option<(u32, u32)>,
+    pub finalised: bool,
+
+    /// The last image which made it through the whole update, and the number of upcoming chunks
+    /// which should be corrupted when written
+    pub flashed: Option<Vec<u8>>,
+    pub corrupt_chunks: usize,
+}
+
+/// A single request received by a virtual device, as raw command id and body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandRecord {
+    pub command: u32,
+    pub body: Vec<u8>,
+}
+
+impl Default for VirtualState {
+    fn default() -> Self {
+        Self {
+            volumes: [0; 11],
+            mute_states: [0; 11],
+            submix_volumes: [0; 8],
+            routing: BTreeMap::new(),
+            fader_channels: [0; 4],
+            fader_display: [[0; 2]; 4],
+            colour_map: vec![],
+            button_display: vec![],
+            scribbles: Default::default(),
+            encoder_values: [0; 4],
+            encoder_modes: Default::default(),
+            mic_params: BTreeMap::new(),
+            mic_effects: BTreeMap::new(),
+            animation: vec![],
+            channel_mixes: vec![],
+            monitored_mix: 0,
+            pressed_buttons: 0,
+            fader_positions: [0; 4],
+            created: Instant::now(),
+            firmware: Default::default(),
+            commands: VecDeque::new(),
+            failures: 0,
+        }
+    }
+}
+
+impl VirtualState {
+    pub fn record_command(&mut self, command: u32, body: &[u8]) {
+        if self.commands.len() == MAX_COMMANDS {
+            self.commands.pop_front();
+        }
+        self.commands.push_back(CommandRecord {
+            command,
+            body: body.to_vec(),
+        });
+    }
+}
@@ -4,6 +4,7 @@
 */
 
 use std::cmp::Ordering;
+use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering as AtomicOrder;
 use std::sync::Arc;
@@ -21,7 +22,9 @@ use goxlr_shared::version::VersionNumber;
 
 use crate::events::commands::{BasicResultCommand, CommandSender};
 use crate::events::interaction::InteractionEvent;
+use crate::handlers::firmware::FirmwareUpdater;
 use crate::handlers::state_tracker::StateTracker;
+use crate::platform::capture::recorder::CaptureGoXLR;
 use crate::platform::common::device::GoXLRConfiguration;
 use crate::platform::{from_device, FullGoXLRDevice};
 use crate::types::channels::MixOutputChannel;
@@ -63,6 +66,9 @@ impl GoXLRUSBDevice {
         // Ok, firstly, we need to create a GoXLR device from our Location..
         debug!("[RUNNER]{} Initialising Device..", self.config.device);
         let mut device = from_device(config).await?;
+        if let Some(directory) = &self.config.capture {
+            device = CaptureGoXLR::wrap(device, directory, &self.config.device);
+        }
         device.run().await?;
 
         debug!(
@@ -97,6 +103,16 @@ impl GoXLRUSBDevice {
                     }
                 }
                 Some(command) = self.config.command_receiver.recv() => {
+                    if let CommandSender::UpdateFirmware(image, progress) = command {
+                        // Once an update has started, the device needs to be brought back up
+                        // from scratch, so bail and let the device be picked back up.
+                        let updater = FirmwareUpdater::new(&mut device, progress);
+                        if updater.run(&image).await {
+                            device.stop().await;
+                            bail!("Firmware Update Performed, device needs re-initialising");
+                        }
+                        continue;
+                    }
                     self.handle_command(command, &mut device).await;
                 }
                 _ = &mut self.config.stop => {
@@ -142,6 +158,11 @@ impl GoXLRUSBDevice {
                 BasicResultCommand::SetScribble(fader, data) => {
                     let _ = responder.send(device.set_scribble(fader, data).await);
                 }
+                BasicResultCommand::SetAnimation(mode, mod1, mod2, waterfall) => {
+                    let (mode, waterfall) = (mode.into(), waterfall.into());
+                    let result = device.set_animation_mode(mode, mod1, mod2, waterfall).await;
+                    let _ = responder.send(result);
+                }
                 BasicResultCommand::SetSubMixVolume(source, volume) => {
                     let _ = responder.send(device.set_submix_volume(source.into(), volume).await);
                 }
@@ -156,6 +177,9 @@ impl GoXLRUSBDevice {
 
                     let _ = responder.send(device.set_submix_mix(a, b).await);
                 }
+                BasicResultCommand::SetMonitorMix(mix) => {
+                    let _ = responder.send(device.set_monitor_mix(mix.into()).await);
+                }
                 BasicResultCommand::SetMicGain(mic_type, gain) => {
                     let _ = responder.send(device.set_microphone_gain(mic_type, gain).await);
                 }
@@ -176,6 +200,9 @@ impl GoXLRUSBDevice {
                     let _ = responder.send(device.set_mic_effects(map).await);
                 }
             },
+            CommandSender::UpdateFirmware(..) => {
+                // This takes over the device, so is handled directly by the event loop
+            }
             CommandSender::GetMicLevel(responder) => {
                 let _ = responder.send(device.get_microphone_level().await);
             }
@@ -269,6 +296,9 @@ pub struct GoXLRUSBConfiguration {
     pub device_event: mpsc::Sender<DeviceMessage>,
     pub command_receiver: mpsc::Receiver<CommandSender>,
     pub stop: oneshot::Receiver<()>,
+
+    /// When set, all requests sent to the device are captured to a file in this directory
+    pub capture: Option<PathBuf>,
 }
 
 #[derive(Debug, Copy, Clone)]
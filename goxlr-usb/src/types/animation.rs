@@ -0,0 +1,41 @@
+use goxlr_shared::animation::{AnimationMode, WaterfallDirection};
+
+#[derive(Debug, Copy, Clone)]
+pub enum DeviceAnimationMode {
+    RetroRainbow = 0x00,
+    RainbowDark = 0x01,
+    RainbowBright = 0x02,
+    Simple = 0x03,
+    Ripple = 0x04,
+    None = 0x05,
+}
+
+impl From<AnimationMode> for DeviceAnimationMode {
+    fn from(value: AnimationMode) -> Self {
+        match value {
+            AnimationMode::RetroRainbow => DeviceAnimationMode::RetroRainbow,
+            AnimationMode::RainbowDark => DeviceAnimationMode::RainbowDark,
+            AnimationMode::RainbowBright => DeviceAnimationMode::RainbowBright,
+            AnimationMode::Simple => DeviceAnimationMode::Simple,
+            AnimationMode::Ripple => DeviceAnimationMode::Ripple,
+            AnimationMode::None => DeviceAnimationMode::None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum DeviceWaterfallDirection {
+    Down = 0x00,
+    Up = 0x01,
+    Off = 0x02,
+}
+
+impl From<WaterfallDirection> for DeviceWaterfallDirection {
+    fn from(value: WaterfallDirection) -> Self {
+        match value {
+            WaterfallDirection::Down => DeviceWaterfallDirection::Down,
+            WaterfallDirection::Up => DeviceWaterfallDirection::Up,
+            WaterfallDirection::Off => DeviceWaterfallDirection::Off,
+        }
+    }
+}
@@ -1,3 +1,4 @@
+pub(crate) mod animation;
 pub(crate) mod buttons;
 pub(crate) mod channels;
 pub(crate) mod colours;